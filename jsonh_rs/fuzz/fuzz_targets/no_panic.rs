@@ -0,0 +1,51 @@
+#![no_main]
+
+use jsonh_rs::*;
+use libfuzzer_sys::fuzz_target;
+
+// Asserts that reading, parsing, formatting and linting never panic on arbitrary input.
+// Run with `cargo fuzz run no_panic` from the `jsonh_rs` directory.
+fuzz_target!(|data: &[u8]| {
+    let Ok(source) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let reader_options: [JsonhReaderOptions; 3] = [
+        JsonhReaderOptions::new(),
+        JsonhReaderOptions::new().with_version(JsonhVersion::V1).with_parse_single_element(true),
+        JsonhReaderOptions::new().incomplete_inputs(true).with_lazy_unescaping(true).with_max_allocation(Some(4096)),
+    ];
+    for options in reader_options {
+        let _ = JsonhReader::parse_element_from_str(source, options);
+        let _ = JsonhReader::from_str(source, options).read_element().count();
+        let _ = JsonhReader::from_str(source, options).parse_json(true, Some("  "));
+        let _ = JsonhReader::from_str(source, options).parse_json_with_source_map(true, None);
+        let _ = JsonhReader::from_str(source, options).parse_element_with_comments();
+        let _ = JsonhReader::from_str(source, options).find_property_value("a");
+        let _ = JsonhReader::from_str(source, options).skip_value();
+        let _ = jsonh_rs::transcode_to_json(&mut JsonhReader::from_str(source, options), Vec::new());
+        let _ = jsonh_rs::parse_with_recovery(source, options);
+        let _ = JsonhLinter::new(options).fix(source);
+
+        // Push parser (in two chunks)
+        let mut parser: JsonhPushParser = JsonhPushParser::new(options);
+        let middle: usize = (0..=source.len() / 2).rev().find(|index| source.is_char_boundary(*index)).unwrap_or(0);
+        parser.feed(&source[..middle]);
+        parser.feed(&source[middle..]);
+        let _ = parser.finish();
+        let _ = parser.drain_tokens();
+    }
+
+    let _ = jsonh_rs::from_str::<Value>(source);
+    let _ = jsonh_rs::get(source, "a[0]");
+    let _ = jsonh_rs::detect_format(source);
+
+    let formatter_options: JsonhFormatterOptions = JsonhFormatterOptions::new()
+        .with_quoteless_strings(Some(true))
+        .with_multi_quoted_strings(Some(0));
+    let _ = JsonhFormatter::format(source, formatter_options);
+    let _ = JsonhFormatter::infer_options(source, formatter_options);
+    let _ = JsonhFormatter::sort_keys(source, formatter_options);
+    let _ = jsonh_rs::check_round_trip(source, formatter_options);
+    let _ = JsonhNumberParser::parse(source.to_string());
+});
\ No newline at end of file
@@ -0,0 +1,68 @@
+//! Command-line tool for comparing and patching JSONH files.
+//! 
+//! ```
+//! jsonh diff [--json] a.jsonh b.jsonh
+//! jsonh patch file.jsonh patch.json
+//! ```
+
+use std::env;
+use std::fs;
+use std::process::ExitCode;
+
+use jsonh_rs::*;
+
+const USAGE: &str = "Usage:
+    jsonh diff [--json] <a> <b>     Print the changes from <a> to <b>, ignoring formatting (exits with 1 if they differ)
+    jsonh patch <file> <patch>      Print <file> with the JSON Patch (RFC 6902) operations in <patch> applied";
+
+fn main() -> ExitCode {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let arguments: Vec<&str> = arguments.iter().map(String::as_str).collect();
+
+    let result: Result<ExitCode, String> = match arguments.as_slice() {
+        ["diff", a, b] => diff_command(a, b, false),
+        ["diff", "--json", a, b] | ["diff", a, b, "--json"] => diff_command(a, b, true),
+        ["patch", file, patch] => patch_command(file, patch),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+    match result {
+        Ok(exit_code) => return exit_code,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::from(2);
+        },
+    }
+}
+
+fn diff_command(a_path: &str, b_path: &str, json: bool) -> Result<ExitCode, String> {
+    let a: Value = read_file(a_path)?;
+    let b: Value = read_file(b_path)?;
+    let operations: Vec<JsonhPatchOperation> = diff(&a, &b);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&patch_to_value(&operations)).map_err(|error| error.to_string())?);
+    }
+    else {
+        for operation in &operations {
+            println!("{operation}");
+        }
+    }
+    return Ok(if operations.is_empty() { ExitCode::SUCCESS } else { ExitCode::from(1) });
+}
+fn patch_command(file_path: &str, patch_path: &str) -> Result<ExitCode, String> {
+    let mut value: Value = read_file(file_path)?;
+    let patch_source: String = fs::read_to_string(patch_path).map_err(|error| format!("{patch_path}: {error}"))?;
+    let operations: Vec<JsonhPatchOperation> = parse_patch(&patch_source, JsonhReaderOptions::new())
+        .map_err(|error| format!("{patch_path}: {error}"))?;
+
+    apply_patch(&mut value, &operations).map_err(|error| format!("{patch_path}: {error}"))?;
+    println!("{}", to_string_pretty(&value).map_err(|error| error.to_string())?);
+    return Ok(ExitCode::SUCCESS);
+}
+fn read_file(path: &str) -> Result<Value, String> {
+    let source: String = fs::read_to_string(path).map_err(|error| format!("{path}: {error}"))?;
+    return JsonhReader::parse_element_from_str(&source, JsonhReaderOptions::new()).map_err(|error| format!("{path}: {error}"));
+}
\ No newline at end of file
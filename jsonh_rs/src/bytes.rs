@@ -0,0 +1,55 @@
+//! A serde adapter for binary data.
+//! 
+//! ```
+//! struct Attachment {
+//!     #[serde(with = "jsonh_rs::bytes")]
+//!     data: Vec<u8>, // "SGk="
+//! }
+//! ```
+//! 
+//! `JsonhDeserializer` decodes strings using its bytes encoding (base64 by default).
+//! Other deserializers can provide bytes, arrays of bytes, or base64 strings.
+
+use std::fmt;
+
+use serde::Deserializer;
+use serde::Serializer;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+
+use crate::JsonhBytesEncoding;
+
+/// Serializes the binary data as bytes.
+pub fn serialize<T: AsRef<[u8]>, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    return serializer.serialize_bytes(value.as_ref());
+}
+/// Deserializes the binary data from bytes, an array of bytes, or an encoded string.
+pub fn deserialize<'de, T: From<Vec<u8>>, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    return deserializer.deserialize_byte_buf(BytesVisitor).map(T::from);
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return formatter.write_str("binary data");
+    }
+    fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        return Ok(value.to_vec());
+    }
+    fn visit_byte_buf<E: serde::de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        return Ok(value);
+    }
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        return JsonhBytesEncoding::Base64.decode(value).map_err(E::custom);
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        return Ok(bytes);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+
+/// A deserialized value along with the comments that preceded it in the source.
+/// 
+/// ```
+/// {
+///     // The port to listen on.
+///     port: 8080
+/// }
+/// ```
+/// ```
+/// struct Config {
+///     port: Commented<u16>, // comments: ["The port to listen on."], value: 8080
+/// }
+/// ```
+/// 
+/// Comments after the previous value on the same line are also captured, since they come before the value.
+/// 
+/// Comments are only captured by `JsonhDeserializer`. Other deserializers will fail to deserialize a `Commented`.
+/// Serializing a `Commented` serializes the value only.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Commented<T> {
+    /// The contents of the comments before the value (or its property name), trimmed of whitespace.
+    pub comments: Vec<String>,
+    /// The value itself.
+    pub value: T,
+}
+
+impl<T> Commented<T> {
+    /// Constructs a value with the given comments.
+    pub fn new(comments: Vec<String>, value: T) -> Self {
+        return Self { comments: comments, value: value };
+    }
+    /// Returns the value, discarding the comments.
+    pub fn into_inner(self) -> T {
+        return self.value;
+    }
+}
+
+pub(crate) const COMMENTED_NAME: &str = "$__jsonh_private_Commented";
+pub(crate) const COMMENTED_COMMENTS_FIELD: &str = "$__jsonh_private_comments";
+pub(crate) const COMMENTED_VALUE_FIELD: &str = "$__jsonh_private_value";
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Commented<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return deserializer.deserialize_struct(COMMENTED_NAME, &[COMMENTED_COMMENTS_FIELD, COMMENTED_VALUE_FIELD], CommentedVisitor { marker: PhantomData });
+    }
+}
+
+impl<T: Serialize> Serialize for Commented<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return self.value.serialize(serializer);
+    }
+}
+
+struct CommentedVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for CommentedVisitor<T> {
+    type Value = Commented<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return formatter.write_str("a commented value");
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        // Comments
+        if map.next_key::<&str>()? != Some(COMMENTED_COMMENTS_FIELD) {
+            return Err(serde::de::Error::custom("Expected comments of commented value"));
+        }
+        let comments: Vec<String> = map.next_value()?;
+
+        // Value
+        if map.next_key::<&str>()? != Some(COMMENTED_VALUE_FIELD) {
+            return Err(serde::de::Error::custom("Expected commented value"));
+        }
+        let value: T = map.next_value()?;
+
+        return Ok(Commented::new(comments, value));
+    }
+}
\ No newline at end of file
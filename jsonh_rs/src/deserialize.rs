@@ -0,0 +1,66 @@
+//! Functions for deserializing JSONH directly into types implementing `Deserialize`, without building a `Value` first.
+//!
+//! ```
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! let config: Config = jsonh_rs::from_str("host: localhost, port: 8080")?;
+//! ```
+
+use serde::de::DeserializeOwned;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhDeserializer;
+use crate::JsonhError;
+
+/// Deserializes a single JSONH element from a string slice.
+/// 
+/// Errors if the source contains anything other than a single element and comments.
+/// Errors include the path and the line and column numbers of the element that failed (see `JsonhError`).
+pub fn from_str<T: DeserializeOwned>(source: &str) -> Result<T, JsonhError> {
+    return from_str_with_options(source, JsonhReaderOptions::new());
+}
+/// Deserializes a single JSONH element from a string slice with the given options.
+/// 
+/// See `from_str`.
+pub fn from_str_with_options<T: DeserializeOwned>(source: &str, options: JsonhReaderOptions) -> Result<T, JsonhError> {
+    return deserialize_element(&mut JsonhReader::from_str(source, options), Some(source)).map_err(|error| error.with_source(source));
+}
+/// Deserializes the next JSONH element from the reader, ensuring that no other elements follow it.
+/// 
+/// Since the source text is unknown, `JsonhRawValue` cannot be deserialized (see `JsonhDeserializer::with_source`).
+pub fn from_reader<T: DeserializeOwned>(reader: &mut JsonhReader<'_>) -> Result<T, JsonhError> {
+    return deserialize_element(reader, None);
+}
+
+fn deserialize_element<T: DeserializeOwned>(reader: &mut JsonhReader<'_>, source: Option<&str>) -> Result<T, JsonhError> {
+    let value: T = deserialize_value(reader, source)?;
+
+    // Ensure exactly one element
+    for token_result in reader.read_end_of_elements() {
+        token_result?;
+    }
+
+    return Ok(value);
+}
+pub(crate) fn deserialize_value<T: DeserializeOwned>(reader: &mut JsonhReader<'_>, source: Option<&str>) -> Result<T, JsonhError> {
+    let value_result: Result<T, JsonhError> = {
+        let mut deserializer: JsonhDeserializer<'_> = JsonhDeserializer::new(reader);
+        if let Some(source) = source {
+            deserializer = deserializer.with_source(source);
+        }
+        deserializer.peek_start().and_then(|start| T::deserialize(&mut deserializer).map_err(|error| deserializer.add_context(error, start)))
+    };
+
+    // Locate syntax errors where the reader stopped
+    let value: T = value_result.map_err(|error| match error.position {
+        Some(_) => error,
+        None => error.with_position(reader.char_counter).with_location(reader.line_counter, reader.column_counter),
+    })?;
+
+    return Ok(value);
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+//! Functions for rendering errors as snippets of the source with a caret under the error, for command-line tools and test failures.
+//!
+//! ```
+//! if let Err(error) = jsonh_rs::from_str::<Config>(&source) {
+//!     eprintln!("{}", jsonh_rs::render_error(&source, &error));
+//! }
+//! ```
+//! ```
+//! error: invalid value: integer `70000`, expected u16
+//!  --> 6:15 (servers[2].port)
+//!   |
+//! 6 |         port: 70000
+//!   |               ^
+//! ```
+
+use crate::JsonhError;
+use crate::JsonhLineIndex;
+
+/// Renders the error with the line of the source it occurred on and a caret under its column.
+/// 
+/// If the error has no location (or position), only the message and path are rendered.
+pub fn render_error(source: &str, error: &JsonhError) -> String {
+    let mut snippet: String = format!("error: {}", error.message);
+
+    // Location
+    let location: Option<(u64, u64)> = error.location.or_else(|| error.position.map(|position| crate::spanned::char_location(source, position)));
+    let path: Option<String> = error.path.as_ref().filter(|path| !path.is_root()).map(|path| path.to_string());
+    let Some((line, column)) = location else {
+        if let Some(path) = path {
+            snippet.push_str(&format!("\n --> {path}"));
+        }
+        return snippet;
+    };
+    snippet.push_str(&format!("\n --> {line}:{column}"));
+    if let Some(path) = path {
+        snippet.push_str(&format!(" ({path})"));
+    }
+
+    // Source line
+    let line_text: &str = JsonhLineIndex::new(source).line(line).unwrap_or("");
+    let gutter: String = " ".repeat(line.to_string().len());
+    let padding: String = line_text.chars().take(column.saturating_sub(1) as usize).map(|next| if next == '\t' { '\t' } else { ' ' }).collect();
+    snippet.push_str(&format!("\n{gutter} |\n{line} | {line_text}\n{gutter} | {padding}^"));
+    return snippet;
+}
\ No newline at end of file
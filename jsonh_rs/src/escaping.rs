@@ -0,0 +1,65 @@
+//! Functions for escaping and unescaping the contents of JSONH strings.
+//! 
+//! ```
+//! let escaped: String = jsonh_rs::escape("a\tb", JsonhStringStyle::Double); // a\tb
+//! let unescaped: String = jsonh_rs::unescape("a\\tb")?; // a<tab>b
+//! ```
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhFormatter;
+use crate::JsonhStringStyle;
+
+/// Decodes the escape sequences in the contents of a string (excluding the quotes).
+/// 
+/// ```
+/// cd\x65 -> cde
+/// ```
+pub fn unescape(value: &str) -> Result<String, &'static str> {
+    return JsonhReader::unescape(value);
+}
+/// Escapes the string so that it can be written as the contents of a string in the given style (excluding the quotes).
+/// 
+/// Backslashes, control characters and newlines are always escaped. Quotes are escaped if they match the style.
+/// 
+/// For quoteless strings, reserved characters are also escaped, as is the first character if the string would otherwise be read as a number or named literal.
+/// Leading and trailing whitespace cannot be written in quoteless strings, since it is trimmed.
+/// 
+/// ```
+/// a"b -> a\"b (Double)
+/// 1, 2 -> 1\, 2 (Quoteless)
+/// ```
+pub fn escape(value: &str, style: JsonhStringStyle) -> String {
+    return escape_for_reader(value, style, &JsonhReader::from_str("", JsonhReaderOptions::new()));
+}
+/// Escapes the string so that it can be read by the given reader as the contents of a string in the given style (see `escape`).
+/// 
+/// For quoteless strings, the reserved characters of the reader's version and those registered with `JsonhReader::with_reserved_chars` are escaped:
+/// ```
+/// let reader: JsonhReader = JsonhReader::from_str(source, options.with_version(JsonhVersion::V1)).with_reserved_chars(&['$']);
+/// jsonh_rs::escape_for_reader("@a$b", JsonhStringStyle::Quoteless, &reader); // @a\$b
+/// ```
+pub fn escape_for_reader(value: &str, style: JsonhStringStyle, reader: &JsonhReader<'_>) -> String {
+    match style {
+        JsonhStringStyle::Double => return JsonhFormatter::escape(value, Some('"')),
+        JsonhStringStyle::Single => return JsonhFormatter::escape(value, Some('\'')),
+        JsonhStringStyle::Quoteless => {
+            let mut escaped: String = String::new();
+            for (index, next) in value.chars().enumerate() {
+                // Number or named literal
+                if index == 0 && (next.is_ascii_digit() || matches!(next, '-' | '+' | '.') || matches!(value, "null" | "true" | "false")) {
+                    escaped += &format!("\\u{:04x}", next as u32);
+                }
+                // Reserved character
+                else if next != '\\' && reader.is_reserved_char(next) {
+                    escaped.push('\\');
+                    escaped.push(next);
+                }
+                else {
+                    escaped += &JsonhFormatter::escape(&next.to_string(), None);
+                }
+            }
+            return escaped;
+        },
+    }
+}
\ No newline at end of file
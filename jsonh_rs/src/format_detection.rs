@@ -0,0 +1,200 @@
+//! Functions for guessing the format of a document before parsing it.
+//!
+//! ```
+//! match jsonh_rs::detect_format(source) {
+//!     JsonhFormatGuess::Json => serde_json::from_str(source)?,
+//!     JsonhFormatGuess::JsonhV1 | JsonhFormatGuess::JsonhV2 => JsonhReader::parse_element_from_str(source, JsonhReaderOptions::new())?,
+//!     JsonhFormatGuess::NotJsonh => return Err("Unsupported format"),
+//! }
+//! ```
+
+use std::str::CharIndices;
+
+use crate::JsonhFormatGuess;
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhToken;
+use crate::JsonTokenType;
+use crate::JsonhVersion;
+
+/// The maximum number of tokens read when detecting the format.
+const MAX_TOKENS: usize = 1024;
+/// Characters that are considered whitespace in JSON.
+const JSON_WHITESPACE_CHARS: &[char] = &[' ', '\t', '\n', '\r'];
+
+/// Guesses the format of a single element by reading at most the first 1024 tokens.
+/// 
+/// The lowest version that can read the element is returned, so JSON is preferred over JSONH V1, and JSONH V1 is preferred over JSONH V2.
+/// Since only a limited number of tokens are read, the rest of a large document may be invalid.
+pub fn detect_format(source: &str) -> JsonhFormatGuess {
+    // JSONH V1
+    if let Some(tokens) = read_tokens(source, JsonhVersion::V1) {
+        // Verbatim strings are quoteless strings in JSONH V1
+        if read_tokens(source, JsonhVersion::V2).is_some_and(|v2_tokens| v2_tokens != tokens) {
+            return JsonhFormatGuess::JsonhV2;
+        }
+        if is_json(source, &tokens) {
+            return JsonhFormatGuess::Json;
+        }
+        return JsonhFormatGuess::JsonhV1;
+    }
+    // JSONH V2
+    if read_tokens(source, JsonhVersion::V2).is_some() {
+        return JsonhFormatGuess::JsonhV2;
+    }
+    return JsonhFormatGuess::NotJsonh;
+}
+
+fn read_tokens(source: &str, version: JsonhVersion) -> Option<Vec<JsonhToken>> {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(source, JsonhReaderOptions::new().with_version(version));
+    let mut tokens: Vec<JsonhToken> = Vec::new();
+
+    // Read element
+    for token_result in reader.read_element() {
+        tokens.push(token_result.ok()?);
+        if tokens.len() >= MAX_TOKENS {
+            return Some(tokens);
+        }
+    }
+    // Read end of element
+    for token_result in reader.read_end_of_elements() {
+        tokens.push(token_result.ok()?);
+        if tokens.len() >= MAX_TOKENS {
+            return Some(tokens);
+        }
+    }
+    return Some(tokens);
+}
+fn is_json(source: &str, tokens: &[JsonhToken]) -> bool {
+    let mut char_offsets: CharOffsets<'_> = CharOffsets { source: source, char_indices: source.char_indices(), position: 0, offset: 0 };
+    let mut previous: Option<&JsonhToken> = None;
+
+    for token in tokens {
+        // Implicit braces of braceless objects
+        if matches!(token.json_type, JsonTokenType::StartObject | JsonTokenType::EndObject) && token.start == token.end {
+            return false;
+        }
+
+        // Separator between tokens
+        let gap_start: usize = char_offsets.offset;
+        let token_start: usize = char_offsets.advance_to(token.start);
+        let separator: String = source[gap_start..token_start].chars().filter(|next| !JSON_WHITESPACE_CHARS.contains(next)).collect();
+        let expected_separator: &str = match (previous.map(|previous| previous.json_type), token.json_type) {
+            (None, _) => "",
+            (Some(JsonTokenType::PropertyName), _) => ":",
+            (Some(JsonTokenType::StartObject | JsonTokenType::StartArray), _) => "",
+            (_, JsonTokenType::EndObject | JsonTokenType::EndArray) => "",
+            _ => ",",
+        };
+        if separator != expected_separator {
+            return false;
+        }
+
+        // Raw token
+        let token_end: usize = char_offsets.advance_to(token.end);
+        let raw: &str = &source[token_start..token_end];
+        let is_valid: bool = match token.json_type {
+            JsonTokenType::Comment => false,
+            JsonTokenType::String | JsonTokenType::PropertyName => is_json_string(raw),
+            JsonTokenType::Number => is_json_number(raw),
+            JsonTokenType::Null => raw == "null",
+            JsonTokenType::True => raw == "true",
+            JsonTokenType::False => raw == "false",
+            _ => true,
+        };
+        if !is_valid {
+            return false;
+        }
+
+        previous = Some(token);
+    }
+
+    // Trailing whitespace
+    return source[char_offsets.offset..].chars().all(|next| JSON_WHITESPACE_CHARS.contains(&next)) || tokens.len() >= MAX_TOKENS;
+}
+fn is_json_string(raw: &str) -> bool {
+    // Double quotes (excluding multi-quoted strings)
+    let Some(contents) = raw.strip_prefix('"').and_then(|raw| raw.strip_suffix('"')) else {
+        return false;
+    };
+    if raw.len() < 2 || raw.starts_with("\"\"\"") {
+        return false;
+    }
+
+    let mut chars: std::str::Chars<'_> = contents.chars();
+    while let Some(next) = chars.next() {
+        // Escape sequence
+        if next == '\\' {
+            match chars.next() {
+                Some('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't') => {},
+                Some('u') => {
+                    if !(0..4).all(|_| chars.next().is_some_and(|digit| digit.is_ascii_hexdigit())) {
+                        return false;
+                    }
+                },
+                _ => return false,
+            }
+        }
+        // Control character
+        else if next < '\u{20}' {
+            return false;
+        }
+    }
+    return true;
+}
+fn is_json_number(raw: &str) -> bool {
+    let bytes: &[u8] = raw.as_bytes();
+    let mut index: usize = 0;
+    let count_digits = |index: usize| -> usize { bytes[index..].iter().take_while(|byte| byte.is_ascii_digit()).count() };
+
+    // Sign
+    if bytes.get(index) == Some(&b'-') {
+        index += 1;
+    }
+    // Integer (without leading zeroes)
+    let integer_digits: usize = count_digits(index);
+    if integer_digits == 0 || (integer_digits > 1 && bytes[index] == b'0') {
+        return false;
+    }
+    index += integer_digits;
+    // Fraction
+    if bytes.get(index) == Some(&b'.') {
+        let fraction_digits: usize = count_digits(index + 1);
+        if fraction_digits == 0 {
+            return false;
+        }
+        index += 1 + fraction_digits;
+    }
+    // Exponent
+    if matches!(bytes.get(index), Some(b'e' | b'E')) {
+        index += 1;
+        if matches!(bytes.get(index), Some(b'+' | b'-')) {
+            index += 1;
+        }
+        let exponent_digits: usize = count_digits(index);
+        if exponent_digits == 0 {
+            return false;
+        }
+        index += exponent_digits;
+    }
+    return index == bytes.len();
+}
+
+/// Converts character positions to byte offsets in a single pass.
+struct CharOffsets<'a> {
+    source: &'a str,
+    char_indices: CharIndices<'a>,
+    position: u64,
+    offset: usize,
+}
+
+impl CharOffsets<'_> {
+    fn advance_to(&mut self, position: u64) -> usize {
+        while self.position < position {
+            self.char_indices.next();
+            self.position += 1;
+            self.offset = self.char_indices.clone().next().map_or(self.source.len(), |(offset, _)| offset);
+        }
+        return self.offset;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,146 @@
+use bumpalo::Bump;
+use bumpalo::collections::Vec as BumpVec;
+use serde_json::{Value, Number};
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhToken;
+use crate::JsonTokenType;
+use crate::JsonhNumberParser;
+use crate::JsonhError;
+
+/// A JSONH element allocated in a bump allocator (requires the `arena` feature).
+/// 
+/// Every string, array and object in the element is allocated in the arena, so the whole element is freed at once when the arena is reset or dropped.
+/// This is potentially useful for parsing many short-lived elements, such as request bodies in a server.
+/// 
+/// ```
+/// let bump: Bump = Bump::new();
+/// let element: JsonhArenaValue<'_> = JsonhArenaValue::parse_from_str("{ a: [1, 2] }", JsonhReaderOptions::new(), &bump)?;
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub enum JsonhArenaValue<'bump> {
+    /// A null literal.
+    /// 
+    /// Example: `null`
+    Null,
+    /// A boolean literal.
+    /// 
+    /// Example: `true`
+    Bool(bool),
+    /// A number.
+    /// 
+    /// Example: `0x10`
+    Number(f64),
+    /// A string.
+    /// 
+    /// Example: `"value"`
+    String(&'bump str),
+    /// An array of items.
+    /// 
+    /// Example: `[1, 2]`
+    Array(BumpVec<'bump, JsonhArenaValue<'bump>>),
+    /// An object of properties in the order they were read.
+    /// 
+    /// Example: `{ a: 1 }`
+    Object(BumpVec<'bump, (&'bump str, JsonhArenaValue<'bump>)>),
+}
+
+impl<'bump> JsonhArenaValue<'bump> {
+    /// Parses a single element from a string slice into the arena.
+    pub fn parse_from_str(source: &str, options: JsonhReaderOptions, bump: &'bump Bump) -> Result<Self, JsonhError> {
+        return Self::parse(&mut JsonhReader::from_str(source, options), bump);
+    }
+    /// Parses a single element from the reader into the arena.
+    /// 
+    /// Unlike `JsonhReader::parse_element`, type hints are not applied.
+    pub fn parse(reader: &mut JsonhReader<'_>, bump: &'bump Bump) -> Result<Self, JsonhError> {
+        return Self::parse_tokens(reader, bump).map_err(|error| reader.locate_error(error));
+    }
+    fn parse_tokens(reader: &mut JsonhReader<'_>, bump: &'bump Bump) -> Result<Self, &'static str> {
+        let mut current_structures: Vec<(Self, Option<&'bump str>)> = Vec::new();
+        let mut current_property_name: Option<&'bump str> = None;
+        let mut root: Option<Self> = None;
+
+        for token_result in reader.read_element().raw() {
+            let token: JsonhToken = token_result?;
+
+            let element: Self = match token.json_type {
+                // Primitives
+                JsonTokenType::Null => Self::Null,
+                JsonTokenType::True => Self::Bool(true),
+                JsonTokenType::False => Self::Bool(false),
+                JsonTokenType::String => Self::String(bump.alloc_str(&token.unescaped_value()?)),
+                JsonTokenType::Number => Self::Number(JsonhNumberParser::parse(token.value)?),
+                // Start Object/Array
+                JsonTokenType::StartObject => {
+                    current_structures.push((Self::Object(BumpVec::new_in(bump)), current_property_name.take()));
+                    continue;
+                },
+                JsonTokenType::StartArray => {
+                    current_structures.push((Self::Array(BumpVec::new_in(bump)), current_property_name.take()));
+                    continue;
+                },
+                // End Object/Array
+                JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                    let Some((structure, property_name)) = current_structures.pop() else {
+                        return Err("Unexpected end of structure");
+                    };
+                    current_property_name = property_name;
+                    structure
+                },
+                // Property Name
+                JsonTokenType::PropertyName => {
+                    current_property_name = Some(bump.alloc_str(&token.value));
+                    continue;
+                },
+                // Comment
+                JsonTokenType::Comment => continue,
+                // Not implemented
+                _ => return Err("Token type not implemented"),
+            };
+
+            // Submit element to parent
+            match (current_structures.last_mut(), current_property_name.take()) {
+                (None, _) => root = Some(element),
+                (Some((Self::Array(items), _)), None) => items.push(element),
+                (Some((Self::Object(properties), _)), Some(property_name)) => properties.push((property_name, element)),
+                (Some((Self::Object(_), _)), None) => return Err("Expected property name in object"),
+                (Some(_), _) => return Err("Unexpected property name in array"),
+            }
+        }
+
+        let Some(root) = root else {
+            return Err("Expected token, got end of input");
+        };
+
+        // Ensure exactly one element
+        if reader.options.parse_single_element {
+            for token_result in reader.read_end_of_elements().raw() {
+                token_result?;
+            }
+        }
+
+        return Ok(root);
+    }
+
+    /// Copies the element out of the arena.
+    /// 
+    /// Errors if a number is infinite or NaN.
+    pub fn to_value(&self) -> Result<Value, &'static str> {
+        match self {
+            Self::Null => return Ok(Value::Null),
+            Self::Bool(boolean) => return Ok(Value::Bool(*boolean)),
+            Self::Number(number) => return Number::from_f64(*number).map(Value::Number).ok_or("Infinity and NaN are not supported"),
+            Self::String(string) => return Ok(Value::String(string.to_string())),
+            Self::Array(items) => return items.iter().map(Self::to_value).collect::<Result<Vec<Value>, &'static str>>().map(Value::Array),
+            Self::Object(properties) => {
+                let mut object: serde_json::Map<String, Value> = serde_json::Map::new();
+                for (name, value) in properties.iter() {
+                    object.insert(name.to_string(), value.to_value()?);
+                }
+                return Ok(Value::Object(object));
+            },
+        }
+    }
+}
\ No newline at end of file
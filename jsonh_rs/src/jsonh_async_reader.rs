@@ -0,0 +1,204 @@
+use std::cell::Ref;
+use std::future;
+use std::io;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures_core::Stream;
+use futures_io::AsyncBufRead;
+use serde_json::Value;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhToken;
+use crate::JsonhCharSource;
+use crate::JsonhPushParser;
+use crate::JsonhReadState;
+use crate::JsonhError;
+
+/// A reader that reads JSONH from an asynchronous source of UTF-8 bytes, such as a network response (requires the `async` feature).
+/// 
+/// ```
+/// let mut reader: JsonhAsyncReader<'_> = JsonhAsyncReader::from_stream(response.bytes_stream(), JsonhReaderOptions::new());
+/// while let Some(token) = reader.next_token().await {
+///     println!("{:?}", token?.json_type);
+/// }
+/// ```
+/// 
+/// Tokens are read from the decoded text with a `JsonhPushParser`, so they are yielded once the text after them has arrived, and errors that may be resolved by the next chunk are only yielded at the end of the input.
+pub struct JsonhAsyncReader<'a> {
+    /// The parser reading tokens from the text decoded so far.
+    pub parser: JsonhPushParser,
+    /// The error returned by the byte source, if reading failed.
+    pub error: Option<io::Error>,
+    /// The chunks of bytes to decode.
+    chunks: Pin<Box<dyn Stream<Item = io::Result<Vec<u8>>> + 'a>>,
+    /// The bytes at the end of the last chunk that are part of an incomplete UTF-8 sequence.
+    undecoded: Vec<u8>,
+    /// Whether an error has been yielded.
+    is_failed: bool,
+}
+
+impl<'a> JsonhAsyncReader<'a> {
+    /// Constructs a reader that reads JSONH from a stream of byte chunks, such as `Bytes` or `Vec<u8>`.
+    pub fn from_stream<B: AsRef<[u8]> + 'a>(chunks: impl Stream<Item = B> + 'a, options: JsonhReaderOptions) -> Self {
+        return Self::from_chunks(Box::pin(ByteChunks { chunks: Box::pin(chunks) }), options);
+    }
+    /// Constructs a reader that reads JSONH from an asynchronous buffered byte reader.
+    /// 
+    /// Tokio readers can be converted with `tokio_util::compat`. If the byte reader fails, the input ends early.
+    pub fn from_async_read(source: impl AsyncBufRead + 'a, options: JsonhReaderOptions) -> Self {
+        return Self::from_chunks(Box::pin(AsyncReadChunks { reader: Box::pin(source) }), options);
+    }
+    fn from_chunks(chunks: Pin<Box<dyn Stream<Item = io::Result<Vec<u8>>> + 'a>>, options: JsonhReaderOptions) -> Self {
+        return Self { parser: JsonhPushParser::new(options), error: None, chunks: chunks, undecoded: Vec::new(), is_failed: false };
+    }
+
+    /// Reads the next token of the element, waiting for more chunks if needed.
+    /// 
+    /// Returns `None` once every token has been read.
+    pub async fn next_token(&mut self) -> Option<Result<JsonhToken, JsonhError>> {
+        loop {
+            if self.is_failed {
+                return None;
+            }
+            match self.parser.read_token() {
+                // Complete token
+                Ok(JsonhReadState::Token(token)) => return Some(Ok(token)),
+                // Next chunk
+                Ok(JsonhReadState::NeedMoreInput) => self.read_chunk(true).await,
+                // End of input
+                Ok(JsonhReadState::EndOfInput) => return None,
+                // Error
+                Err(error) => {
+                    self.is_failed = true;
+                    return Some(Err(error));
+                },
+            }
+        }
+    }
+    /// Reads the rest of the input and parses a single element from it.
+    /// 
+    /// If tokens have already been read with `next_token`, parsing continues after them.
+    pub async fn parse_element(&mut self) -> Result<Value, JsonhError> {
+        while !self.parser.is_finished() {
+            self.read_chunk(false).await;
+        }
+
+        // Continue after tokens read
+        let start: u64 = self.parser.drained_end();
+        let buffer: Ref<'_, str> = self.parser.buffer();
+        let offset: usize = buffer.char_indices().nth(start as usize).map_or(buffer.len(), |(offset, _)| offset);
+        let source: JsonhCharSource<_> = JsonhCharSource { chars: buffer[offset..].chars().peekable(), position: start };
+        let result: Result<Value, JsonhError> = JsonhReader::parse_element_from_source(source, self.parser.options);
+        drop(buffer);
+
+        // Mark input as read
+        self.parser.skip_tokens();
+
+        return result;
+    }
+
+    async fn read_chunk(&mut self, read_tokens: bool) {
+        match future::poll_fn(|context| self.chunks.as_mut().poll_next(context)).await {
+            // Chunk
+            Some(Ok(chunk)) => {
+                let text: String = self.decode(&chunk);
+                if read_tokens {
+                    self.parser.feed(&text);
+                }
+                else {
+                    self.parser.append(&text);
+                }
+            },
+            // Error
+            Some(Err(error)) => {
+                self.error = Some(error);
+                self.finish();
+            },
+            // End of chunks
+            None => {
+                self.finish();
+            },
+        }
+    }
+    fn decode(&mut self, chunk: &[u8]) -> String {
+        self.undecoded.extend_from_slice(chunk);
+
+        let mut text: String = String::new();
+        let mut start: usize = 0;
+        loop {
+            match std::str::from_utf8(&self.undecoded[start..]) {
+                // Valid UTF-8
+                Ok(valid) => {
+                    text.push_str(valid);
+                    start = self.undecoded.len();
+                    break;
+                },
+                Err(error) => {
+                    let valid_end: usize = start + error.valid_up_to();
+                    text.push_str(&String::from_utf8_lossy(&self.undecoded[start..valid_end]));
+                    match error.error_len() {
+                        // Invalid UTF-8 sequence
+                        Some(length) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            start = valid_end + length;
+                        },
+                        // Incomplete UTF-8 sequence
+                        None => {
+                            start = valid_end;
+                            break;
+                        },
+                    }
+                },
+            }
+        }
+        self.undecoded.drain(..start);
+        return text;
+    }
+    fn finish(&mut self) {
+        if !self.undecoded.is_empty() {
+            self.parser.append("\u{FFFD}");
+            self.undecoded.clear();
+        }
+        let _ = self.parser.finish();
+    }
+}
+
+/// Adapts a stream of byte chunks to a stream of owned byte chunks.
+struct ByteChunks<'a, B: AsRef<[u8]>> {
+    chunks: Pin<Box<dyn Stream<Item = B> + 'a>>,
+}
+
+impl<B: AsRef<[u8]>> Stream for ByteChunks<'_, B> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        return self.chunks.as_mut().poll_next(context).map(|chunk| chunk.map(|chunk| Ok(chunk.as_ref().to_vec())));
+    }
+}
+
+/// Adapts an asynchronous buffered byte reader to a stream of owned byte chunks.
+struct AsyncReadChunks<'a> {
+    reader: Pin<Box<dyn AsyncBufRead + 'a>>,
+}
+
+impl Stream for AsyncReadChunks<'_> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let chunk: Vec<u8> = match self.reader.as_mut().poll_fill_buf(context) {
+            Poll::Ready(Ok([])) => return Poll::Ready(None),
+            Poll::Ready(Ok(buffer)) => buffer.to_vec(),
+            Poll::Ready(Err(error)) if error.kind() == io::ErrorKind::Interrupted => {
+                context.waker().wake_by_ref();
+                return Poll::Pending;
+            },
+            Poll::Ready(Err(error)) => return Poll::Ready(Some(Err(error))),
+            Poll::Pending => return Poll::Pending,
+        };
+        self.reader.as_mut().consume(chunk.len());
+        return Poll::Ready(Some(Ok(chunk)));
+    }
+}
\ No newline at end of file
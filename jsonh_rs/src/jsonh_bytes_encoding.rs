@@ -0,0 +1,80 @@
+/// The encoding used to write binary data as strings.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhBytesEncoding {
+    /// Base64 with the standard alphabet. When decoding, the URL-safe alphabet and missing padding are also accepted.
+    /// 
+    /// Example: `"SGk="`
+    Base64 = 0,
+    /// Hexadecimal with two digits per byte. When decoding, uppercase and lowercase digits are accepted.
+    /// 
+    /// Example: `"4869"`
+    Hex = 1,
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl JsonhBytesEncoding {
+    /// Encodes the bytes as a string.
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            JsonhBytesEncoding::Base64 => {
+                let mut result: String = String::with_capacity(bytes.len().div_ceil(3) * 4);
+                for chunk in bytes.chunks(3) {
+                    let group: u32 = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | (*chunk.get(2).unwrap_or(&0) as u32);
+                    for index in 0..4 {
+                        if index <= chunk.len() {
+                            result.push(BASE64_ALPHABET[(group >> (18 - index * 6) & 0b111111) as usize] as char);
+                        }
+                        else {
+                            result.push('=');
+                        }
+                    }
+                }
+                return result;
+            },
+            JsonhBytesEncoding::Hex => {
+                return bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+            },
+        }
+    }
+    /// Decodes the bytes from a string.
+    pub fn decode(self, text: &str) -> Result<Vec<u8>, &'static str> {
+        match self {
+            JsonhBytesEncoding::Base64 => {
+                let digits: &[u8] = text.trim_end_matches('=').as_bytes();
+                if digits.len() % 4 == 1 {
+                    return Err("Invalid base64 length");
+                }
+
+                let mut bytes: Vec<u8> = Vec::with_capacity(digits.len() * 3 / 4);
+                for chunk in digits.chunks(4) {
+                    let mut group: u32 = 0;
+                    for (index, digit) in chunk.iter().enumerate() {
+                        let value: u32 = match digit {
+                            b'A'..=b'Z' => (digit - b'A') as u32,
+                            b'a'..=b'z' => (digit - b'a' + 26) as u32,
+                            b'0'..=b'9' => (digit - b'0' + 52) as u32,
+                            b'+' | b'-' => 62,
+                            b'/' | b'_' => 63,
+                            _ => return Err("Invalid base64 digit"),
+                        };
+                        group |= value << (18 - index * 6);
+                    }
+                    for index in 0..(chunk.len() - 1) {
+                        bytes.push((group >> (16 - index * 8)) as u8);
+                    }
+                }
+                return Ok(bytes);
+            },
+            JsonhBytesEncoding::Hex => {
+                if !text.len().is_multiple_of(2) {
+                    return Err("Invalid hexadecimal length");
+                }
+                return text.as_bytes().chunks(2)
+                    .map(|pair| std::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()).ok_or("Invalid hexadecimal digit"))
+                    .collect();
+            },
+        }
+    }
+}
\ No newline at end of file
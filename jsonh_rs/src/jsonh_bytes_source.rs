@@ -0,0 +1,68 @@
+use crate::JsonhSource;
+
+/// A `JsonhSource` reading UTF-8 text from a byte slice, validating it as it is read.
+/// 
+/// Unlike `JsonhReadSource`, invalid UTF-8 sequences are not replaced. The source ends at the first invalid sequence and the reader returns an error at its position.
+pub struct JsonhBytesSource<'a> {
+    /// The bytes to read UTF-8 text from.
+    pub bytes: &'a [u8],
+    /// The byte offset of the next character in `bytes`.
+    pub offset: usize,
+    /// The number of characters read from `bytes`.
+    pub position: u64,
+    /// The error at `offset`, if the next bytes are not valid UTF-8.
+    pub error: Option<&'static str>,
+}
+
+impl<'a> JsonhBytesSource<'a> {
+    /// Constructs a source reading UTF-8 text from a byte slice.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        return Self { bytes: bytes, offset: 0, position: 0, error: None };
+    }
+
+    fn decode(&mut self) -> Option<(char, usize)> {
+        if self.error.is_some() {
+            return None;
+        }
+        let first: u8 = *self.bytes.get(self.offset)?;
+        let length: usize = match first {
+            0x00..=0x7F => return Some((first as char, 1)),
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 0,
+        };
+
+        // Validate sequence
+        let sequence: Option<char> = self.bytes.get(self.offset..self.offset + length)
+            .filter(|_| length != 0)
+            .and_then(|sequence| std::str::from_utf8(sequence).ok())
+            .and_then(|sequence| sequence.chars().next());
+        let Some(next) = sequence else {
+            self.error = Some("Invalid UTF-8 sequence");
+            return None;
+        };
+        return Some((next, length));
+    }
+}
+
+impl JsonhSource for JsonhBytesSource<'_> {
+    fn peek(&mut self) -> Option<char> {
+        return self.decode().map(|(next, _)| next);
+    }
+    fn next(&mut self) -> Option<char> {
+        let (next, length): (char, usize) = self.decode()?;
+        self.offset += length;
+        self.position += 1;
+        return Some(next);
+    }
+    fn position(&self) -> u64 {
+        return self.position;
+    }
+    fn size_hint(&self) -> Option<usize> {
+        return Some(self.bytes.len() - self.offset);
+    }
+    fn error(&self) -> Option<&'static str> {
+        return self.error;
+    }
+}
\ No newline at end of file
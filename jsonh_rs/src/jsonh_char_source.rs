@@ -0,0 +1,37 @@
+use std::iter::Peekable;
+
+use crate::JsonhSource;
+
+/// A `JsonhSource` reading from a character iterator.
+pub struct JsonhCharSource<I: Iterator<Item = char>> {
+    /// The peekable character iterator to read characters from.
+    pub chars: Peekable<I>,
+    /// The number of characters read from `chars`.
+    pub position: u64,
+}
+
+impl<I: Iterator<Item = char>> JsonhCharSource<I> {
+    /// Constructs a source reading from a character iterator.
+    pub fn new(chars: I) -> Self {
+        return Self { chars: chars.peekable(), position: 0 };
+    }
+}
+
+impl<I: Iterator<Item = char>> JsonhSource for JsonhCharSource<I> {
+    fn peek(&mut self) -> Option<char> {
+        return self.chars.peek().copied();
+    }
+    fn next(&mut self) -> Option<char> {
+        let next: Option<char> = self.chars.next();
+        if next.is_some() {
+            self.position += 1;
+        }
+        return next;
+    }
+    fn position(&self) -> u64 {
+        return self.position;
+    }
+    fn size_hint(&self) -> Option<usize> {
+        return self.chars.size_hint().1;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,99 @@
+use serde_json::Value;
+
+use crate::JsonhPath;
+use crate::JsonhPathSegment;
+
+/// A comment in a JSONH document, associated with the element it documents.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonhComment {
+    /// The path of the element following the comment.
+    /// 
+    /// For comments before the end of an object or array, this is the path of the object or array.
+    pub path: JsonhPath,
+    /// The contents of the comment, excluding the comment delimiters.
+    pub value: String,
+    /// The number of characters in the source before the start of the comment.
+    pub start: u64,
+    /// The number of characters in the source before the end of the comment.
+    pub end: u64,
+}
+
+impl JsonhComment {
+    /// Constructs a comment associated with the given path.
+    pub fn new(path: JsonhPath, value: String) -> Self {
+        return Self { path: path, value: value, start: 0, end: 0 };
+    }
+    /// Sets the range of characters in the source that the comment was read from.
+    pub fn with_span(mut self, start: u64, end: u64) -> Self {
+        self.start = start;
+        self.end = end;
+        return self;
+    }
+
+    /// Adds the comments to the element as `$comment` properties.
+    /// 
+    /// Comments for an object are added to the object as `$comment`.
+    /// Comments for another value in an object are added to the object as `$comment:<name>`.
+    /// Comments for other values in arrays and at the root are skipped.
+    /// Multiple comments for the same element are joined with newlines.
+    /// 
+    /// ```
+    /// // The server
+    /// {
+    ///   # The port
+    ///   port: 8080
+    /// }
+    /// ```
+    /// becomes:
+    /// ```
+    /// {
+    ///   "$comment": "The server",
+    ///   "$comment:port": "The port",
+    ///   "port": 8080
+    /// }
+    /// ```
+    pub fn inject_all(element: &mut Value, comments: &[JsonhComment]) {
+        for comment in comments {
+            // Find element
+            let Some(target) = Self::find_mut(element, &comment.path.segments) else {
+                continue;
+            };
+
+            // Object
+            let (object, key): (&mut serde_json::Map<String, Value>, String) = if let Value::Object(target) = target {
+                (target, "$comment".to_string())
+            }
+            // Property in object
+            else if let Some((JsonhPathSegment::Property(name), parent_segments)) = comment.path.segments.split_last()
+                && let Some(Value::Object(parent)) = Self::find_mut(element, parent_segments) {
+                (parent, format!("$comment:{name}"))
+            }
+            // Other
+            else {
+                continue;
+            };
+
+            // Join with existing comments
+            let text: &str = comment.value.trim();
+            match object.get_mut(&key) {
+                Some(Value::String(existing)) => {
+                    existing.push('\n');
+                    existing.push_str(text);
+                },
+                _ => {
+                    object.insert(key, Value::String(text.to_string()));
+                },
+            }
+        }
+    }
+    fn find_mut<'a>(element: &'a mut Value, segments: &[JsonhPathSegment]) -> Option<&'a mut Value> {
+        let mut current: &mut Value = element;
+        for segment in segments {
+            current = match segment {
+                JsonhPathSegment::Property(name) => current.as_object_mut()?.get_mut(name)?,
+                JsonhPathSegment::Index(index) => current.as_array_mut()?.get_mut(*index)?,
+            };
+        }
+        return Some(current);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+/// The syntaxes of comments.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhCommentStyle {
+    /// A hash-style line comment.
+    /// 
+    /// Example: `# comment`
+    Hash = 0,
+    /// A slash-style line comment.
+    /// 
+    /// Example: `// comment`
+    Line = 1,
+    /// A block comment.
+    /// 
+    /// Example: `/* comment */`
+    Block = 2,
+}
+
+impl JsonhCommentStyle {
+    /// Returns the style of the comment as written in the source, or `None` if it is not a comment.
+    pub fn of(raw: &str) -> Option<JsonhCommentStyle> {
+        if raw.starts_with('#') {
+            return Some(JsonhCommentStyle::Hash);
+        }
+        else if raw.starts_with("//") {
+            return Some(JsonhCommentStyle::Line);
+        }
+        else if raw.starts_with("/*") || raw.starts_with("/=") {
+            return Some(JsonhCommentStyle::Block);
+        }
+        return None;
+    }
+}
\ No newline at end of file
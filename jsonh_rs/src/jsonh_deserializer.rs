@@ -0,0 +1,592 @@
+use std::iter::Peekable;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::de::IgnoredAny;
+use serde::de::DeserializeSeed;
+use serde::de::EnumAccess;
+use serde::de::IntoDeserializer;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::VariantAccess;
+use serde::de::Visitor;
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::value::StringDeserializer;
+use serde::forward_to_deserialize_any;
+
+use crate::JsonhReader;
+use crate::JsonhToken;
+use crate::jsonh_token_iter::RawTokenIter;
+use crate::JsonTokenType;
+use crate::JsonhNumberParser;
+use crate::JsonhError;
+use crate::JsonhBytesEncoding;
+use crate::JsonhPath;
+use crate::JsonhPathSegment;
+use crate::commented::COMMENTED_NAME;
+use crate::commented::COMMENTED_COMMENTS_FIELD;
+use crate::commented::COMMENTED_VALUE_FIELD;
+use crate::jsonh_raw_value::RAW_VALUE_NAME;
+use crate::spanned::SPANNED_NAME;
+use crate::spanned::char_offset;
+use crate::spanned::SPANNED_VALUE_FIELD;
+use crate::spanned::SPANNED_START_FIELD;
+use crate::spanned::SPANNED_END_FIELD;
+
+/// A serde deserializer reading a single JSONH element from the tokens of a `JsonhReader`.
+/// 
+/// ```
+/// let mut reader: JsonhReader = JsonhReader::from_str(source, JsonhReaderOptions::new());
+/// let config: Config = Config::deserialize(&mut JsonhDeserializer::new(&mut reader))?;
+/// ```
+/// 
+/// Integers are visited as `u64` or `i64` without losing precision (e.g. `0xFFFF_FFFF_FFFF_FFFF`), and other numbers as `f64`.
+/// Larger integers are visited as `i128` or `u128` when deserializing those types, and as `f64` otherwise.
+/// Errors include the path to the element that failed (e.g. `servers[2].port`) and its position in the source.
+/// 
+/// Externally tagged enums are read from a string for unit variants (e.g. `"Empty"`) or an object with a single property (e.g. `{ Circle: 1.5 }`).
+pub struct JsonhDeserializer<'r> {
+    tokens: Peekable<RawTokenIter<'r>>,
+    comments: Vec<String>,
+    previous_end: u64,
+    bytes_encoding: JsonhBytesEncoding,
+    path: JsonhPath,
+    source: Option<&'r str>,
+    comments_start: Option<u64>,
+    pub(crate) first_error: Option<JsonhError>,
+}
+
+impl<'r> JsonhDeserializer<'r> {
+    /// Constructs a deserializer reading the next element from the reader.
+    pub fn new(reader: &'r mut JsonhReader<'_>) -> Self {
+        return Self { tokens: reader.read_element().raw().peekable(), comments: Vec::new(), previous_end: 0, bytes_encoding: JsonhBytesEncoding::Base64, path: JsonhPath::new(), source: None, comments_start: None, first_error: None };
+    }
+    /// Sets the encoding used to decode strings deserialized as bytes (e.g. with `jsonh_rs::bytes`).
+    /// 
+    /// ```
+    /// // Bytes encoding: Hex
+    /// "4869" -> [0x48, 0x69]
+    /// ```
+    pub fn with_bytes_encoding(mut self, value: JsonhBytesEncoding) -> Self {
+        self.bytes_encoding = value;
+        return self;
+    }
+    /// Sets the source text the reader is reading from, which is required to deserialize `JsonhRawValue`.
+    pub fn with_source(mut self, value: &'r str) -> Self {
+        self.source = Some(value);
+        return self;
+    }
+
+    pub(crate) fn peek_type(&mut self) -> Result<Option<JsonTokenType>, JsonhError> {
+        // Collect comments
+        while let Some(Ok(token)) = self.tokens.peek() && token.json_type == JsonTokenType::Comment {
+            if let Some(Ok(comment)) = self.tokens.next() {
+                self.comments_start.get_or_insert(comment.start);
+                self.comments.push(comment.value.trim().to_string());
+            }
+        }
+
+        return match self.tokens.peek() {
+            Some(Ok(token)) => Ok(Some(token.json_type)),
+            Some(Err(token_error)) => {
+                let token_error: &'static str = token_error;
+                Err(self.token_error(token_error))
+            },
+            None => Ok(None),
+        };
+    }
+    pub(crate) fn peek_start(&mut self) -> Result<u64, JsonhError> {
+        self.peek_type()?;
+
+        return match self.tokens.peek() {
+            Some(Ok(token)) => Ok(token.start),
+            _ => Ok(self.previous_end),
+        };
+    }
+    pub(crate) fn next_token(&mut self) -> Result<JsonhToken, JsonhError> {
+        self.peek_type()?;
+
+        match self.tokens.next() {
+            Some(Ok(token)) => {
+                // Keep comments before property name for its value
+                if token.json_type != JsonTokenType::PropertyName {
+                    self.comments.clear();
+                }
+                self.comments_start = None;
+                self.previous_end = token.end;
+                return Ok(token);
+            },
+            Some(Err(token_error)) => return Err(self.token_error(token_error)),
+            None => return Err(JsonhError::from("Expected token, got end of input")),
+        }
+    }
+    fn token_error(&mut self, token_error: &'static str) -> JsonhError {
+        let error: JsonhError = JsonhError::from(token_error).with_path(self.path.clone());
+        self.first_error.get_or_insert_with(|| error.clone());
+        return error;
+    }
+    fn end_structure(&mut self, json_type: JsonTokenType) -> Result<(), JsonhError> {
+        let token: JsonhToken = self.next_token()?;
+        if token.json_type != json_type {
+            return Err(JsonhError::from(if json_type == JsonTokenType::EndObject { "Expected end of object" } else { "Expected end of array" }));
+        }
+        return Ok(());
+    }
+    /// Errors with a suggestion if the next token is a string that looks like a misspelling of one of the literals (e.g. `ture`).
+    fn check_misspelled_literal(&mut self, expected: &str, literals: &[&'static str]) -> Result<(), JsonhError> {
+        if let Some(literal) = self.peek_misspelled_literal(literals)? {
+            let Some(Ok(token)) = self.tokens.peek() else {
+                return Ok(());
+            };
+            return Err(JsonhError::new(format!("invalid type: string {:?}, expected {expected}, did you mean `{literal}`?", token.value)));
+        }
+        return Ok(());
+    }
+    /// Returns the literal that the next token looks like a misspelling of, if it is a string.
+    fn peek_misspelled_literal(&mut self, literals: &[&'static str]) -> Result<Option<&'static str>, JsonhError> {
+        if self.peek_type()? != Some(JsonTokenType::String) {
+            return Ok(None);
+        }
+        let Some(Ok(token)) = self.tokens.peek() else {
+            return Ok(None);
+        };
+        return Ok(crate::suggest_literal_from(&token.value, literals));
+    }
+    /// Deserializes a nested element, adding its path and position to errors.
+    pub(crate) fn deserialize_at<T>(&mut self, segment: JsonhPathSegment, deserialize: impl FnOnce(&mut Self) -> Result<T, JsonhError>) -> Result<T, JsonhError> {
+        self.path.push(segment);
+        let result: Result<T, JsonhError> = match self.peek_start() {
+            Ok(start) => deserialize(self).map_err(|error| self.add_context(error, start)),
+            Err(error) => Err(self.add_context(error, self.previous_end)),
+        };
+        self.path.pop();
+
+        // Keep innermost error in case it passes through a serializer (see `transcode_to_json`)
+        if let Err(error) = &result {
+            self.first_error.get_or_insert_with(|| error.clone());
+        }
+        return result;
+    }
+    /// Adds the current path and the position to the error, unless a nested element already added them.
+    pub(crate) fn add_context(&self, error: JsonhError, start: u64) -> JsonhError {
+        if error.path.is_some() {
+            return error;
+        }
+        return error.with_path(self.path.clone()).with_position(start);
+    }
+    /// Visits a number as a `u64` or `i64` if it is an integer in their range, or as an `f64` otherwise.
+    fn visit_number<'de, V: Visitor<'de>>(&mut self, value: String, visitor: V) -> Result<V::Value, JsonhError> {
+        // Integer (without losing precision)
+        if let Some(integer) = JsonhNumberParser::parse_integer(&value) {
+            if let Ok(unsigned) = u64::try_from(integer) {
+                return visitor.visit_u64(unsigned);
+            }
+            else if let Ok(signed) = i64::try_from(integer) {
+                return visitor.visit_i64(signed);
+            }
+            return visitor.visit_f64(integer as f64);
+        }
+
+        let number: f64 = JsonhNumberParser::parse(value)?;
+
+        // Integral real (e.g. `1e3`)
+        if number.fract() == 0.0 && number >= 0.0 && number < (u64::MAX as f64) {
+            return visitor.visit_u64(number as u64);
+        }
+        else if number.fract() == 0.0 && number >= (i64::MIN as f64) && number < 0.0 {
+            return visitor.visit_i64(number as i64);
+        }
+        // Float
+        return visitor.visit_f64(number);
+    }
+}
+
+impl<'de> Deserializer<'de> for &mut JsonhDeserializer<'_> {
+    type Error = JsonhError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let token: JsonhToken = self.next_token()?;
+
+        match token.json_type {
+            // Null
+            JsonTokenType::Null => {
+                return visitor.visit_unit();
+            },
+            // True
+            JsonTokenType::True => {
+                return visitor.visit_bool(true);
+            },
+            // False
+            JsonTokenType::False => {
+                return visitor.visit_bool(false);
+            },
+            // String
+            JsonTokenType::String => {
+                return visitor.visit_string(token.unescaped_value()?.into_owned());
+            },
+            // Number
+            JsonTokenType::Number => {
+                return self.visit_number(token.value, visitor);
+            },
+            // Object
+            JsonTokenType::StartObject => {
+                let value: V::Value = visitor.visit_map(JsonhMapAccess { deserializer: self, property_name: String::new() })?;
+                self.end_structure(JsonTokenType::EndObject)?;
+                return Ok(value);
+            },
+            // Array
+            JsonTokenType::StartArray => {
+                let value: V::Value = visitor.visit_seq(JsonhSeqAccess { deserializer: self, index: 0 })?;
+                self.end_structure(JsonTokenType::EndArray)?;
+                return Ok(value);
+            },
+            // Other
+            _ => {
+                return Err(JsonhError::from("Unexpected token"));
+            },
+        }
+    }
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.peek_type()? == Some(JsonTokenType::Number) {
+            let token: JsonhToken = self.next_token()?;
+            if let Some(integer) = JsonhNumberParser::parse_integer(&token.value) {
+                return visitor.visit_i128(integer);
+            }
+            return self.visit_number(token.value, visitor);
+        }
+        return self.deserialize_any(visitor);
+    }
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.peek_type()? == Some(JsonTokenType::Number) {
+            let token: JsonhToken = self.next_token()?;
+            if let Some(integer) = JsonhNumberParser::parse_unsigned_integer(&token.value) {
+                return visitor.visit_u128(integer);
+            }
+            return self.visit_number(token.value, visitor);
+        }
+        return self.deserialize_any(visitor);
+    }
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.check_misspelled_literal("a boolean", &["true", "false"])?;
+        return self.deserialize_any(visitor);
+    }
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.check_misspelled_literal("null", &["null"])?;
+        return self.deserialize_any(visitor);
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.peek_type()? == Some(JsonTokenType::Null) {
+            self.next_token()?;
+            return visitor.visit_none();
+        }
+
+        // Suggest null if the value is invalid (a misspelled null may be a valid string)
+        let misspelled_null: Option<&'static str> = self.peek_misspelled_literal(&["null"])?;
+        return visitor.visit_some(&mut *self).map_err(|mut error| {
+            if let Some(literal) = misspelled_null {
+                error.message = format!("{}, did you mean `{literal}`?", error.message);
+            }
+            return error;
+        });
+    }
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.deserialize_byte_buf(visitor);
+    }
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        // Encoded string
+        if self.peek_type()? == Some(JsonTokenType::String) {
+            let token: JsonhToken = self.next_token()?;
+            return visitor.visit_byte_buf(self.bytes_encoding.decode(&token.unescaped_value()?)?);
+        }
+        return self.deserialize_any(visitor);
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        // Raw value
+        if name == RAW_VALUE_NAME {
+            let Some(source) = self.source else {
+                return Err(JsonhError::from("Source text is required to deserialize a raw value"));
+            };
+            let start: u64 = self.peek_start()?;
+            let start: u64 = self.comments_start.unwrap_or(start);
+            IgnoredAny::deserialize(&mut *self)?;
+            return visitor.visit_string(source[char_offset(source, start)..char_offset(source, self.previous_end)].to_string());
+        }
+        return visitor.visit_newtype_struct(self);
+    }
+    fn deserialize_struct<V: Visitor<'de>>(self, name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        // Commented value
+        if name == COMMENTED_NAME {
+            self.peek_type()?;
+            let comments: Vec<String> = std::mem::take(&mut self.comments);
+            return visitor.visit_map(CommentedMapAccess { deserializer: self, comments: Some(comments), has_value: false });
+        }
+
+        // Spanned value
+        if name == SPANNED_NAME {
+            let start: u64 = self.peek_start()?;
+            return visitor.visit_map(SpannedMapAccess { deserializer: self, start: start, field_index: 0 });
+        }
+
+        let _ = fields;
+        return self.deserialize_any(visitor);
+    }
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        match self.peek_type()? {
+            // Unit variant
+            Some(JsonTokenType::String) => {
+                let token: JsonhToken = self.next_token()?;
+                let variant: StringDeserializer<JsonhError> = token.unescaped_value()?.into_owned().into_deserializer();
+                return visitor.visit_enum(variant);
+            },
+            // Variant with content
+            Some(JsonTokenType::StartObject) => {
+                self.next_token()?;
+                let value: V::Value = visitor.visit_enum(JsonhEnumAccess { deserializer: self })?;
+                self.end_structure(JsonTokenType::EndObject)?;
+                return Ok(value);
+            },
+            // Other
+            _ => {
+                return Err(JsonhError::from("Expected string or object for enum"));
+            },
+        }
+    }
+
+    forward_to_deserialize_any! {
+        i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        unit_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+/// Reads the properties of an object.
+struct JsonhMapAccess<'d, 'r> {
+    deserializer: &'d mut JsonhDeserializer<'r>,
+    property_name: String,
+}
+
+impl<'de> MapAccess<'de> for JsonhMapAccess<'_, '_> {
+    type Error = JsonhError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        if self.deserializer.peek_type()? != Some(JsonTokenType::PropertyName) {
+            return Ok(None);
+        }
+        let token: JsonhToken = self.deserializer.next_token()?;
+        self.property_name = token.unescaped_value()?.into_owned();
+        let property_name: String = self.property_name.clone();
+        return self.deserializer.deserialize_at(JsonhPathSegment::Property(property_name.clone()), |_| seed.deserialize(JsonhMapKeyDeserializer { key: property_name })).map(Some);
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        return self.deserializer.deserialize_at(JsonhPathSegment::Property(std::mem::take(&mut self.property_name)), |deserializer| seed.deserialize(deserializer));
+    }
+}
+
+/// Reads a property name as a string, or as a number or boolean for keys of those types (e.g. `HashMap<u32, T>`).
+struct JsonhMapKeyDeserializer {
+    key: String,
+}
+
+impl JsonhMapKeyDeserializer {
+    fn visit_integer<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, JsonhError> {
+        if let Some(integer) = JsonhNumberParser::parse_integer(&self.key) {
+            if let Ok(unsigned) = u64::try_from(integer) {
+                return visitor.visit_u64(unsigned);
+            }
+            else if let Ok(signed) = i64::try_from(integer) {
+                return visitor.visit_i64(signed);
+            }
+            return visitor.visit_i128(integer);
+        }
+        if let Some(integer) = JsonhNumberParser::parse_unsigned_integer(&self.key) {
+            return visitor.visit_u128(integer);
+        }
+        return visitor.visit_string(self.key);
+    }
+}
+
+impl<'de> Deserializer<'de> for JsonhMapKeyDeserializer {
+    type Error = JsonhError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return visitor.visit_string(self.key);
+    }
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.visit_integer(visitor);
+    }
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.deserialize_f64(visitor);
+    }
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return match JsonhNumberParser::parse(self.key.clone()) {
+            Ok(number) => visitor.visit_f64(number),
+            Err(_) => visitor.visit_string(self.key),
+        };
+    }
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return match self.key.as_str() {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => visitor.visit_string(self.key),
+        };
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        return visitor.visit_some(self);
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        return visitor.visit_newtype_struct(self);
+    }
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        let variant: StringDeserializer<JsonhError> = self.key.into_deserializer();
+        return visitor.visit_enum(variant);
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Reads the items of an array.
+struct JsonhSeqAccess<'d, 'r> {
+    deserializer: &'d mut JsonhDeserializer<'r>,
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for JsonhSeqAccess<'_, '_> {
+    type Error = JsonhError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        if matches!(self.deserializer.peek_type()?, Some(JsonTokenType::EndArray) | None) {
+            return Ok(None);
+        }
+        self.index += 1;
+        return self.deserializer.deserialize_at(JsonhPathSegment::Index(self.index - 1), |deserializer| seed.deserialize(deserializer)).map(Some);
+    }
+}
+
+/// Reads the variant of an enum from an object with a single property (e.g. `{ Circle: 1.5 }`).
+struct JsonhEnumAccess<'d, 'r> {
+    deserializer: &'d mut JsonhDeserializer<'r>,
+}
+
+impl<'de> EnumAccess<'de> for JsonhEnumAccess<'_, '_> {
+    type Error = JsonhError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Self::Error> {
+        if self.deserializer.peek_type()? != Some(JsonTokenType::PropertyName) {
+            return Err(JsonhError::from("Expected variant name in object"));
+        }
+        let token: JsonhToken = self.deserializer.next_token()?;
+        let variant_name: StringDeserializer<JsonhError> = token.unescaped_value()?.into_owned().into_deserializer();
+        let variant: V::Value = seed.deserialize(variant_name)?;
+        return Ok((variant, self));
+    }
+}
+
+impl<'de> VariantAccess<'de> for JsonhEnumAccess<'_, '_> {
+    type Error = JsonhError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        return <() as serde::Deserialize>::deserialize(&mut *self.deserializer);
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        return seed.deserialize(&mut *self.deserializer);
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        return self.deserializer.deserialize_seq(visitor);
+    }
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        return self.deserializer.deserialize_struct("", fields, visitor);
+    }
+}
+
+/// Reads the comments and value of a `Commented`.
+struct CommentedMapAccess<'d, 'r> {
+    deserializer: &'d mut JsonhDeserializer<'r>,
+    comments: Option<Vec<String>>,
+    has_value: bool,
+}
+
+impl<'de> MapAccess<'de> for CommentedMapAccess<'_, '_> {
+    type Error = JsonhError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        if self.comments.is_some() {
+            return seed.deserialize(BorrowedStrDeserializer::new(COMMENTED_COMMENTS_FIELD)).map(Some);
+        }
+        else if !self.has_value {
+            return seed.deserialize(BorrowedStrDeserializer::new(COMMENTED_VALUE_FIELD)).map(Some);
+        }
+        return Ok(None);
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        // Comments
+        if let Some(comments) = self.comments.take() {
+            return seed.deserialize(comments.into_deserializer());
+        }
+        // Value
+        self.has_value = true;
+        return seed.deserialize(&mut *self.deserializer);
+    }
+}
+
+/// Reads the value and span of a `Spanned`.
+struct SpannedMapAccess<'d, 'r> {
+    deserializer: &'d mut JsonhDeserializer<'r>,
+    start: u64,
+    field_index: usize,
+}
+
+impl<'de> MapAccess<'de> for SpannedMapAccess<'_, '_> {
+    type Error = JsonhError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        let field: &'static str = match self.field_index {
+            0 => SPANNED_VALUE_FIELD,
+            1 => SPANNED_START_FIELD,
+            2 => SPANNED_END_FIELD,
+            _ => return Ok(None),
+        };
+        return seed.deserialize(BorrowedStrDeserializer::new(field)).map(Some);
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        self.field_index += 1;
+        return match self.field_index {
+            // Value
+            1 => seed.deserialize(&mut *self.deserializer),
+            // Start
+            2 => seed.deserialize(self.start.into_deserializer()),
+            // End
+            _ => seed.deserialize(self.deserializer.previous_end.into_deserializer()),
+        };
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use crate::JsonhSeverity;
+use crate::JsonhTextEdit;
+
+/// A problem found in a JSONH document, such as a lint warning.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonhDiagnostic {
+    /// The severity of the problem.
+    pub severity: JsonhSeverity,
+    /// The name of the rule or check that found the problem (e.g. `duplicate-keys`).
+    pub code: &'static str,
+    /// A description of the problem.
+    pub message: String,
+    /// The number of characters in the source before the start of the problem.
+    pub start: u64,
+    /// The number of characters in the source before the end of the problem.
+    pub end: u64,
+    /// The edits that fix the problem, or empty if the problem cannot be fixed automatically.
+    pub edits: Vec<JsonhTextEdit>,
+}
+
+impl JsonhDiagnostic {
+    /// Constructs a diagnostic with the given severity and message.
+    pub fn new(severity: JsonhSeverity, code: &'static str, message: String) -> Self {
+        return Self { severity: severity, code: code, message: message, start: 0, end: 0, edits: Vec::new() };
+    }
+    /// Sets the range of characters in the source that the diagnostic applies to.
+    pub fn with_span(mut self, start: u64, end: u64) -> Self {
+        self.start = start;
+        self.end = end;
+        return self;
+    }
+    /// Adds an edit that fixes the problem.
+    pub fn with_edit(mut self, edit: JsonhTextEdit) -> Self {
+        self.edits.push(edit);
+        return self;
+    }
+    /// Returns whether the problem can be fixed automatically.
+    pub fn is_fixable(&self) -> bool {
+        return !self.edits.is_empty();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::JsonhPath;
+use crate::JsonhPathSegment;
+
+/// The first difference found between two elements.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonhDivergence {
+    /// The path to the element that differs.
+    pub path: JsonhPath,
+    /// The element in the first value, or None if it is missing.
+    pub expected: Option<Value>,
+    /// The element in the second value, or None if it is missing.
+    pub actual: Option<Value>,
+}
+
+impl JsonhDivergence {
+    /// Constructs a divergence at the given path.
+    pub fn new(path: JsonhPath, expected: Option<Value>, actual: Option<Value>) -> Self {
+        return Self { path: path, expected: expected, actual: actual };
+    }
+
+    /// Finds the first difference between two elements, or None if they are equal.
+    /// 
+    /// Properties are compared by name regardless of their order.
+    /// ```
+    /// { a: [1, 2] } vs { a: [1, 3] } -> a[1]: expected 2, got 3
+    /// ```
+    pub fn find(expected: &Value, actual: &Value) -> Option<Self> {
+        let mut path: JsonhPath = JsonhPath::new();
+        return Self::find_at(expected, actual, &mut path);
+    }
+
+    fn find_at(expected: &Value, actual: &Value, path: &mut JsonhPath) -> Option<Self> {
+        match (expected, actual) {
+            // Object
+            (Value::Object(expected_properties), Value::Object(actual_properties)) => {
+                for (name, expected_value) in expected_properties {
+                    path.push(JsonhPathSegment::Property(name.clone()));
+                    let divergence: Option<Self> = match actual_properties.get(name) {
+                        Some(actual_value) => Self::find_at(expected_value, actual_value, path),
+                        None => Some(Self::new(path.clone(), Some(expected_value.clone()), None)),
+                    };
+                    path.pop();
+                    if divergence.is_some() {
+                        return divergence;
+                    }
+                }
+                for (name, actual_value) in actual_properties {
+                    if !expected_properties.contains_key(name) {
+                        path.push(JsonhPathSegment::Property(name.clone()));
+                        let divergence: Self = Self::new(path.clone(), None, Some(actual_value.clone()));
+                        path.pop();
+                        return Some(divergence);
+                    }
+                }
+                return None;
+            },
+            // Array
+            (Value::Array(expected_items), Value::Array(actual_items)) => {
+                for index in 0..expected_items.len().max(actual_items.len()) {
+                    path.push(JsonhPathSegment::Index(index));
+                    let divergence: Option<Self> = match (expected_items.get(index), actual_items.get(index)) {
+                        (Some(expected_item), Some(actual_item)) => Self::find_at(expected_item, actual_item, path),
+                        (expected_item, actual_item) => Some(Self::new(path.clone(), expected_item.cloned(), actual_item.cloned())),
+                    };
+                    path.pop();
+                    if divergence.is_some() {
+                        return divergence;
+                    }
+                }
+                return None;
+            },
+            // Primitive
+            _ => {
+                if expected == actual {
+                    return None;
+                }
+                return Some(Self::new(path.clone(), Some(expected.clone()), Some(actual.clone())));
+            },
+        }
+    }
+}
+
+impl fmt::Display for JsonhDivergence {
+    /// Formats the divergence like `a[1]: expected 2, got 3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: expected ", self.path)?;
+        match &self.expected {
+            Some(expected) => write!(f, "{expected}")?,
+            None => write!(f, "nothing")?,
+        }
+        write!(f, ", got ")?;
+        match &self.actual {
+            Some(actual) => write!(f, "{actual}")?,
+            None => write!(f, "nothing")?,
+        }
+        return Ok(());
+    }
+}
\ No newline at end of file
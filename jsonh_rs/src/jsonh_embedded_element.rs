@@ -0,0 +1,166 @@
+use serde_json::Value;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhError;
+
+/// A JSONH element embedded in a host document, such as a Markdown file or a template.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonhEmbeddedElement {
+    /// The parsed element.
+    pub value: Value,
+    /// The number of characters in the host document before the start of the JSONH.
+    pub start: u64,
+    /// The number of characters in the host document before the end of the JSONH.
+    pub end: u64,
+}
+
+impl JsonhEmbeddedElement {
+    /// Constructs an embedded element read from the given range of the host document.
+    pub fn new(value: Value, start: u64, end: u64) -> Self {
+        return Self { value: value, start: start, end: end };
+    }
+
+    /// Parses every fenced code block in a Markdown document with the info string `jsonh`.
+    /// 
+    /// ````
+    /// # Config
+    /// ```jsonh
+    /// port: 8080
+    /// ```
+    /// ````
+    pub fn parse_markdown(host: &str, options: JsonhReaderOptions) -> Result<Vec<JsonhEmbeddedElement>, JsonhError> {
+        let lines: Vec<HostLine<'_>> = HostLine::split(host);
+        let mut elements: Vec<JsonhEmbeddedElement> = Vec::new();
+
+        let mut index: usize = 0;
+        while index < lines.len() {
+            // Opening fence
+            let Some((fence_char, fence_length, info)) = Self::read_fence(lines[index].text) else {
+                index += 1;
+                continue;
+            };
+            let is_jsonh: bool = info.split_whitespace().next().is_some_and(|language| language.eq_ignore_ascii_case("jsonh"));
+
+            // Closing fence (or end of document)
+            let content_start: usize = index + 1;
+            let mut content_end: usize = content_start;
+            while content_end < lines.len() {
+                if let Some((closing_char, closing_length, closing_info)) = Self::read_fence(lines[content_end].text)
+                    && closing_char == fence_char && closing_length >= fence_length && closing_info.is_empty() {
+                    break;
+                }
+                content_end += 1;
+            }
+
+            if is_jsonh {
+                elements.push(Self::parse_lines(host, &lines, content_start, content_end, options)?);
+            }
+            index = content_end + 1;
+        }
+
+        return Ok(elements);
+    }
+    /// Parses the front matter at the start of a document, delimited by lines of `---`.
+    /// 
+    /// Returns `None` if the document does not start with front matter.
+    /// 
+    /// ```
+    /// ---
+    /// title: Hello
+    /// ---
+    /// Content
+    /// ```
+    pub fn parse_front_matter(host: &str, options: JsonhReaderOptions) -> Result<Option<JsonhEmbeddedElement>, JsonhError> {
+        let lines: Vec<HostLine<'_>> = HostLine::split(host);
+
+        // Opening delimiter
+        if lines.first().is_none_or(|line| line.text.trim_end() != "---") {
+            return Ok(None);
+        }
+        // Closing delimiter
+        let Some(content_end) = lines.iter().skip(1).position(|line| line.text.trim_end() == "---") else {
+            return Ok(None);
+        };
+
+        return Ok(Some(Self::parse_lines(host, &lines, 1, content_end + 1, options)?));
+    }
+    /// Parses every region of a document between the given start and end delimiters.
+    /// 
+    /// A start delimiter without an end delimiter is an error.
+    /// 
+    /// ```
+    /// // Delimiters: "<%jsonh", "%>"
+    /// <p>Hello</p>
+    /// <%jsonh { title: Hello } %>
+    /// ```
+    pub fn parse_delimited(host: &str, start_delimiter: &str, end_delimiter: &str, options: JsonhReaderOptions) -> Result<Vec<JsonhEmbeddedElement>, JsonhError> {
+        let mut elements: Vec<JsonhEmbeddedElement> = Vec::new();
+
+        let mut byte_position: usize = 0;
+        while let Some(start_index) = host[byte_position..].find(start_delimiter) {
+            let content_start: usize = byte_position + start_index + start_delimiter.len();
+            let Some(end_index) = host[content_start..].find(end_delimiter) else {
+                return Err(JsonhError::from("Expected end delimiter for embedded JSONH"));
+            };
+            let content_end: usize = content_start + end_index;
+
+            elements.push(Self::parse_range(host, content_start, content_end, options)?);
+            byte_position = content_end + end_delimiter.len();
+        }
+
+        return Ok(elements);
+    }
+
+    fn read_fence(line: &str) -> Option<(char, usize, &str)> {
+        // Up to 3 spaces of indentation
+        let trimmed: &str = line.trim_start_matches(' ');
+        if line.len() - trimmed.len() > 3 {
+            return None;
+        }
+
+        let fence_char: char = trimmed.chars().next().filter(|next| matches!(next, '`' | '~'))?;
+        let fence_length: usize = trimmed.chars().take_while(|next| *next == fence_char).count();
+        if fence_length < 3 {
+            return None;
+        }
+        return Some((fence_char, fence_length, trimmed[fence_length..].trim()));
+    }
+    fn parse_lines(host: &str, lines: &[HostLine<'_>], start_line: usize, end_line: usize, options: JsonhReaderOptions) -> Result<JsonhEmbeddedElement, JsonhError> {
+        let content_start: usize = lines.get(start_line).map_or(host.len(), |line| line.byte_start);
+        let content_end: usize = lines.get(end_line).map_or(host.len(), |line| line.byte_start).max(content_start);
+        return Self::parse_range(host, content_start, content_end, options);
+    }
+    fn parse_range(host: &str, byte_start: usize, byte_end: usize, options: JsonhReaderOptions) -> Result<JsonhEmbeddedElement, JsonhError> {
+        let start: u64 = host[..byte_start].chars().count() as u64;
+
+        // Locate errors in host
+        let value: Value = JsonhReader::parse_element_from_str(&host[byte_start..byte_end], options).map_err(|error| {
+            let position: u64 = start + error.position.unwrap_or_default();
+            return error.with_position(position).with_source(host);
+        })?;
+
+        let end: u64 = start + host[byte_start..byte_end].chars().count() as u64;
+        return Ok(Self::new(value, start, end));
+    }
+}
+
+/// A line in a host document.
+struct HostLine<'a> {
+    /// The text of the line, excluding the newline.
+    text: &'a str,
+    /// The number of bytes in the host document before the start of the line.
+    byte_start: usize,
+}
+
+impl<'a> HostLine<'a> {
+    fn split(host: &'a str) -> Vec<HostLine<'a>> {
+        let mut lines: Vec<HostLine<'a>> = Vec::new();
+        let mut byte_start: usize = 0;
+        for line in host.split_inclusive('\n') {
+            lines.push(HostLine { text: line.trim_end_matches(['\n', '\r']), byte_start: byte_start });
+            byte_start += line.len();
+        }
+        return lines;
+    }
+}
\ No newline at end of file
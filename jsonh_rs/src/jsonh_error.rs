@@ -0,0 +1,129 @@
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::JsonhPath;
+
+/// An error produced when serializing or deserializing JSONH.
+/// 
+/// Deserialization errors include the path to the element that failed and where it starts in the source (or where the syntax error is), for example:
+/// ```
+/// invalid value: integer `70000`, expected u16 at servers[2].port (line 9, column 15)
+/// ```
+/// 
+/// Errors returned by `JsonhReader` include the position, line and column the reader stopped at.
+/// Errors caused by another error (e.g. an I/O error) return it from `Error::source`.
+#[derive(Clone, Debug)]
+pub struct JsonhError {
+    /// A description of the error.
+    pub message: String,
+    /// The path to the element being deserialized when the error occurred, if known.
+    pub path: Option<JsonhPath>,
+    /// The character position in the source of the element being deserialized when the error occurred, if known.
+    pub position: Option<u64>,
+    /// The line and column numbers (starting from 1) of `position`, if known.
+    pub location: Option<(u64, u64)>,
+    /// The path of the file the source was read from, if known.
+    pub file_path: Option<Arc<Path>>,
+    /// The error that caused this error, if any.
+    pub cause: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl JsonhError {
+    /// Constructs an error with the given message.
+    pub fn new(message: String) -> Self {
+        return Self { message: message, path: None, position: None, location: None, file_path: None, cause: None };
+    }
+    /// Sets the path to the element being deserialized when the error occurred.
+    pub fn with_path(mut self, value: JsonhPath) -> Self {
+        self.path = Some(value);
+        return self;
+    }
+    /// Sets the character position in the source of the element being deserialized when the error occurred.
+    pub fn with_position(mut self, value: u64) -> Self {
+        self.position = Some(value);
+        return self;
+    }
+    /// Sets the error that caused this error.
+    pub fn with_cause(mut self, value: impl Error + Send + Sync + 'static) -> Self {
+        self.cause = Some(Arc::new(value));
+        return self;
+    }
+    /// Sets the line and column numbers of `position`.
+    pub fn with_location(mut self, line: u64, column: u64) -> Self {
+        self.location = Some((line, column));
+        return self;
+    }
+    /// Sets the path of the file the source was read from.
+    pub fn with_file_path(mut self, value: impl AsRef<Path>) -> Self {
+        self.file_path = Some(Arc::from(value.as_ref()));
+        return self;
+    }
+    /// Sets the line and column numbers of `position` by counting lines in the source it was read from.
+    pub fn with_source(mut self, source: &str) -> Self {
+        if let Some(position) = self.position {
+            self.location = Some(crate::spanned::char_location(source, position));
+        }
+        return self;
+    }
+}
+
+impl fmt::Display for JsonhError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.message)?;
+
+        // Path
+        if let Some(path) = &self.path && !path.is_root() {
+            write!(formatter, " at {path}")?;
+        }
+        // File path
+        if let Some(file_path) = &self.file_path {
+            write!(formatter, " in {}", file_path.display())?;
+        }
+        // Location
+        if let Some((line, column)) = self.location {
+            write!(formatter, " (line {line}, column {column})")?;
+        }
+        else if let Some(position) = self.position {
+            write!(formatter, " (character {position})")?;
+        }
+        return Ok(());
+    }
+}
+
+impl PartialEq for JsonhError {
+    /// Compares the errors, comparing causes by their messages.
+    fn eq(&self, other: &Self) -> bool {
+        return self.message == other.message
+            && self.path == other.path
+            && self.position == other.position
+            && self.location == other.location
+            && self.file_path == other.file_path
+            && self.cause.as_ref().map(|cause| cause.to_string()) == other.cause.as_ref().map(|cause| cause.to_string());
+    }
+}
+
+impl Error for JsonhError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return self.cause.as_deref().map(|cause| cause as &(dyn Error + 'static));
+    }
+}
+
+impl serde::de::Error for JsonhError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        return Self::new(message.to_string());
+    }
+}
+
+impl serde::ser::Error for JsonhError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        return Self::new(message.to_string());
+    }
+}
+
+impl From<&'static str> for JsonhError {
+    fn from(message: &'static str) -> Self {
+        return Self::new(message.to_string());
+    }
+}
\ No newline at end of file
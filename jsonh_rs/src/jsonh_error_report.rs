@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::fmt;
+
+use miette::Diagnostic;
+use miette::LabeledSpan;
+use miette::SourceCode;
+
+use crate::JsonhError;
+
+/// A `JsonhError` along with the source it was read from, rendered by miette with a label at the error (requires the `miette` feature).
+/// 
+/// ```
+/// let config: Config = jsonh_rs::from_str(&source).map_err(|error| error.to_report(&source))?;
+/// ```
+/// ```
+///   × invalid value: integer `70000`, expected u16
+///    ╭─[6:15]
+///  5 │         host: c
+///  6 │         port: 70000
+///    ·               ───┬───
+///    ·                  ╰── servers[2].port
+///  7 │     }
+///    ╰────
+/// ```
+#[derive(Debug)]
+pub struct JsonhErrorReport {
+    /// The error.
+    pub error: JsonhError,
+    /// The source the error was read from.
+    pub source: String,
+}
+
+impl JsonhError {
+    /// Attaches the source the error was read from, so miette can render the error with a label (requires the `miette` feature).
+    pub fn to_report(&self, source: &str) -> JsonhErrorReport {
+        return JsonhErrorReport { error: self.clone(), source: source.to_string() };
+    }
+}
+
+impl fmt::Display for JsonhErrorReport {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return formatter.write_str(&self.error.message);
+    }
+}
+
+impl Error for JsonhErrorReport {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return self.error.source();
+    }
+}
+
+impl Diagnostic for JsonhErrorReport {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        return self.error.code();
+    }
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        return Some(&self.source);
+    }
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let position: u64 = self.error.position?;
+
+        // Label the token at the position
+        let offset: usize = crate::spanned::char_offset(&self.source, position);
+        let remaining: &str = &self.source[offset..];
+        let token: &str = remaining[..remaining.find([',', '\n', '\r', ']', '}']).unwrap_or(remaining.len())].trim_end();
+        let label: Option<String> = self.error.path.as_ref().filter(|path| !path.is_root()).map(|path| path.to_string());
+        return Some(Box::new(std::iter::once(LabeledSpan::new(label, offset, token.len()))));
+    }
+}
+
+impl Diagnostic for JsonhError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        return Some(Box::new("jsonh::error"));
+    }
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let path: &crate::JsonhPath = self.path.as_ref().filter(|path| !path.is_root())?;
+        return Some(Box::new(format!("at {path}")));
+    }
+}
\ No newline at end of file
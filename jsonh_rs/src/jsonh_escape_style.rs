@@ -0,0 +1,19 @@
+/// The characters escaped when writing strings.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhEscapeStyle {
+    /// Backslashes, quotes, newlines and control characters are escaped.
+    /// 
+    /// Example: `"café\n" -> "café\n"`
+    Standard = 0,
+    /// Backslashes, quotes, newlines, control characters and non-ASCII characters are escaped, so the output is pure ASCII.
+    /// 
+    /// Characters outside the Basic Multilingual Plane are escaped with `\U` and 8 hex digits.
+    /// 
+    /// Example: `"café\n😀" -> "caf\u00e9\n\U0001f600"`
+    Ascii = 1,
+    /// Only backslashes and quotes are escaped, since all other characters (including newlines) can be written as-is in quoted strings.
+    /// 
+    /// Example: `"café\n" -> "café<newline>"`
+    Minimal = 2,
+}
\ No newline at end of file
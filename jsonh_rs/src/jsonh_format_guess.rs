@@ -0,0 +1,21 @@
+/// The most likely format of a document (see `detect_format`).
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhFormatGuess {
+    /// Strict JSON, which is also valid JSONH.
+    /// 
+    /// Example: `{"a": [1, 2]}`
+    Json = 0,
+    /// JSONH readable by JSONH V1.
+    /// 
+    /// Example: `{ a: [1, 2,] } // comment`
+    JsonhV1 = 1,
+    /// JSONH only readable by JSONH V2.
+    /// 
+    /// Example: `{ a: @"C:\path" }`
+    JsonhV2 = 2,
+    /// Neither JSON nor JSONH.
+    /// 
+    /// Example: `{ a: [1, 2 }`
+    NotJsonh = 3,
+}
\ No newline at end of file
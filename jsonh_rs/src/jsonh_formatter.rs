@@ -0,0 +1,986 @@
+use std::cmp::Ordering;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhToken;
+use crate::JsonTokenType;
+use crate::JsonhFormatterOptions;
+use crate::JsonhVersion;
+use crate::JsonhQuoteStyle;
+use crate::JsonhCommentStyle;
+use crate::JsonhTextEdit;
+use crate::Value;
+
+/// Methods for formatting JSONH text in a consistent style.
+/// 
+/// Comments, literals and strings are preserved exactly as written. Only the layout is changed:
+/// ```
+/// {a:1,b:[2,3]  # comment
+/// }
+/// ```
+/// becomes:
+/// ```
+/// {
+///     a: 1,
+///     b: [
+///         2,
+///         3
+///     ] # comment
+/// }
+/// ```
+pub struct JsonhFormatter {
+}
+
+impl JsonhFormatter {
+    /// Formats a single JSONH element and the comments surrounding it.
+    pub fn format(source: &str, options: JsonhFormatterOptions) -> Result<String, &'static str> {
+        let document: FormatDocument = Self::read_document(source, options)?;
+
+        let mut printer: FormatPrinter = FormatPrinter::new(options);
+        printer.print_document(&document);
+        return Ok(printer.output);
+    }
+    /// Formats a single JSONH element, sorting the properties of each object alphabetically by name.
+    /// 
+    /// The comments before and after each property are moved along with it.
+    /// ```
+    /// {
+    ///     // second
+    ///     b: 2,
+    ///     a: 1 // first
+    /// }
+    /// ```
+    /// becomes:
+    /// ```
+    /// {
+    ///     a: 1, // first
+    ///     // second
+    ///     b: 2
+    /// }
+    /// ```
+    pub fn sort_keys(source: &str, options: JsonhFormatterOptions) -> Result<String, &'static str> {
+        return Self::sort_keys_by(source, options, |name1, name2| name1.cmp(name2));
+    }
+    /// Formats a single JSONH element, sorting the properties of each object by name with the given comparator.
+    /// 
+    /// The comments before and after each property are moved along with it. Properties that compare equal keep their order.
+    pub fn sort_keys_by(source: &str, options: JsonhFormatterOptions, mut compare: impl FnMut(&str, &str) -> Ordering) -> Result<String, &'static str> {
+        let mut document: FormatDocument = Self::read_document(source, options)?;
+        document.root.sort_keys(&mut compare);
+
+        let mut printer: FormatPrinter = FormatPrinter::new(options);
+        printer.print_document(&document);
+        return Ok(printer.output);
+    }
+    /// Returns whether the JSONH element is already formatted with the given options.
+    pub fn is_formatted(source: &str, options: JsonhFormatterOptions) -> Result<bool, &'static str> {
+        return Ok(Self::format(source, options)? == source);
+    }
+
+    /// Infers options matching the style of an existing JSONH element, so that formatting it changes as little as possible.
+    /// 
+    /// The indentation, newlines, commas, quotes and comments are inferred from the source.
+    /// Any styles that cannot be inferred (such as the indentation of a single-line element) are taken from the given options.
+    /// 
+    /// ```
+    /// let options: JsonhFormatterOptions = JsonhFormatter::infer_options(original, JsonhFormatterOptions::new())?;
+    /// let formatted: String = JsonhFormatter::format(edited, options)?;
+    /// ```
+    pub fn infer_options(source: &str, options: JsonhFormatterOptions) -> Result<JsonhFormatterOptions, &'static str> {
+        let tokens: Vec<JsonhToken> = Self::read_tokens(source, options.version)?;
+        let chars: Vec<char> = source.chars().collect();
+
+        let mut inferer: StyleInferer<'_> = StyleInferer { tokens: &tokens, chars: &chars };
+        return Ok(inferer.infer(options));
+    }
+
+    /// Converts comments of the given styles to another style, without changing the layout of the rest of the document.
+    /// 
+    /// Comments that cannot be represented in the new style (such as block comments followed by a value on the same line) are left unchanged.
+    /// 
+    /// ```
+    /// let converted: String = JsonhFormatter::convert_comments(source, &[JsonhCommentStyle::Hash, JsonhCommentStyle::Block], JsonhCommentStyle::Line, JsonhVersion::Latest)?;
+    /// ```
+    pub fn convert_comments(source: &str, from: &[JsonhCommentStyle], to: JsonhCommentStyle, version: JsonhVersion) -> Result<String, &'static str> {
+        let edits: Vec<JsonhTextEdit> = Self::convert_comments_edits(source, from, to, version)?;
+        return Ok(JsonhTextEdit::apply_all(source, &edits));
+    }
+    /// Returns the edits that convert comments of the given styles to another style.
+    /// 
+    /// See `convert_comments`.
+    pub fn convert_comments_edits(source: &str, from: &[JsonhCommentStyle], to: JsonhCommentStyle, version: JsonhVersion) -> Result<Vec<JsonhTextEdit>, &'static str> {
+        let tokens: Vec<JsonhToken> = Self::read_tokens(source, version)?;
+        let chars: Vec<char> = source.chars().collect();
+
+        let mut edits: Vec<JsonhTextEdit> = Vec::new();
+        for token in tokens.iter().filter(|token| token.json_type == JsonTokenType::Comment) {
+            let raw: String = chars[(token.start as usize)..(token.end as usize)].iter().collect();
+            let Some(style) = JsonhCommentStyle::of(&raw) else {
+                continue;
+            };
+            if style == to || !from.contains(&style) {
+                continue;
+            }
+
+            // Only whitespace until end of line
+            let is_end_of_line: bool = chars[(token.end as usize)..].iter()
+                .take_while(|next| !matches!(next, '\n' | '\r' | '\u{2028}' | '\u{2029}'))
+                .all(|next| next.is_whitespace());
+
+            if let Some(converted) = Self::convert_comment(&token.value, to, is_end_of_line) {
+                edits.push(JsonhTextEdit::new(token.start, token.end, converted));
+            }
+        }
+        return Ok(edits);
+    }
+
+    /// Generates a template JSONH element from a JSON schema (such as one generated by `schemars`), with every property present and annotated with its description.
+    /// 
+    /// Each value is the schema's default value if it has one, otherwise a placeholder for its type (e.g. `""` or `0`).
+    /// References to `$defs` and `definitions` are resolved. Recursive references are written as `null`.
+    /// 
+    /// ```
+    /// let schema: Value = serde_json::to_value(schemars::schema_for!(Config))?;
+    /// let template: String = JsonhFormatter::skeleton(&schema, JsonhFormatterOptions::new())?;
+    /// ```
+    /// ```
+    /// // The server configuration.
+    /// {
+    ///     // The host name.
+    ///     host: "localhost",
+    ///     // The port to listen on.
+    ///     port: 0
+    /// }
+    /// ```
+    pub fn skeleton(schema: &Value, options: JsonhFormatterOptions) -> Result<String, &'static str> {
+        let mut builder: SkeletonBuilder<'_> = SkeletonBuilder { root: schema, references: Vec::new(), source: String::new() };
+        builder.build_comment(schema);
+        builder.build_value(schema)?;
+
+        return Self::format(&builder.source, options);
+    }
+
+    /// Returns whether the string can be written without quotes and read back unchanged.
+    pub(crate) fn is_quoteless_safe(value: &str, is_property_name: bool, version: JsonhVersion) -> bool {
+        // Whitespace would be trimmed
+        if value.is_empty() || value.trim() != value || value.contains(['\n', '\r', '\u{2028}', '\u{2029}']) {
+            return false;
+        }
+
+        // Read back quoteless string
+        let reader_options: JsonhReaderOptions = JsonhReaderOptions::new().with_version(version);
+        if is_property_name {
+            let source: String = format!("{{{value}: 0}}");
+            return match JsonhReader::parse_element_from_str(&source, reader_options) {
+                Ok(Value::Object(object)) => object.len() == 1 && object.contains_key(value),
+                _ => false,
+            };
+        }
+        else {
+            return JsonhReader::parse_element_from_str(value, reader_options).is_ok_and(|element| element.as_str() == Some(value));
+        }
+    }
+    /// Writes the string in quotes, escaping any quotes, backslashes and control characters.
+    pub(crate) fn quote(value: &str, quote: char) -> String {
+        return quote.to_string() + &Self::escape(value, Some(quote)) + &quote.to_string();
+    }
+    /// Escapes any backslashes, control characters and (optionally) quotes in the string.
+    pub(crate) fn escape(value: &str, quote: Option<char>) -> String {
+        let mut escaped: String = String::new();
+        for next in value.chars() {
+            match next {
+                '\\' => escaped += "\\\\",
+                '\n' => escaped += "\\n",
+                '\r' => escaped += "\\r",
+                '\t' => escaped += "\\t",
+                '\u{8}' => escaped += "\\b",
+                '\u{c}' => escaped += "\\f",
+                _ if Some(next) == quote => {
+                    escaped.push('\\');
+                    escaped.push(next);
+                },
+                _ if next.is_control() || matches!(next, '\u{2028}' | '\u{2029}') => {
+                    escaped += &format!("\\u{:04x}", next as u32);
+                },
+                _ => escaped.push(next),
+            }
+        }
+        return escaped;
+    }
+    /// Writes the string in multiple quotes over multiple lines, indenting each line with the given indent.
+    /// 
+    /// Returns `None` if the string contains newlines other than `\n`, since they cannot be read back unchanged.
+    pub(crate) fn multi_quote(value: &str, quote: char, indent: &str) -> Option<String> {
+        if value.contains(['\r', '\u{2028}', '\u{2029}']) {
+            return None;
+        }
+
+        // Use more quotes than the longest run of quotes in the string
+        let mut quote_counter: usize = 3;
+        let mut run_counter: usize = 0;
+        for next in value.chars() {
+            run_counter = if next == quote { run_counter + 1 } else { 0 };
+            quote_counter = quote_counter.max(run_counter + 1);
+        }
+        let quotes: String = quote.to_string().repeat(quote_counter);
+
+        let mut quoted: String = quotes.clone();
+        for line in value.split('\n') {
+            quoted.push('\n');
+            if !line.is_empty() {
+                quoted += indent;
+            }
+            quoted += &Self::escape(line, None);
+        }
+        quoted.push('\n');
+        quoted += indent;
+        quoted += &quotes;
+        return Some(quoted);
+    }
+    /// Converts a quoted string or property name to use the given quotes, escaping any quotes in its contents.
+    /// 
+    /// Returns `None` for multi-quoted, verbatim and quoteless strings.
+    pub(crate) fn requote(raw: &str, quote: char) -> Option<String> {
+        let chars: Vec<char> = raw.chars().collect();
+
+        // Multi-quoted and verbatim strings cannot be requoted without changing their meaning
+        if chars.len() < 2 || !matches!(chars[0], '"' | '\'') || chars[1] == chars[0] {
+            return None;
+        }
+
+        let mut requoted: String = String::new();
+        requoted.push(quote);
+        let mut index: usize = 1;
+        while index < chars.len() - 1 {
+            let next: char = chars[index];
+            // Escape sequence
+            if next == '\\' {
+                requoted.push(next);
+                if let Some(escaped) = chars.get(index + 1) {
+                    requoted.push(*escaped);
+                }
+                index += 2;
+                continue;
+            }
+            // New quote
+            if next == quote {
+                requoted.push('\\');
+            }
+            requoted.push(next);
+            index += 1;
+        }
+        requoted.push(quote);
+        return Some(requoted);
+    }
+    /// Converts the contents of a comment to a comment of the given style.
+    /// 
+    /// Returns `None` if the contents cannot be represented in the style (e.g. a multi-line block comment as a line comment).
+    pub(crate) fn convert_comment(value: &str, style: JsonhCommentStyle, is_end_of_line: bool) -> Option<String> {
+        match style {
+            // Line comment
+            JsonhCommentStyle::Hash | JsonhCommentStyle::Line => {
+                if !is_end_of_line || value.contains(['\n', '\r', '\u{2028}', '\u{2029}']) {
+                    return None;
+                }
+                let prefix: &str = if style == JsonhCommentStyle::Hash { "#" } else { "//" };
+                return Some(prefix.to_string() + value.trim_end());
+            },
+            // Block comment
+            JsonhCommentStyle::Block => {
+                if value.contains("*/") {
+                    return None;
+                }
+                let suffix: &str = if value.starts_with(' ') && !value.ends_with(' ') { " */" } else { "*/" };
+                return Some("/*".to_string() + value + suffix);
+            },
+        }
+    }
+
+    fn read_document(source: &str, options: JsonhFormatterOptions) -> Result<FormatDocument, &'static str> {
+        let tokens: Vec<JsonhToken> = Self::read_tokens(source, options.version)?;
+
+        let chars: Vec<char> = source.chars().collect();
+        let mut builder: FormatTreeBuilder<'_> = FormatTreeBuilder { tokens: &tokens, chars: &chars, index: 0, previous_end: 0 };
+        return Ok(builder.build_document());
+    }
+    fn read_tokens(source: &str, version: JsonhVersion) -> Result<Vec<JsonhToken>, &'static str> {
+        let reader_options: JsonhReaderOptions = JsonhReaderOptions::new().with_version(version);
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(source, reader_options);
+
+        // Read element and trailing comments
+        let mut tokens: Vec<JsonhToken> = Vec::new();
+        for token_result in reader.read_element().raw() {
+            tokens.push(token_result?);
+        }
+        for token_result in reader.read_end_of_elements().raw() {
+            tokens.push(token_result?);
+        }
+        return Ok(tokens);
+    }
+}
+
+/// A comment and its position relative to the surrounding lines.
+struct FormatComment {
+    /// The comment as written in the source.
+    raw: String,
+    /// The contents of the comment.
+    value: String,
+    /// Whether the comment was preceded by a blank line.
+    blank_line_before: bool,
+}
+
+/// An element along with its property name and comments.
+struct FormatNode {
+    /// The comments on the lines before the element.
+    leading_comments: Vec<FormatComment>,
+    /// Whether the element (or its leading comments) was preceded by a blank line.
+    blank_line_before: bool,
+    /// The property name as written in the source.
+    property_name: Option<String>,
+    /// The property name after parsing.
+    property_name_value: Option<String>,
+    /// The element itself.
+    value: FormatValue,
+    /// The comments on the same line after the element.
+    trailing_comments: Vec<FormatComment>,
+}
+
+impl FormatNode {
+    fn sort_keys(&mut self, compare: &mut dyn FnMut(&str, &str) -> Ordering) {
+        if let FormatValue::Structure { is_array, children, .. } = &mut self.value {
+            // Sort properties
+            if !*is_array {
+                children.sort_by(|child1, child2| {
+                    return compare(child1.property_name_value.as_deref().unwrap_or_default(), child2.property_name_value.as_deref().unwrap_or_default());
+                });
+            }
+            // Sort nested objects
+            for child in children {
+                child.sort_keys(compare);
+            }
+        }
+    }
+}
+
+/// The contents of an element.
+enum FormatValue {
+    /// A primitive.
+    Primitive {
+        /// The primitive as written in the source.
+        raw: String,
+        /// The type of the primitive.
+        json_type: JsonTokenType,
+        /// The primitive after parsing.
+        value: String,
+    },
+    /// An object or array.
+    Structure {
+        /// Whether the structure is an array rather than an object.
+        is_array: bool,
+        /// Whether the structure is an object without braces.
+        is_braceless: bool,
+        /// The comments on the same line as the opening brace/bracket.
+        open_comments: Vec<FormatComment>,
+        /// The properties or items of the structure.
+        children: Vec<FormatNode>,
+        /// The comments after the last property/item.
+        close_comments: Vec<FormatComment>,
+    },
+}
+
+/// A formatted element and the comments outside of it.
+struct FormatDocument {
+    /// The root element.
+    root: FormatNode,
+    /// The comments on the lines after the root element.
+    trailing_comments: Vec<FormatComment>,
+}
+
+/// Builds a tree of elements and comments from a stream of tokens.
+struct FormatTreeBuilder<'t> {
+    tokens: &'t [JsonhToken],
+    chars: &'t [char],
+    index: usize,
+    previous_end: u64,
+}
+
+impl<'t> FormatTreeBuilder<'t> {
+    fn build_document(&mut self) -> FormatDocument {
+        let leading_comments: Vec<FormatComment> = self.take_comments();
+        let root: FormatNode = self.build_node(leading_comments);
+        let trailing_comments: Vec<FormatComment> = self.take_comments();
+        return FormatDocument { root: root, trailing_comments: trailing_comments };
+    }
+    fn build_node(&mut self, mut leading_comments: Vec<FormatComment>) -> FormatNode {
+        let mut blank_line_before: bool = self.peek().is_some_and(|token| self.count_newlines(self.previous_end, token.start) >= 2);
+
+        // Property name
+        let mut property_name: Option<String> = None;
+        let mut property_name_value: Option<String> = None;
+        if let Some(token) = self.peek() && token.json_type == JsonTokenType::PropertyName {
+            let token: JsonhToken = self.take();
+            property_name = Some(self.raw(&token));
+            property_name_value = Some(token.value);
+
+            // Move comments between property name and value before property name
+            for mut comment in self.take_comments() {
+                comment.blank_line_before = blank_line_before;
+                blank_line_before = false;
+                leading_comments.push(comment);
+            }
+        }
+
+        // Value
+        let value: FormatValue = self.build_value();
+
+        // Comments on same line
+        let trailing_comments: Vec<FormatComment> = self.take_trailing_comments();
+
+        return FormatNode {
+            leading_comments: leading_comments,
+            blank_line_before: blank_line_before,
+            property_name: property_name,
+            property_name_value: property_name_value,
+            value: value,
+            trailing_comments: trailing_comments,
+        };
+    }
+    fn build_value(&mut self) -> FormatValue {
+        let token: JsonhToken = self.take();
+
+        // Structure
+        if matches!(token.json_type, JsonTokenType::StartObject | JsonTokenType::StartArray) {
+            let is_array: bool = token.json_type == JsonTokenType::StartArray;
+            let is_braceless: bool = token.start == token.end;
+
+            let open_comments: Vec<FormatComment> = if is_braceless { Vec::new() } else { self.take_trailing_comments() };
+
+            let mut children: Vec<FormatNode> = Vec::new();
+            let close_comments: Vec<FormatComment>;
+            loop {
+                let leading_comments: Vec<FormatComment> = self.take_comments();
+
+                // End of structure
+                if self.peek().is_none_or(|next| matches!(next.json_type, JsonTokenType::EndObject | JsonTokenType::EndArray)) {
+                    if self.peek().is_some() {
+                        self.take();
+                    }
+                    close_comments = leading_comments;
+                    break;
+                }
+
+                // Property or item
+                children.push(self.build_node(leading_comments));
+            }
+
+            return FormatValue::Structure {
+                is_array: is_array,
+                is_braceless: is_braceless,
+                open_comments: open_comments,
+                children: children,
+                close_comments: close_comments,
+            };
+        }
+        // Primitive
+        else {
+            return FormatValue::Primitive { raw: self.raw(&token), json_type: token.json_type, value: token.value };
+        }
+    }
+    fn take_comments(&mut self) -> Vec<FormatComment> {
+        let mut comments: Vec<FormatComment> = Vec::new();
+        while let Some(token) = self.peek() && token.json_type == JsonTokenType::Comment {
+            let blank_line_before: bool = self.count_newlines(self.previous_end, token.start) >= 2;
+            let token: JsonhToken = self.take();
+            comments.push(FormatComment { raw: self.raw(&token), value: token.value, blank_line_before: blank_line_before });
+        }
+        return comments;
+    }
+    fn take_trailing_comments(&mut self) -> Vec<FormatComment> {
+        let mut comments: Vec<FormatComment> = Vec::new();
+        while let Some(token) = self.peek() && token.json_type == JsonTokenType::Comment && self.count_newlines(self.previous_end, token.start) == 0 {
+            let token: JsonhToken = self.take();
+            comments.push(FormatComment { raw: self.raw(&token), value: token.value, blank_line_before: false });
+        }
+        return comments;
+    }
+    fn peek(&self) -> Option<&'t JsonhToken> {
+        return self.tokens.get(self.index);
+    }
+    fn take(&mut self) -> JsonhToken {
+        let token: JsonhToken = self.tokens[self.index].clone();
+        self.index += 1;
+        self.previous_end = token.end;
+        return token;
+    }
+    fn raw(&self, token: &JsonhToken) -> String {
+        return self.chars[(token.start as usize)..(token.end as usize)].iter().collect();
+    }
+    fn count_newlines(&self, start: u64, end: u64) -> usize {
+        let mut counter: usize = 0;
+        let mut index: usize = start as usize;
+        while index < (end as usize) {
+            let next: char = self.chars[index];
+            if matches!(next, '\n' | '\u{2028}' | '\u{2029}') {
+                counter += 1;
+            }
+            else if next == '\r' {
+                // Join CR LF
+                if self.chars.get(index + 1) != Some(&'\n') {
+                    counter += 1;
+                }
+            }
+            index += 1;
+        }
+        return counter;
+    }
+}
+
+/// Prints a tree of elements and comments.
+struct FormatPrinter {
+    options: JsonhFormatterOptions,
+    output: String,
+    is_start_of_structure: bool,
+}
+
+impl FormatPrinter {
+    fn new(options: JsonhFormatterOptions) -> Self {
+        return Self { options: options, output: String::new(), is_start_of_structure: true };
+    }
+    fn print_document(&mut self, document: &FormatDocument) {
+        self.print_node(&document.root, 0, false);
+        for comment in &document.trailing_comments {
+            self.print_line_comment(comment, 0);
+        }
+        if self.options.final_newline {
+            self.output += self.options.newline;
+        }
+    }
+    fn print_node(&mut self, node: &FormatNode, depth: usize, has_next: bool) {
+        // Leading comments
+        for comment in &node.leading_comments {
+            self.print_line_comment(comment, depth);
+        }
+
+        // Property name
+        self.print_newline(depth, node.blank_line_before);
+        if let Some(property_name) = &node.property_name {
+            self.print_string(property_name, node.property_name_value.as_deref().unwrap_or_default(), true);
+            self.output += ": ";
+        }
+
+        // Value
+        self.print_value(&node.value, depth);
+
+        // Comma
+        if has_next && self.options.commas {
+            self.output.push(',');
+        }
+
+        // Trailing comments
+        self.print_inline_comments(&node.trailing_comments);
+    }
+    fn print_value(&mut self, value: &FormatValue, depth: usize) {
+        match value {
+            // Primitive
+            FormatValue::Primitive { raw, json_type, value } => {
+                if *json_type == JsonTokenType::String {
+                    self.print_string_value(raw, value, depth);
+                }
+                else {
+                    self.output += raw;
+                }
+            },
+            // Braceless object
+            FormatValue::Structure { is_braceless: true, children, close_comments, .. } => {
+                for (index, child) in children.iter().enumerate() {
+                    self.print_node(child, depth, index + 1 < children.len());
+                }
+                for comment in close_comments {
+                    self.print_line_comment(comment, depth);
+                }
+            },
+            // Object or array
+            FormatValue::Structure { is_array, open_comments, children, close_comments, .. } => {
+                self.output.push(if *is_array { '[' } else { '{' });
+                self.is_start_of_structure = true;
+                self.print_inline_comments(open_comments);
+
+                // Empty structure
+                if open_comments.is_empty() && children.is_empty() && close_comments.is_empty() {
+                    self.output.push(if *is_array { ']' } else { '}' });
+                    self.is_start_of_structure = false;
+                    return;
+                }
+
+                for (index, child) in children.iter().enumerate() {
+                    self.print_node(child, depth + 1, index + 1 < children.len());
+                }
+                for comment in close_comments {
+                    self.print_line_comment(comment, depth + 1);
+                }
+
+                self.print_newline(depth, false);
+                self.output.push(if *is_array { ']' } else { '}' });
+            },
+        }
+    }
+    fn print_string_value(&mut self, raw: &str, value: &str, depth: usize) {
+        // Convert to multi-quoted string
+        if let Some(min_length) = self.options.multi_quoted_strings
+            && self.options.newline == "\n"
+            && raw.starts_with(['"', '\''])
+            && raw.chars().nth(1) != raw.chars().next()
+            && value.contains('\n')
+            && raw.chars().count() >= min_length
+        {
+            let quote: char = match self.options.quote_style {
+                Some(quote_style) => quote_style.quote(),
+                None => raw.chars().next().unwrap_or('"'),
+            };
+            let indent: String = self.options.indent_char.to_string().repeat((depth + 1) * self.options.indent_width);
+            if let Some(multi_quoted) = JsonhFormatter::multi_quote(value, quote, &indent) {
+                self.output += &multi_quoted;
+                return;
+            }
+        }
+
+        self.print_string(raw, value, false);
+    }
+    fn print_string(&mut self, raw: &str, value: &str, is_property_name: bool) {
+        let is_quoted: bool = raw.starts_with(['"', '\'']);
+        let quote: char = self.options.quote_style.unwrap_or(JsonhQuoteStyle::Double).quote();
+
+        // Remove quotes
+        if self.options.quoteless_strings == Some(true) && is_quoted && JsonhFormatter::is_quoteless_safe(value, is_property_name, self.options.version) {
+            self.output += value;
+        }
+        // Add quotes
+        else if self.options.quoteless_strings == Some(false) && !is_quoted {
+            self.output += &JsonhFormatter::quote(value, quote);
+        }
+        // Convert quotes
+        else if let Some(quote_style) = self.options.quote_style && let Some(requoted) = JsonhFormatter::requote(raw, quote_style.quote()) {
+            self.output += &requoted;
+        }
+        else {
+            self.output += raw;
+        }
+    }
+    fn print_inline_comments(&mut self, comments: &[FormatComment]) {
+        for (index, comment) in comments.iter().enumerate() {
+            self.output.push(' ');
+            self.print_comment(comment, index + 1 == comments.len());
+        }
+    }
+    fn print_line_comment(&mut self, comment: &FormatComment, depth: usize) {
+        self.print_newline(depth, comment.blank_line_before);
+        self.print_comment(comment, true);
+    }
+    fn print_comment(&mut self, comment: &FormatComment, is_end_of_line: bool) {
+        // Convert syntax
+        if let Some(comment_style) = self.options.comment_style
+            && JsonhCommentStyle::of(&comment.raw) != Some(comment_style)
+            && let Some(converted) = JsonhFormatter::convert_comment(&comment.value, comment_style, is_end_of_line)
+        {
+            self.output += &converted;
+        }
+        else {
+            self.output += &comment.raw;
+        }
+    }
+    fn print_newline(&mut self, depth: usize, blank_line: bool) {
+        if !self.output.is_empty() {
+            // Preserve blank line (except at start of structure)
+            if blank_line && !self.is_start_of_structure {
+                self.output += self.options.newline;
+            }
+            self.output += self.options.newline;
+        }
+        for _counter in 0..(depth * self.options.indent_width) {
+            self.output.push(self.options.indent_char);
+        }
+        self.is_start_of_structure = false;
+    }
+}
+
+/// Infers the style of an element from its tokens.
+struct StyleInferer<'t> {
+    tokens: &'t [JsonhToken],
+    chars: &'t [char],
+}
+
+impl<'t> StyleInferer<'t> {
+    fn infer(&mut self, mut options: JsonhFormatterOptions) -> JsonhFormatterOptions {
+        // Newline
+        if let Some(newline) = self.infer_newline() {
+            options.newline = newline;
+        }
+        options.final_newline = self.chars.last().is_some_and(|next| matches!(next, '\n' | '\r'));
+
+        // Indentation
+        if let Some((indent_char, indent_width)) = self.infer_indent() {
+            options.indent_char = indent_char;
+            options.indent_width = indent_width;
+        }
+
+        // Commas
+        if let Some(commas) = self.infer_commas() {
+            options.commas = commas;
+        }
+
+        // Quotes
+        let double_quotes: usize = self.count_quoted('"');
+        let single_quotes: usize = self.count_quoted('\'');
+        if double_quotes + single_quotes > 0 {
+            options.quote_style = Some(if double_quotes >= single_quotes { JsonhQuoteStyle::Double } else { JsonhQuoteStyle::Single });
+        }
+
+        // Comments
+        let comment_styles: [JsonhCommentStyle; 3] = [JsonhCommentStyle::Line, JsonhCommentStyle::Hash, JsonhCommentStyle::Block];
+        let comment_counts: Vec<usize> = comment_styles.iter().map(|style| self.count_comments(*style)).collect();
+        if comment_counts.iter().any(|count| *count > 0) {
+            let mut most_common: usize = 0;
+            for index in 1..comment_styles.len() {
+                if comment_counts[index] > comment_counts[most_common] {
+                    most_common = index;
+                }
+            }
+            options.comment_style = Some(comment_styles[most_common]);
+        }
+
+        return options;
+    }
+    fn infer_newline(&self) -> Option<&'static str> {
+        for (index, next) in self.chars.iter().enumerate() {
+            if *next == '\n' {
+                return Some("\n");
+            }
+            else if *next == '\r' {
+                return Some(if self.chars.get(index + 1) == Some(&'\n') { "\r\n" } else { "\r" });
+            }
+        }
+        return None;
+    }
+    fn infer_indent(&self) -> Option<(char, usize)> {
+        let mut space_lines: usize = 0;
+        let mut tab_lines: usize = 0;
+        let mut width_counts: Vec<usize> = Vec::new();
+
+        let mut depth: usize = 0;
+        for token in self.tokens {
+            let is_braced: bool = token.start != token.end;
+
+            // Leave structure
+            if matches!(token.json_type, JsonTokenType::EndObject | JsonTokenType::EndArray) && is_braced {
+                depth = depth.saturating_sub(1);
+            }
+
+            // Indentation of first token on line
+            if let Some(indent) = self.indent_before(token.start) && depth > 0 && !indent.is_empty() {
+                if indent.iter().all(|next| *next == '\t') {
+                    tab_lines += 1;
+                }
+                else if indent.iter().all(|next| *next == ' ') {
+                    space_lines += 1;
+                }
+                if indent.len() % depth == 0 {
+                    let width: usize = indent.len() / depth;
+                    if width_counts.len() <= width {
+                        width_counts.resize(width + 1, 0);
+                    }
+                    width_counts[width] += 1;
+                }
+            }
+
+            // Enter structure
+            if matches!(token.json_type, JsonTokenType::StartObject | JsonTokenType::StartArray) && is_braced {
+                depth += 1;
+            }
+        }
+
+        if space_lines + tab_lines == 0 {
+            return None;
+        }
+        let indent_char: char = if tab_lines > space_lines { '\t' } else { ' ' };
+
+        // Most common width
+        let mut indent_width: usize = 0;
+        for (width, count) in width_counts.iter().enumerate() {
+            if *count > width_counts[indent_width] {
+                indent_width = width;
+            }
+        }
+        if indent_width == 0 {
+            return None;
+        }
+        return Some((indent_char, indent_width));
+    }
+    fn indent_before(&self, position: u64) -> Option<&'t [char]> {
+        // Find start of line
+        let mut line_start: usize = (position as usize).min(self.chars.len());
+        while line_start > 0 && !matches!(self.chars[line_start - 1], '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+            line_start -= 1;
+        }
+
+        // Only whitespace before position
+        let indent: &'t [char] = self.chars.get(line_start..(position as usize))?;
+        if !indent.iter().all(|next| next.is_whitespace()) {
+            return None;
+        }
+        return Some(indent);
+    }
+    fn infer_commas(&self) -> Option<bool> {
+        let mut with_commas: usize = 0;
+        let mut without_commas: usize = 0;
+
+        let mut previous_end: u64 = 0;
+        let mut is_after_value: bool = false;
+        let mut has_comma: bool = false;
+        let mut has_newline: bool = false;
+        for token in self.tokens {
+            // Commas and newlines are between tokens
+            let gap: &[char] = self.chars.get((previous_end as usize)..(token.start as usize)).unwrap_or_default();
+            has_comma |= gap.contains(&',');
+            has_newline |= gap.iter().any(|next| matches!(next, '\n' | '\r' | '\u{2028}' | '\u{2029}'));
+            previous_end = token.end;
+
+            if token.json_type == JsonTokenType::Comment {
+                continue;
+            }
+
+            // Separator between properties/items on different lines
+            let is_value_start: bool = !matches!(token.json_type, JsonTokenType::EndObject | JsonTokenType::EndArray);
+            if is_after_value && is_value_start && has_newline {
+                if has_comma {
+                    with_commas += 1;
+                }
+                else {
+                    without_commas += 1;
+                }
+            }
+
+            is_after_value = !matches!(token.json_type, JsonTokenType::StartObject | JsonTokenType::StartArray | JsonTokenType::PropertyName);
+            has_comma = false;
+            has_newline = false;
+        }
+
+        if with_commas + without_commas == 0 {
+            return None;
+        }
+        return Some(with_commas >= without_commas);
+    }
+    fn count_quoted(&self, quote: char) -> usize {
+        return self.tokens.iter()
+            .filter(|token| matches!(token.json_type, JsonTokenType::String | JsonTokenType::PropertyName))
+            .filter(|token| self.chars.get(token.start as usize) == Some(&quote))
+            .count();
+    }
+    fn count_comments(&self, style: JsonhCommentStyle) -> usize {
+        return self.tokens.iter()
+            .filter(|token| token.json_type == JsonTokenType::Comment)
+            .filter(|token| {
+                let prefix: String = self.chars[(token.start as usize)..].iter().take(2).collect();
+                return JsonhCommentStyle::of(&prefix) == Some(style);
+            })
+            .count();
+    }
+}
+
+/// Writes a template JSONH element for a JSON schema, to be formatted afterwards.
+struct SkeletonBuilder<'t> {
+    root: &'t Value,
+    references: Vec<&'t str>,
+    source: String,
+}
+
+impl<'t> SkeletonBuilder<'t> {
+    fn build_comment(&mut self, schema: &'t Value) {
+        // Description alongside or inside reference
+        let description: Option<&Value> = schema.get("description").or_else(|| self.resolve(schema)?.get("description"));
+        let Some(description) = description.and_then(Value::as_str) else {
+            return;
+        };
+        for line in description.lines() {
+            self.source += "//";
+            if !line.is_empty() {
+                self.source.push(' ');
+            }
+            self.source += line;
+            self.source.push('\n');
+        }
+    }
+    fn build_value(&mut self, schema: &'t Value) -> Result<(), &'static str> {
+        // Reference
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            // Recursive reference
+            if self.references.contains(&reference) {
+                self.source += "null";
+                return Ok(());
+            }
+            let Some(referenced_schema) = self.resolve(schema) else {
+                return Err("Unresolved reference in schema");
+            };
+
+            self.references.push(reference);
+            self.build_value(referenced_schema)?;
+            self.references.pop();
+            return Ok(());
+        }
+
+        // Default value
+        if let Some(default) = schema.get("default").or_else(|| schema.get("const")).or_else(|| schema.get("enum").and_then(|values| values.get(0))) {
+            self.source += &serde_json::to_string(default).map_err(|_| "Invalid default value in schema")?;
+            return Ok(());
+        }
+
+        // Subschemas (preferring non-null)
+        for keyword in ["allOf", "anyOf", "oneOf"] {
+            if let Some(subschemas) = schema.get(keyword).and_then(Value::as_array) && !subschemas.is_empty() {
+                let subschema: &'t Value = subschemas.iter().find(|subschema| subschema.get("type").and_then(Value::as_str) != Some("null")).unwrap_or(&subschemas[0]);
+                return self.build_value(subschema);
+            }
+        }
+
+        // Type (preferring non-null)
+        let json_type: &str = match schema.get("type") {
+            Some(Value::String(json_type)) => json_type,
+            Some(Value::Array(json_types)) => json_types.iter().filter_map(Value::as_str).find(|json_type| *json_type != "null").unwrap_or("null"),
+            _ if schema.get("properties").is_some() => "object",
+            _ => "null",
+        };
+
+        match json_type {
+            // Object
+            "object" => {
+                self.source += "{\n";
+                if let Some(Value::Object(properties)) = schema.get("properties") {
+                    for (property_name, property_schema) in properties {
+                        self.build_comment(property_schema);
+                        self.source += &JsonhFormatter::quote(property_name, '"');
+                        self.source += ": ";
+                        self.build_value(property_schema)?;
+                        self.source += "\n";
+                    }
+                }
+                self.source += "}";
+            },
+            // Array
+            "array" => self.source += "[]",
+            // String
+            "string" => self.source += "\"\"",
+            // Number
+            "integer" | "number" => self.source += "0",
+            // Boolean
+            "boolean" => self.source += "false",
+            // Null
+            _ => self.source += "null",
+        }
+        return Ok(());
+    }
+    fn resolve(&self, schema: &'t Value) -> Option<&'t Value> {
+        let Some(reference) = schema.get("$ref").and_then(Value::as_str) else {
+            return Some(schema);
+        };
+        return self.root.pointer(reference.strip_prefix('#')?);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,196 @@
+use crate::JsonhVersion;
+use crate::JsonhQuoteStyle;
+use crate::JsonhCommentStyle;
+
+/// Options for a `JsonhFormatter`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[non_exhaustive]
+pub struct JsonhFormatterOptions {
+    /// Specifies the major version of the JSONH specification to use when reading the input.
+    pub version: JsonhVersion,
+    /// Sets the number of indent characters per level of nesting.
+    /// 
+    /// ```
+    /// // Indent width: 2
+    /// {
+    ///   a: b
+    /// }
+    /// ```
+    pub indent_width: usize,
+    /// Sets the character used for indentation, usually a space or a tab.
+    pub indent_char: char,
+    /// Sets the newline sequence used between lines, usually `"\n"` or `"\r\n"`.
+    pub newline: &'static str,
+    /// Enables/disables commas between properties and items.
+    /// 
+    /// ```
+    /// {
+    ///   a: b, // With commas
+    ///   c: d  // Without commas
+    /// }
+    /// ```
+    pub commas: bool,
+    /// Enables/disables a newline at the end of the output.
+    pub final_newline: bool,
+    /// Sets the quotes to convert quoted strings and property names to, or `None` to preserve the quotes as written.
+    /// 
+    /// Multi-quoted and verbatim strings are never converted.
+    /// 
+    /// ```
+    /// // Quote style: Double
+    /// 'a' -> "a"
+    /// ```
+    pub quote_style: Option<JsonhQuoteStyle>,
+    /// Sets the syntax to convert comments to, or `None` to preserve the comments as written.
+    /// 
+    /// Block comments are only converted to line comments if they are on a single line and nothing follows them on the same line.
+    /// 
+    /// ```
+    /// // Comment style: Line
+    /// # comment -> // comment
+    /// ```
+    pub comment_style: Option<JsonhCommentStyle>,
+    /// Enables/disables quoteless strings and property names, or `None` to preserve the quotes as written.
+    /// 
+    /// If disabled, quoteless strings are quoted (using `quote_style`, or double quotes).
+    /// If enabled, quotes are removed wherever the string would be read back unchanged under the configured version.
+    /// 
+    /// ```
+    /// // Quoteless strings: Some(true)
+    /// "a": "b c" -> a: b c
+    /// "d": "true" -> "d": "true"
+    /// ```
+    pub quoteless_strings: Option<bool>,
+    /// Sets the minimum length of strings containing escaped newlines to convert to multi-quoted strings, or `None` to never convert them.
+    /// 
+    /// Strings are only converted if the newline sequence is `"\n"`, since the newlines in the string are written as-is.
+    /// 
+    /// ```
+    /// // Multi-quoted strings: Some(0)
+    /// {
+    ///     a: "line 1\nline 2"
+    /// }
+    /// ```
+    /// becomes:
+    /// ```
+    /// {
+    ///     a: """
+    ///         line 1
+    ///         line 2
+    ///         """
+    /// }
+    /// ```
+    pub multi_quoted_strings: Option<usize>,
+}
+
+impl JsonhFormatterOptions {
+    /// Constructs a `JsonhFormatterOptions` with some default values.
+    pub fn new() -> Self {
+        return Self { version: JsonhVersion::Latest, indent_width: 4, indent_char: ' ', newline: "\n", commas: true, final_newline: true, quote_style: None, comment_style: None, quoteless_strings: None, multi_quoted_strings: None };
+    }
+    /// Specifies the major version of the JSONH specification to use when reading the input.
+    pub fn with_version(mut self, value: JsonhVersion) -> Self {
+        self.version = value;
+        return self;
+    }
+    /// Sets the number of indent characters per level of nesting.
+    /// 
+    /// ```
+    /// // Indent width: 2
+    /// {
+    ///   a: b
+    /// }
+    /// ```
+    pub fn with_indent_width(mut self, value: usize) -> Self {
+        self.indent_width = value;
+        return self;
+    }
+    /// Sets the character used for indentation, usually a space or a tab.
+    pub fn with_indent_char(mut self, value: char) -> Self {
+        self.indent_char = value;
+        return self;
+    }
+    /// Sets the newline sequence used between lines, usually `"\n"` or `"\r\n"`.
+    pub fn with_newline(mut self, value: &'static str) -> Self {
+        self.newline = value;
+        return self;
+    }
+    /// Enables/disables commas between properties and items.
+    /// 
+    /// ```
+    /// {
+    ///   a: b, // With commas
+    ///   c: d  // Without commas
+    /// }
+    /// ```
+    pub fn with_commas(mut self, value: bool) -> Self {
+        self.commas = value;
+        return self;
+    }
+    /// Enables/disables a newline at the end of the output.
+    pub fn with_final_newline(mut self, value: bool) -> Self {
+        self.final_newline = value;
+        return self;
+    }
+    /// Sets the quotes to convert quoted strings and property names to, or `None` to preserve the quotes as written.
+    /// 
+    /// Multi-quoted and verbatim strings are never converted.
+    /// 
+    /// ```
+    /// // Quote style: Double
+    /// 'a' -> "a"
+    /// ```
+    pub fn with_quote_style(mut self, value: Option<JsonhQuoteStyle>) -> Self {
+        self.quote_style = value;
+        return self;
+    }
+    /// Sets the syntax to convert comments to, or `None` to preserve the comments as written.
+    /// 
+    /// Block comments are only converted to line comments if they are on a single line and nothing follows them on the same line.
+    /// 
+    /// ```
+    /// // Comment style: Line
+    /// # comment -> // comment
+    /// ```
+    pub fn with_comment_style(mut self, value: Option<JsonhCommentStyle>) -> Self {
+        self.comment_style = value;
+        return self;
+    }
+    /// Enables/disables quoteless strings and property names, or `None` to preserve the quotes as written.
+    /// 
+    /// If disabled, quoteless strings are quoted (using `quote_style`, or double quotes).
+    /// If enabled, quotes are removed wherever the string would be read back unchanged under the configured version.
+    /// 
+    /// ```
+    /// // Quoteless strings: Some(true)
+    /// "a": "b c" -> a: b c
+    /// "d": "true" -> "d": "true"
+    /// ```
+    pub fn with_quoteless_strings(mut self, value: Option<bool>) -> Self {
+        self.quoteless_strings = value;
+        return self;
+    }
+    /// Sets the minimum length of strings containing escaped newlines to convert to multi-quoted strings, or `None` to never convert them.
+    /// 
+    /// Strings are only converted if the newline sequence is `"\n"`, since the newlines in the string are written as-is.
+    /// 
+    /// ```
+    /// // Multi-quoted strings: Some(0)
+    /// {
+    ///     a: "line 1\nline 2"
+    /// }
+    /// ```
+    /// becomes:
+    /// ```
+    /// {
+    ///     a: """
+    ///         line 1
+    ///         line 2
+    ///         """
+    /// }
+    /// ```
+    pub fn with_multi_quoted_strings(mut self, value: Option<usize>) -> Self {
+        self.multi_quoted_strings = value;
+        return self;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,127 @@
+/// Precomputed line boundaries of a source, for converting between character positions, byte offsets and line/column numbers.
+/// 
+/// Lines are separated by `\n`, `\r`, `\r\n`, `\u{2028}` or `\u{2029}`. Line and column numbers start from 1, and columns count characters.
+/// 
+/// ```
+/// let line_index: JsonhLineIndex<'_> = JsonhLineIndex::new(source);
+/// for token in &tokens {
+///     let (line, column): (u64, u64) = line_index.location(token.start);
+///     println!("{line}:{column} {:?}", token.json_type);
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct JsonhLineIndex<'a> {
+    /// The source the lines were read from.
+    pub source: &'a str,
+    /// The byte offset and character position of the start of each line.
+    line_starts: Vec<(usize, u64)>,
+    /// The byte offset and character position of the end of each line (before the newline).
+    line_ends: Vec<(usize, u64)>,
+}
+
+impl<'a> JsonhLineIndex<'a> {
+    /// Constructs a line index by reading the source once.
+    pub fn new(source: &'a str) -> Self {
+        let mut line_starts: Vec<(usize, u64)> = vec![(0, 0)];
+        let mut line_ends: Vec<(usize, u64)> = Vec::new();
+
+        let mut position: u64 = 0;
+        let mut chars = source.char_indices().peekable();
+        while let Some((offset, next)) = chars.next() {
+            position += 1;
+            if !matches!(next, '\n' | '\r' | '\u{2028}' | '\u{2029}') {
+                continue;
+            }
+            line_ends.push((offset, position - 1));
+            // Join CR LF
+            if next == '\r' && chars.next_if(|(_, after)| *after == '\n').is_some() {
+                line_starts.push((offset + 2, position + 1));
+                position += 1;
+            }
+            else {
+                line_starts.push((offset + next.len_utf8(), position));
+            }
+        }
+        line_ends.push((source.len(), position));
+
+        return Self { source: source, line_starts: line_starts, line_ends: line_ends };
+    }
+    /// Returns the number of lines in the source (at least 1).
+    pub fn line_count(&self) -> usize {
+        return self.line_starts.len();
+    }
+    /// Returns the text of the line (starting from 1), excluding the newline.
+    pub fn line(&self, line: u64) -> Option<&'a str> {
+        let index: usize = self.line_number_index(line)?;
+        return Some(&self.source[self.line_starts[index].0..self.line_ends[index].0]);
+    }
+
+    /// Returns the line and column numbers of the character position.
+    /// 
+    /// Positions past the end of the source are clamped to the end, and positions within a newline are located at the end of the line.
+    pub fn location(&self, position: u64) -> (u64, u64) {
+        let index: usize = self.line_starts.partition_point(|(_, start)| *start <= position) - 1;
+        let column: u64 = position.min(self.line_ends[index].1) - self.line_starts[index].1;
+        return (index as u64 + 1, column + 1);
+    }
+    /// Returns the line and column numbers of the byte offset.
+    /// 
+    /// Offsets within a character are located at the start of the character.
+    pub fn offset_location(&self, offset: usize) -> (u64, u64) {
+        let index: usize = self.line_starts.partition_point(|(start, _)| *start <= offset) - 1;
+        let mut end: usize = offset.min(self.line_ends[index].0);
+        while !self.source.is_char_boundary(end) {
+            end -= 1;
+        }
+        let column: usize = self.source[self.line_starts[index].0..end].chars().count();
+        return (index as u64 + 1, column as u64 + 1);
+    }
+    /// Returns the character position of the line and column numbers, or `None` if they are not in the source.
+    /// 
+    /// The column after the last character of a line is the position of its newline.
+    pub fn position(&self, line: u64, column: u64) -> Option<u64> {
+        let index: usize = self.line_number_index(line)?;
+        let position: u64 = self.line_starts[index].1 + column.checked_sub(1)?;
+        if position > self.line_ends[index].1 {
+            return None;
+        }
+        return Some(position);
+    }
+    /// Returns the byte offset of the line and column numbers, or `None` if they are not in the source.
+    /// 
+    /// The column after the last character of a line is the offset of its newline.
+    pub fn offset(&self, line: u64, column: u64) -> Option<usize> {
+        let index: usize = self.line_number_index(line)?;
+        let (line_start, line_end): (usize, usize) = (self.line_starts[index].0, self.line_ends[index].0);
+        let mut offsets = self.source[line_start..line_end].char_indices().map(|(offset, _)| line_start + offset).chain([line_end]);
+        return offsets.nth(column.checked_sub(1)? as usize);
+    }
+
+    /// Returns the character position of the byte offset.
+    /// 
+    /// Offsets within a character are rounded down to the start of the character.
+    pub fn offset_to_position(&self, offset: usize) -> u64 {
+        let index: usize = self.line_starts.partition_point(|(start, _)| *start <= offset) - 1;
+        let mut end: usize = offset.min(self.source.len());
+        while !self.source.is_char_boundary(end) {
+            end -= 1;
+        }
+        return self.line_starts[index].1 + self.source[self.line_starts[index].0..end].chars().count() as u64;
+    }
+    /// Returns the byte offset of the character position.
+    /// 
+    /// Positions past the end of the source are clamped to the end.
+    pub fn position_to_offset(&self, position: u64) -> usize {
+        let index: usize = self.line_starts.partition_point(|(_, start)| *start <= position) - 1;
+        let (line_start, line_start_position): (usize, u64) = self.line_starts[index];
+        return self.source[line_start..].char_indices().nth((position - line_start_position) as usize).map_or(self.source.len(), |(offset, _)| line_start + offset);
+    }
+
+    fn line_number_index(&self, line: u64) -> Option<usize> {
+        let index: usize = line.checked_sub(1)? as usize;
+        if index >= self.line_starts.len() {
+            return None;
+        }
+        return Some(index);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use crate::JsonhToken;
+use crate::JsonhReaderOptions;
+
+/// The document being checked by a `JsonhLintRule`.
+pub struct JsonhLintContext<'s> {
+    /// The source text of the document.
+    pub source: &'s str,
+    /// The characters of the source text, indexed by token positions.
+    pub chars: Vec<char>,
+    /// The tokens read from the document (up to the first syntax error).
+    pub tokens: Vec<JsonhToken>,
+    /// The options used to read the document.
+    pub options: JsonhReaderOptions,
+}
+
+impl<'s> JsonhLintContext<'s> {
+    /// Returns the source text that the token was read from.
+    pub fn raw(&self, token: &JsonhToken) -> String {
+        return self.chars[(token.start as usize)..(token.end as usize)].iter().collect();
+    }
+}
\ No newline at end of file
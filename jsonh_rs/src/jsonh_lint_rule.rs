@@ -0,0 +1,29 @@
+use crate::JsonhDiagnostic;
+use crate::JsonhLintContext;
+
+/// A check run by a `JsonhLinter` over a JSONH document.
+/// 
+/// For example, a rule forbidding `null`:
+/// ```
+/// struct NoNullRule;
+/// 
+/// impl JsonhLintRule for NoNullRule {
+///     fn name(&self) -> &'static str {
+///         return "no-null";
+///     }
+///     fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+///         for token in &context.tokens {
+///             if token.json_type == JsonTokenType::Null {
+///                 diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), "Unexpected null".to_string())
+///                     .with_span(token.start, token.end));
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub trait JsonhLintRule {
+    /// Returns the unique name of the rule (e.g. `duplicate-keys`).
+    fn name(&self) -> &'static str;
+    /// Adds a diagnostic for each problem found in the document.
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>);
+}
\ No newline at end of file
@@ -0,0 +1,345 @@
+//! The built-in rules used by `JsonhLinter`.
+
+use std::collections::HashSet;
+
+use crate::JsonhDiagnostic;
+use crate::JsonhFormatter;
+use crate::JsonhLintContext;
+use crate::JsonhLintRule;
+use crate::JsonhNumberParser;
+use crate::JsonhSeverity;
+use crate::JsonhTextEdit;
+use crate::JsonhToken;
+use crate::JsonTokenType;
+use crate::JsonhReaderOptions;
+use crate::JsonhVersion;
+
+/// Warns about property names that appear more than once in the same object.
+/// 
+/// ```
+/// {
+///   a: 1,
+///   a: 2 // Warning: Duplicate property name
+/// }
+/// ```
+pub struct DuplicateKeysRule;
+
+impl JsonhLintRule for DuplicateKeysRule {
+    fn name(&self) -> &'static str {
+        return "duplicate-keys";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        let mut property_names: Vec<HashSet<&str>> = Vec::new();
+
+        for token in &context.tokens {
+            match token.json_type {
+                // Start structure
+                JsonTokenType::StartObject | JsonTokenType::StartArray => {
+                    property_names.push(HashSet::new());
+                },
+                // End structure
+                JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                    property_names.pop();
+                },
+                // Property name
+                JsonTokenType::PropertyName => {
+                    if let Some(current_property_names) = property_names.last_mut() && !current_property_names.insert(token.value.as_str()) {
+                        diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), format!("Duplicate property name `{}`", token.value))
+                            .with_span(token.start, token.end));
+                    }
+                },
+                // Other
+                _ => ()
+            }
+        }
+    }
+}
+
+/// Notes strings and property names quoted differently to the first quoted string.
+/// 
+/// Single-quoted and double-quoted strings are fixed by swapping the quotes.
+/// 
+/// ```
+/// {
+///   "a": 1,
+///   'b': 2 // Info: Inconsistent quotes
+/// }
+/// ```
+pub struct InconsistentQuotesRule;
+
+impl JsonhLintRule for InconsistentQuotesRule {
+    fn name(&self) -> &'static str {
+        return "inconsistent-quotes";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        let mut expected_quote: Option<char> = None;
+
+        for token in &context.tokens {
+            if !matches!(token.json_type, JsonTokenType::String | JsonTokenType::PropertyName) {
+                continue;
+            }
+
+            // Get quote (skipping verbatim symbol)
+            let raw: String = context.raw(token);
+            let Some(quote) = raw.trim_start_matches('@').chars().next().filter(|next| matches!(next, '"' | '\'')) else {
+                continue;
+            };
+
+            // Compare to first quote
+            match expected_quote {
+                None => expected_quote = Some(quote),
+                Some(expected) if expected != quote => {
+                    let mut diagnostic: JsonhDiagnostic = JsonhDiagnostic::new(JsonhSeverity::Info, self.name(), format!("Inconsistent quotes (expected `{expected}`)"))
+                        .with_span(token.start, token.end);
+                    if let Some(requoted) = JsonhFormatter::requote(&raw, expected) {
+                        diagnostic = diagnostic.with_edit(JsonhTextEdit::new(token.start, token.end, requoted));
+                    }
+                    diagnostics.push(diagnostic);
+                },
+                Some(_) => (),
+            }
+        }
+    }
+}
+
+/// Warns about objects and arrays nested more deeply than a maximum depth.
+/// 
+/// ```
+/// // Max depth: 2
+/// {
+///   a: {
+///     b: {} // Warning: Deeply nested
+///   }
+/// }
+/// ```
+pub struct DeepNestingRule {
+    /// The maximum depth allowed without a warning.
+    pub max_depth: i32,
+}
+
+impl DeepNestingRule {
+    /// Constructs the rule with the given maximum depth.
+    pub fn new(max_depth: i32) -> Self {
+        return Self { max_depth: max_depth };
+    }
+}
+
+impl JsonhLintRule for DeepNestingRule {
+    fn name(&self) -> &'static str {
+        return "deep-nesting";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        let mut current_depth: i32 = 0;
+
+        for token in &context.tokens {
+            match token.json_type {
+                // Start structure
+                JsonTokenType::StartObject | JsonTokenType::StartArray => {
+                    current_depth += 1;
+                    if current_depth == self.max_depth + 1 {
+                        diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), format!("Nested more deeply than {} levels", self.max_depth))
+                            .with_span(token.start, token.end));
+                    }
+                },
+                // End structure
+                JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                    current_depth -= 1;
+                },
+                // Other
+                _ => ()
+            }
+        }
+    }
+}
+
+/// Warns about whitespace at the end of lines (outside of strings).
+/// 
+/// Fixed by removing the whitespace.
+pub struct TrailingWhitespaceRule;
+
+impl JsonhLintRule for TrailingWhitespaceRule {
+    fn name(&self) -> &'static str {
+        return "trailing-whitespace";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        // Whitespace in strings is part of the string
+        let strings: Vec<&JsonhToken> = context.tokens.iter()
+            .filter(|token| matches!(token.json_type, JsonTokenType::String | JsonTokenType::PropertyName))
+            .collect();
+        let is_in_string = |index: u64| -> bool {
+            return strings.iter().any(|token| index >= token.start && index < token.end);
+        };
+
+        let mut whitespace_start: Option<usize> = None;
+        for index in 0..=context.chars.len() {
+            let next: Option<char> = context.chars.get(index).copied();
+
+            // End of line
+            if next.is_none_or(|next| matches!(next, '\n' | '\r' | '\u{2028}' | '\u{2029}')) {
+                if let Some(start) = whitespace_start && !is_in_string(start as u64) {
+                    diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), "Trailing whitespace".to_string())
+                        .with_span(start as u64, index as u64)
+                        .with_edit(JsonhTextEdit::delete(start as u64, index as u64)));
+                }
+                whitespace_start = None;
+            }
+            // Whitespace
+            else if next.is_some_and(char::is_whitespace) {
+                whitespace_start.get_or_insert(index);
+            }
+            // Non-whitespace
+            else {
+                whitespace_start = None;
+            }
+        }
+    }
+}
+
+/// Warns about numbers that are likely to be misinterpreted.
+/// 
+/// ```
+/// [
+///   007, // Warning: Leading zero
+///   1e999, // Warning: Out of range
+///   12345678901234567890 // Warning: Loses precision
+/// ]
+/// ```
+pub struct SuspiciousNumbersRule;
+
+impl JsonhLintRule for SuspiciousNumbersRule {
+    fn name(&self) -> &'static str {
+        return "suspicious-numbers";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        for token in &context.tokens {
+            if token.json_type != JsonTokenType::Number {
+                continue;
+            }
+
+            let digits: String = token.value.replace('_', "");
+            let unsigned_digits: &str = digits.trim_start_matches(['-', '+']);
+
+            // Leading zero
+            if unsigned_digits.len() > 1 && unsigned_digits.starts_with('0') && unsigned_digits[1..].starts_with(|next: char| next.is_ascii_digit()) {
+                diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), "Leading zero in number (octal numbers start with `0o`)".to_string())
+                    .with_span(token.start, token.end));
+                continue;
+            }
+
+            let Ok(number) = JsonhNumberParser::parse(token.value.clone()) else {
+                continue;
+            };
+
+            // Out of range
+            if !number.is_finite() {
+                diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), "Number is too large to be represented".to_string())
+                    .with_span(token.start, token.end));
+                continue;
+            }
+
+            // Integer loses precision
+            let (radix, integer_digits): (u32, &str) = match unsigned_digits.get(..2) {
+                Some("0x" | "0X") => (16, &unsigned_digits[2..]),
+                Some("0b" | "0B") => (2, &unsigned_digits[2..]),
+                Some("0o" | "0O") => (8, &unsigned_digits[2..]),
+                _ => (10, unsigned_digits),
+            };
+            if !integer_digits.is_empty() && integer_digits.chars().all(|next| next.is_digit(radix)) {
+                let is_exact: bool = match u128::from_str_radix(integer_digits, radix) {
+                    Ok(integer) => (number.abs() as u128) == integer,
+                    Err(_) => false,
+                };
+                if !is_exact {
+                    diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), "Integer cannot be represented exactly and will lose precision".to_string())
+                        .with_span(token.start, token.end));
+                }
+            }
+        }
+    }
+}
+
+/// Reports constructs that require a newer version of the specification than the given version.
+/// 
+/// ```
+/// // Version: V1
+/// {
+///   a: @"C:\Users", // Error: Verbatim strings require JSONH V2
+///   /=* nested *=/ // Error: Nestable block comments require JSONH V2
+/// }
+/// ```
+/// 
+/// This rule is not checked by default. The document should be read with the latest version so that newer constructs can be read.
+pub struct CompatibilityRule {
+    /// The version that the document must be compatible with.
+    pub version: JsonhVersion,
+}
+
+impl CompatibilityRule {
+    /// Constructs the rule with the given version.
+    pub fn new(version: JsonhVersion) -> Self {
+        return Self { version: version };
+    }
+}
+
+impl JsonhLintRule for CompatibilityRule {
+    fn name(&self) -> &'static str {
+        return "compatibility";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        let target_options: JsonhReaderOptions = JsonhReaderOptions::new().with_version(self.version);
+        if target_options.supports_version(JsonhVersion::V2) {
+            return;
+        }
+
+        for token in &context.tokens {
+            let raw: String = context.raw(token);
+
+            let construct: Option<&str> = match token.json_type {
+                // Verbatim string
+                JsonTokenType::String | JsonTokenType::PropertyName if raw.starts_with('@') => Some("Verbatim strings"),
+                // Nestable block comment
+                JsonTokenType::Comment if raw.starts_with("/=") => Some("Nestable block comments"),
+                // Other
+                _ => None,
+            };
+
+            if let Some(construct) = construct {
+                diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Error, self.name(), format!("{construct} require JSONH V2"))
+                    .with_span(token.start, token.end));
+            }
+        }
+    }
+}
+/// Warns about quoteless strings that look like misspelled literals.
+/// 
+/// ```
+/// {
+///   enabled: ture // Warning: Quoteless string looks like a misspelled `true`
+/// }
+/// ```
+pub struct SuspiciousLiteralsRule;
+
+impl JsonhLintRule for SuspiciousLiteralsRule {
+    fn name(&self) -> &'static str {
+        return "suspicious-literals";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        for token in &context.tokens {
+            if token.json_type != JsonTokenType::String {
+                continue;
+            }
+
+            // Quoteless string
+            let raw: String = context.raw(token);
+            if raw.starts_with(['"', '\'', '@']) {
+                continue;
+            }
+
+            if let Some(literal) = crate::suggest_literal(raw.trim()) {
+                diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Warning, self.name(), format!("Quoteless string looks like a misspelled `{literal}`"))
+                    .with_span(token.start, token.end)
+                    .with_edit(JsonhTextEdit::new(token.start, token.end, literal.to_string())));
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhToken;
+use crate::JsonhDiagnostic;
+use crate::JsonhSeverity;
+use crate::JsonhLintContext;
+use crate::JsonhLintRule;
+use crate::JsonhTextEdit;
+use crate::JsonhVersion;
+use crate::jsonh_lint_rules::*;
+
+/// Checks JSONH documents for syntax errors and likely mistakes.
+/// 
+/// ```
+/// let diagnostics: Vec<JsonhDiagnostic> = JsonhLinter::new(JsonhReaderOptions::new())
+///     .without_rule("trailing-whitespace")
+///     .lint(source);
+/// ```
+pub struct JsonhLinter {
+    /// The options to use when reading JSONH.
+    pub options: JsonhReaderOptions,
+    /// The rules to check.
+    pub rules: Vec<Box<dyn JsonhLintRule>>,
+}
+
+impl JsonhLinter {
+    /// Constructs a linter with the built-in rules.
+    pub fn new(options: JsonhReaderOptions) -> Self {
+        return Self::without_rules(options)
+            .with_rule(DuplicateKeysRule)
+            .with_rule(InconsistentQuotesRule)
+            .with_rule(DeepNestingRule::new(8))
+            .with_rule(TrailingWhitespaceRule)
+            .with_rule(SuspiciousNumbersRule)
+            .with_rule(SuspiciousLiteralsRule);
+    }
+    /// Constructs a linter that only checks for syntax errors.
+    pub fn without_rules(options: JsonhReaderOptions) -> Self {
+        return Self { options: options, rules: Vec::new() };
+    }
+    /// Adds a rule to check, replacing any rule with the same name.
+    pub fn with_rule(mut self, rule: impl JsonhLintRule + 'static) -> Self {
+        self = self.without_rule(rule.name());
+        self.rules.push(Box::new(rule));
+        return self;
+    }
+    /// Removes the rule with the given name.
+    pub fn without_rule(mut self, name: &str) -> Self {
+        self.rules.retain(|rule| rule.name() != name);
+        return self;
+    }
+
+    /// Checks a single JSONH element and the comments surrounding it.
+    /// 
+    /// Syntax errors are reported with the code `syntax`. The rules are checked against the tokens before the first syntax error.
+    /// 
+    /// The diagnostics are sorted by position.
+    pub fn lint(&self, source: &str) -> Vec<JsonhDiagnostic> {
+        let mut diagnostics: Vec<JsonhDiagnostic> = Vec::new();
+
+        // Read tokens
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(source, self.options);
+        let mut tokens: Vec<JsonhToken> = Vec::new();
+        let mut syntax_error: Option<&'static str> = None;
+        for token_result in reader.read_element().raw() {
+            match token_result {
+                Ok(token) => tokens.push(token),
+                Err(token_error) => syntax_error = Some(token_error),
+            }
+        }
+        if syntax_error.is_none() {
+            for token_result in reader.read_end_of_elements().raw() {
+                match token_result {
+                    Ok(token) => tokens.push(token),
+                    Err(token_error) => syntax_error = Some(token_error),
+                }
+            }
+        }
+
+        // Syntax error
+        if let Some(syntax_error) = syntax_error {
+            diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Error, "syntax", syntax_error.to_string())
+                .with_span(reader.char_counter, reader.char_counter));
+        }
+
+        // Check rules
+        let context: JsonhLintContext<'_> = JsonhLintContext { source: source, chars: source.chars().collect(), tokens: tokens, options: self.options };
+        for rule in &self.rules {
+            rule.check(&context, &mut diagnostics);
+        }
+
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.start, diagnostic.end));
+        return diagnostics;
+    }
+    /// Reports every construct in a single JSONH element that requires a newer version of the specification than the given version.
+    /// 
+    /// The element is read with the latest version. If it cannot be read with the latest version but can be read with the given version, it is compatible.
+    /// 
+    /// ```
+    /// let diagnostics: Vec<JsonhDiagnostic> = JsonhLinter::check_compatibility(source, JsonhVersion::V1);
+    /// ```
+    pub fn check_compatibility(source: &str, version: JsonhVersion) -> Vec<JsonhDiagnostic> {
+        let latest_linter: JsonhLinter = JsonhLinter::without_rules(JsonhReaderOptions::new().with_version(JsonhVersion::Latest).with_unknown_versions(true))
+            .with_rule(CompatibilityRule::new(version));
+        let diagnostics: Vec<JsonhDiagnostic> = latest_linter.lint(source);
+
+        // Syntax error only in latest version
+        if diagnostics.iter().any(|diagnostic| diagnostic.code == "syntax") {
+            let target_linter: JsonhLinter = JsonhLinter::without_rules(JsonhReaderOptions::new().with_version(version).with_unknown_versions(true));
+            if target_linter.lint(source).is_empty() {
+                return Vec::new();
+            }
+        }
+
+        return diagnostics;
+    }
+    /// Checks a single JSONH element and applies the fixes for the diagnostics found.
+    /// 
+    /// ```
+    /// let fixed: String = JsonhLinter::new(JsonhReaderOptions::new()).fix(source);
+    /// ```
+    pub fn fix(&self, source: &str) -> String {
+        return Self::apply_fixes(source, &self.lint(source));
+    }
+    /// Applies the fixes for the given diagnostics to the source text.
+    /// 
+    /// The edits for each diagnostic are applied together or not at all.
+    /// Diagnostics with edits overlapping the edits of an earlier diagnostic are skipped (they can be fixed by linting again).
+    pub fn apply_fixes(source: &str, diagnostics: &[JsonhDiagnostic]) -> String {
+        let mut edits: Vec<JsonhTextEdit> = Vec::new();
+
+        for diagnostic in diagnostics {
+            // Skip fixes overlapping previous fixes
+            if diagnostic.edits.iter().any(|edit| edits.iter().any(|other| edit.overlaps(other))) {
+                continue;
+            }
+            edits.extend(diagnostic.edits.iter().cloned());
+        }
+
+        return JsonhTextEdit::apply_all(source, &edits);
+    }
+}
\ No newline at end of file
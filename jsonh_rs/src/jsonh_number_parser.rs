@@ -59,6 +59,78 @@ impl JsonhNumberParser {
         return Ok(number);
     }
 
+    /// Converts a JSONH number to an integer without losing precision.
+    /// Returns `None` if the number has a fraction or an exponent, or does not fit in an `i128`.
+    /// For example:
+    /// 
+    /// ```
+    /// Input: -0xFFFF_FFFF_FFFF_FFFF
+    /// Output: -18446744073709551615
+    /// ```
+    pub fn parse_integer(jsonh_number: &str) -> Option<i128> {
+        let (is_negative, magnitude) = Self::parse_integer_magnitude(jsonh_number)?;
+        if is_negative {
+            return 0i128.checked_sub_unsigned(magnitude);
+        }
+        return i128::try_from(magnitude).ok();
+    }
+    /// Converts a non-negative JSONH number to an integer without losing precision.
+    /// Returns `None` if the number has a fraction or an exponent, is negative, or does not fit in a `u128`.
+    /// For example:
+    /// 
+    /// ```
+    /// Input: 0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF
+    /// Output: 340282366920938463463374607431768211455
+    /// ```
+    pub fn parse_unsigned_integer(jsonh_number: &str) -> Option<u128> {
+        let (is_negative, magnitude) = Self::parse_integer_magnitude(jsonh_number)?;
+        if is_negative && magnitude != 0 {
+            return None;
+        }
+        return Some(magnitude);
+    }
+
+    /// Converts a JSONH number to its sign and magnitude, or `None` if it is not an integer.
+    fn parse_integer_magnitude(jsonh_number: &str) -> Option<(bool, u128)> {
+        // Remove underscores
+        let jsonh_number: String = jsonh_number.replace('_', "");
+        let mut digits: &str = jsonh_number.as_str();
+
+        // Get sign
+        let mut is_negative: bool = false;
+        if let Some(unsigned) = digits.strip_prefix('-') {
+            is_negative = true;
+            digits = unsigned;
+        }
+        else if let Some(unsigned) = digits.strip_prefix('+') {
+            digits = unsigned;
+        }
+
+        // Get radix
+        let mut radix: u32 = 10;
+        if let Some(hex_digits) = digits.strip_prefix("0x") {
+            radix = 16;
+            digits = hex_digits;
+        }
+        else if let Some(binary_digits) = digits.strip_prefix("0b") {
+            radix = 2;
+            digits = binary_digits;
+        }
+        else if let Some(octal_digits) = digits.strip_prefix("0o") {
+            radix = 8;
+            digits = octal_digits;
+        }
+
+        // Ensure no fraction or exponent
+        if digits.is_empty() || !digits.chars().all(|digit| digit.is_digit(radix)) {
+            return None;
+        }
+
+        // Parse magnitude
+        let magnitude: u128 = u128::from_str_radix(digits, radix).ok()?;
+        return Some((is_negative, magnitude));
+    }
+
     /// Converts a fractional number with an exponent (e.g. `12.3e4.5`) from the given base (e.g. `01234567`) to a base-10 real.
     fn parse_fractional_number_with_exponent(digits: &str, base_digits: &str) -> Result<f64, &'static str> {
         // Find exponent
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use crate::JsonhToken;
+use crate::JsonTokenType;
+
+/// Statistics collected while parsing a JSONH element.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct JsonhParseStats {
+    /// The number of tokens read for each `JsonTokenType`, indexed by its value.
+    pub token_counts: [u64; 12],
+    /// The deepest level of nested objects and arrays reached.
+    pub max_depth: i32,
+    /// The total number of UTF-8 bytes in strings and property names.
+    pub string_bytes: u64,
+    /// The total number of UTF-8 bytes in comments.
+    pub comment_bytes: u64,
+    /// The time taken to parse the element.
+    pub duration: Duration,
+    /// The current level of nested objects and arrays.
+    current_depth: i32,
+}
+
+impl JsonhParseStats {
+    /// Constructs empty parse statistics.
+    pub fn new() -> Self {
+        return Self::default();
+    }
+    /// Returns the number of tokens read with the given type.
+    pub fn token_count(&self, json_type: JsonTokenType) -> u64 {
+        return self.token_counts[json_type as usize];
+    }
+    /// Returns the total number of tokens read.
+    pub fn total_tokens(&self) -> u64 {
+        return self.token_counts.iter().sum();
+    }
+    /// Adds a token to the statistics.
+    pub fn record_token(&mut self, token: &JsonhToken) {
+        self.token_counts[token.json_type as usize] += 1;
+
+        match token.json_type {
+            // Start structure
+            JsonTokenType::StartObject | JsonTokenType::StartArray => {
+                self.current_depth += 1;
+                self.max_depth = self.max_depth.max(self.current_depth);
+            },
+            // End structure
+            JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                self.current_depth -= 1;
+            },
+            // String
+            JsonTokenType::String | JsonTokenType::PropertyName => {
+                self.string_bytes += token.value.len() as u64;
+            },
+            // Comment
+            JsonTokenType::Comment => {
+                self.comment_bytes += token.value.len() as u64;
+            },
+            // Other
+            _ => ()
+        }
+    }
+}
\ No newline at end of file
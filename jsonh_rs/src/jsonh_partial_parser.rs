@@ -0,0 +1,59 @@
+#[cfg(feature = "async")]
+use futures_core::Stream;
+use serde_json::Value;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+#[cfg(feature = "async")]
+use crate::JsonhPartialStream;
+use crate::JsonhError;
+
+/// Assembles a JSONH element from chunks of text as they arrive, parsing the incomplete input after each chunk.
+/// 
+/// This is potentially useful for rendering structured output from large language models progressively.
+/// 
+/// ```
+/// let mut parser: JsonhPartialParser = JsonhPartialParser::new(JsonhReaderOptions::new());
+/// parser.push("{ name: Alice, tags: [a"); // Ok({"name": "Alice", "tags": ["a"]})
+/// parser.push(", b] }"); // Ok({"name": "Alice", "tags": ["a", "b"]})
+/// ```
+/// 
+/// Since the whole input is parsed after each chunk, this is best suited to small elements.
+pub struct JsonhPartialParser {
+    /// The options to use when reading JSONH. Incomplete inputs are always enabled.
+    pub options: JsonhReaderOptions,
+    /// The text received so far.
+    pub buffer: String,
+}
+
+impl JsonhPartialParser {
+    /// Constructs a parser with no text received.
+    pub fn new(options: JsonhReaderOptions) -> Self {
+        return Self { options: options.incomplete_inputs(true), buffer: String::new() };
+    }
+    /// Appends a chunk of text and parses the element received so far.
+    /// 
+    /// Returns an error if the text received so far cannot be parsed yet (e.g. it ends partway through a string).
+    pub fn push(&mut self, chunk: &str) -> Result<Value, JsonhError> {
+        self.buffer += chunk;
+        return JsonhReader::parse_element_from_str(&self.buffer, self.options);
+    }
+    /// Parses the text received so far as a complete element.
+    pub fn finish(&self) -> Result<Value, JsonhError> {
+        return JsonhReader::parse_element_from_str(&self.buffer, self.options.incomplete_inputs(false));
+    }
+    /// Consumes a stream of text chunks, yielding the element received so far whenever it changes (requires the `async` feature).
+    /// 
+    /// Chunks that cannot be parsed yet are skipped. If the complete text cannot be parsed at the end of the stream, the error is yielded.
+    /// 
+    /// ```
+    /// let mut values = JsonhPartialParser::new(JsonhReaderOptions::new()).stream(chunks);
+    /// while let Some(value) = values.next().await {
+    ///     render(value?);
+    /// }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn stream<S: Stream<Item = String> + Unpin>(self, chunks: S) -> JsonhPartialStream<S> {
+        return JsonhPartialStream::new(self, chunks);
+    }
+}
\ No newline at end of file
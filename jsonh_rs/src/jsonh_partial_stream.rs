@@ -0,0 +1,65 @@
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures_core::Stream;
+use serde_json::Value;
+
+use crate::JsonhPartialParser;
+use crate::JsonhError;
+
+/// A stream of the elements assembled by a `JsonhPartialParser` (requires the `async` feature).
+/// 
+/// See `JsonhPartialParser::stream`.
+pub struct JsonhPartialStream<S: Stream<Item = String> + Unpin> {
+    parser: JsonhPartialParser,
+    chunks: S,
+    previous_value: Option<Value>,
+    is_finished: bool,
+}
+
+impl<S: Stream<Item = String> + Unpin> JsonhPartialStream<S> {
+    /// Constructs a stream assembling elements from the chunks with the given parser.
+    pub fn new(parser: JsonhPartialParser, chunks: S) -> Self {
+        return Self { parser: parser, chunks: chunks, previous_value: None, is_finished: false };
+    }
+}
+
+impl<S: Stream<Item = String> + Unpin> Stream for JsonhPartialStream<S> {
+    type Item = Result<Value, JsonhError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.is_finished {
+                return Poll::Ready(None);
+            }
+
+            match Pin::new(&mut self.chunks).poll_next(context) {
+                // Next chunk
+                Poll::Ready(Some(chunk)) => {
+                    // Skip unchanged or incomplete values
+                    let Ok(value) = self.parser.push(&chunk) else {
+                        continue;
+                    };
+                    if self.previous_value.as_ref() == Some(&value) {
+                        continue;
+                    }
+                    self.previous_value = Some(value.clone());
+                    return Poll::Ready(Some(Ok(value)));
+                },
+                // End of stream
+                Poll::Ready(None) => {
+                    self.is_finished = true;
+                    return match self.parser.finish() {
+                        Ok(value) if self.previous_value.as_ref() == Some(&value) => Poll::Ready(None),
+                        result => Poll::Ready(Some(result)),
+                    };
+                },
+                // Waiting for chunk
+                Poll::Pending => {
+                    return Poll::Pending;
+                },
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+/// The kinds of operation in a JSON Patch (RFC 6902) document.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhPatchOp {
+    /// Inserts a value into an array or sets a property.
+    /// 
+    /// Example: `{ "op": "add", "path": "/a/0", "value": 1 }`
+    Add = 0,
+    /// Removes an item from an array or a property from an object.
+    /// 
+    /// Example: `{ "op": "remove", "path": "/a/0" }`
+    Remove = 1,
+    /// Replaces an existing value.
+    /// 
+    /// Example: `{ "op": "replace", "path": "/a", "value": 1 }`
+    Replace = 2,
+    /// Removes a value and adds it at another path.
+    /// 
+    /// Example: `{ "op": "move", "from": "/a", "path": "/b" }`
+    Move = 3,
+    /// Adds a copy of a value at another path.
+    /// 
+    /// Example: `{ "op": "copy", "from": "/a", "path": "/b" }`
+    Copy = 4,
+    /// Checks that a value is equal to the given value.
+    /// 
+    /// Example: `{ "op": "test", "path": "/a", "value": 1 }`
+    Test = 5,
+}
+
+impl JsonhPatchOp {
+    /// Returns the name of the operation in a JSON Patch document (e.g. `add`).
+    pub fn name(self) -> &'static str {
+        return match self {
+            JsonhPatchOp::Add => "add",
+            JsonhPatchOp::Remove => "remove",
+            JsonhPatchOp::Replace => "replace",
+            JsonhPatchOp::Move => "move",
+            JsonhPatchOp::Copy => "copy",
+            JsonhPatchOp::Test => "test",
+        };
+    }
+    /// Returns the operation with the given name in a JSON Patch document, or `None` if it is unknown.
+    pub fn from_name(name: &str) -> Option<Self> {
+        return match name {
+            "add" => Some(JsonhPatchOp::Add),
+            "remove" => Some(JsonhPatchOp::Remove),
+            "replace" => Some(JsonhPatchOp::Replace),
+            "move" => Some(JsonhPatchOp::Move),
+            "copy" => Some(JsonhPatchOp::Copy),
+            "test" => Some(JsonhPatchOp::Test),
+            _ => None,
+        };
+    }
+}
\ No newline at end of file
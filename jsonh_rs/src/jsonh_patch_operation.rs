@@ -0,0 +1,99 @@
+use std::fmt;
+
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::JsonhPatchOp;
+use crate::JsonhPath;
+
+/// A single operation in a JSON Patch (RFC 6902) document, as returned by `diff` and applied by `apply_patch`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonhPatchOperation {
+    /// The kind of operation.
+    pub op: JsonhPatchOp,
+    /// The path to the element the operation changes.
+    pub path: JsonhPath,
+    /// The path to the element to move or copy, for `Move` and `Copy`.
+    pub from: Option<JsonhPath>,
+    /// The element to add, replace with or test against, for `Add`, `Replace` and `Test`.
+    pub value: Option<Value>,
+}
+
+impl JsonhPatchOperation {
+    /// Constructs an operation adding the value at the path.
+    pub fn add(path: JsonhPath, value: Value) -> Self {
+        return Self { op: JsonhPatchOp::Add, path: path, from: None, value: Some(value) };
+    }
+    /// Constructs an operation removing the element at the path.
+    pub fn remove(path: JsonhPath) -> Self {
+        return Self { op: JsonhPatchOp::Remove, path: path, from: None, value: None };
+    }
+    /// Constructs an operation replacing the element at the path with the value.
+    pub fn replace(path: JsonhPath, value: Value) -> Self {
+        return Self { op: JsonhPatchOp::Replace, path: path, from: None, value: Some(value) };
+    }
+
+    /// Converts the operation to an object in a JSON Patch document.
+    /// 
+    /// ```
+    /// { "op": "replace", "path": "/a/0", "value": 1 }
+    /// ```
+    pub fn to_value(&self) -> Value {
+        let mut object: Map<String, Value> = Map::new();
+        object.insert("op".to_string(), Value::String(self.op.name().to_string()));
+        if let Some(from) = &self.from {
+            object.insert("from".to_string(), Value::String(from.to_pointer()));
+        }
+        object.insert("path".to_string(), Value::String(self.path.to_pointer()));
+        if let Some(value) = &self.value {
+            object.insert("value".to_string(), value.clone());
+        }
+        return Value::Object(object);
+    }
+    /// Converts an object in a JSON Patch document to an operation.
+    pub fn from_value(value: &Value) -> Result<Self, &'static str> {
+        let Value::Object(object) = value else {
+            return Err("Expected object for patch operation");
+        };
+
+        // Operation
+        let op: JsonhPatchOp = match object.get("op") {
+            Some(Value::String(name)) => JsonhPatchOp::from_name(name).ok_or("Unknown patch operation")?,
+            _ => return Err("Expected `op` in patch operation"),
+        };
+        // Path
+        let path: JsonhPath = match object.get("path") {
+            Some(Value::String(pointer)) => JsonhPath::from_pointer(pointer)?,
+            _ => return Err("Expected `path` in patch operation"),
+        };
+        // From
+        let from: Option<JsonhPath> = match (op, object.get("from")) {
+            (JsonhPatchOp::Move | JsonhPatchOp::Copy, Some(Value::String(pointer))) => Some(JsonhPath::from_pointer(pointer)?),
+            (JsonhPatchOp::Move | JsonhPatchOp::Copy, _) => return Err("Expected `from` in patch operation"),
+            _ => None,
+        };
+        // Value
+        let value: Option<Value> = match (op, object.get("value")) {
+            (JsonhPatchOp::Add | JsonhPatchOp::Replace | JsonhPatchOp::Test, Some(value)) => Some(value.clone()),
+            (JsonhPatchOp::Add | JsonhPatchOp::Replace | JsonhPatchOp::Test, None) => return Err("Expected `value` in patch operation"),
+            _ => None,
+        };
+
+        return Ok(Self { op: op, path: path, from: from, value: value });
+    }
+}
+
+impl fmt::Display for JsonhPatchOperation {
+    /// Formats the operation like `replace a[0]: 1` or `move a -> b`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", self.op.name())?;
+        if let Some(from) = &self.from {
+            write!(f, "{from} -> ")?;
+        }
+        write!(f, "{}", self.path)?;
+        if let Some(value) = &self.value {
+            write!(f, ": {value}")?;
+        }
+        return Ok(());
+    }
+}
\ No newline at end of file
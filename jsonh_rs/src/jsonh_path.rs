@@ -0,0 +1,165 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::JsonhPathSegment;
+
+/// A path from the root element to a nested element in a JSONH document.
+/// 
+/// For example, `a.b[2].c`:
+/// ```
+/// {
+///   a: {
+///     b: [0, 1, { c: /* Path */ 3 }]
+///   }
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct JsonhPath {
+    /// The segments of the path, starting from the root element.
+    pub segments: Vec<JsonhPathSegment>,
+}
+
+impl JsonhPath {
+    /// Constructs an empty path pointing to the root element.
+    pub fn new() -> Self {
+        return Self { segments: Vec::new() };
+    }
+    /// Returns whether the path points to the root element.
+    pub fn is_root(&self) -> bool {
+        return self.segments.is_empty();
+    }
+    /// Appends a segment to the end of the path.
+    pub fn push(&mut self, segment: JsonhPathSegment) {
+        self.segments.push(segment);
+    }
+    /// Removes the last segment from the end of the path.
+    pub fn pop(&mut self) -> Option<JsonhPathSegment> {
+        return self.segments.pop();
+    }
+    /// Formats the path as a JSON Pointer (RFC 6901), like `/a/b/2/c`, or an empty string for the root element.
+    pub fn to_pointer(&self) -> String {
+        let mut pointer: String = String::new();
+        for segment in &self.segments {
+            pointer.push('/');
+            match segment {
+                JsonhPathSegment::Property(name) => pointer += &name.replace('~', "~0").replace('/', "~1"),
+                JsonhPathSegment::Index(index) => pointer += &index.to_string(),
+            }
+        }
+        return pointer;
+    }
+    /// Parses a JSON Pointer (RFC 6901), like `/a/b/2/c`.
+    /// 
+    /// Since pointers do not distinguish property names from indexes, tokens of digits are parsed as indexes.
+    /// `JsonhPathSegment::Index` should also be treated as a property name when the parent element is an object.
+    pub fn from_pointer(pointer: &str) -> Result<Self, &'static str> {
+        let mut segments: Vec<JsonhPathSegment> = Vec::new();
+        if pointer.is_empty() {
+            return Ok(Self { segments: segments });
+        }
+        let Some(tokens) = pointer.strip_prefix('/') else {
+            return Err("Expected `/` at start of pointer");
+        };
+        for token in tokens.split('/') {
+            // Index (without leading zeros)
+            if !token.is_empty() && token.bytes().all(|byte| byte.is_ascii_digit()) && (token == "0" || !token.starts_with('0')) && let Ok(index) = token.parse() {
+                segments.push(JsonhPathSegment::Index(index));
+                continue;
+            }
+            // Property name
+            if token.replace("~0", "").replace("~1", "").contains('~') {
+                return Err("Invalid escape sequence in pointer");
+            }
+            segments.push(JsonhPathSegment::Property(token.replace("~1", "/").replace("~0", "~")));
+        }
+        return Ok(Self { segments: segments });
+    }
+}
+
+impl fmt::Display for JsonhPath {
+    /// Formats the path like `a.b[2].c`, or `$` for the root element.
+    /// 
+    /// `.`, `[`, `]` and `\` in property names are escaped with a backslash (as is a first property named `$`), so the path can be parsed back.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.segments.is_empty() {
+            return write!(f, "$");
+        }
+        for (index, segment) in self.segments.iter().enumerate() {
+            match segment {
+                JsonhPathSegment::Property(name) => {
+                    if index > 0 {
+                        write!(f, ".")?;
+                    }
+                    // Root symbol
+                    if index == 0 && name == "$" {
+                        write!(f, "\\$")?;
+                        continue;
+                    }
+                    for next in name.chars() {
+                        if matches!(next, '.' | '[' | ']' | '\\') {
+                            write!(f, "\\")?;
+                        }
+                        write!(f, "{next}")?;
+                    }
+                },
+                JsonhPathSegment::Index(array_index) => {
+                    write!(f, "[{array_index}]")?;
+                },
+            }
+        }
+        return Ok(());
+    }
+}
+
+impl FromStr for JsonhPath {
+    type Err = &'static str;
+
+    /// Parses a path formatted like `a.b[2].c`, or `$` for the root element.
+    /// 
+    /// Property names can escape `.`, `[`, `]` and `\` with a backslash (e.g. `a\.b` is the property `a.b`).
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut segments: Vec<JsonhPathSegment> = Vec::new();
+        if path == "$" {
+            return Ok(Self { segments: segments });
+        }
+
+        let mut chars: std::iter::Peekable<std::str::Chars<'_>> = path.chars().peekable();
+        while let Some(next) = chars.peek().copied() {
+            // Index
+            if next == '[' {
+                chars.next();
+                let mut digits: String = String::new();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                if chars.next() != Some(']') {
+                    return Err("Expected `]` to end index in path");
+                }
+                let index: usize = digits.parse().map_err(|_| "Invalid index in path")?;
+                segments.push(JsonhPathSegment::Index(index));
+                continue;
+            }
+
+            // Separator
+            if !segments.is_empty() && chars.next() != Some('.') {
+                return Err("Expected `.` or `[` in path");
+            }
+
+            // Property name
+            let mut name: String = String::new();
+            while let Some(next) = chars.next_if(|next| *next != '.' && *next != '[') {
+                if next == '\\' {
+                    name.push(chars.next().ok_or("Expected escaped character in path")?);
+                }
+                else {
+                    name.push(next);
+                }
+            }
+            if name.is_empty() {
+                return Err("Empty property name in path");
+            }
+            segments.push(JsonhPathSegment::Property(name));
+        }
+        return Ok(Self { segments: segments });
+    }
+}
\ No newline at end of file
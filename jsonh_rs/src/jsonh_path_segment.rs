@@ -0,0 +1,12 @@
+/// A single step in a path through a JSONH document.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhPathSegment {
+    /// A property name in an object.
+    /// 
+    /// Example: `a` in `{ a: 1 }`
+    Property(String),
+    /// An index in an array.
+    /// 
+    /// Example: `0` in `[ 1 ]`
+    Index(usize),
+}
\ No newline at end of file
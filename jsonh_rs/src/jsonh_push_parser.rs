@@ -0,0 +1,222 @@
+use std::cell::Ref;
+use std::cell::RefCell;
+use std::cell::RefMut;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use crate::JsonhError;
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhToken;
+use crate::JsonhReadState;
+use crate::JsonhSource;
+use crate::jsonh_reader::ReaderCheckpoint;
+
+/// Reads the tokens of a JSONH element from chunks of text as they are fed, such as from a socket.
+/// 
+/// ```
+/// let mut parser: JsonhPushParser = JsonhPushParser::new(JsonhReaderOptions::new());
+/// parser.feed("{ a: [1");
+/// parser.drain_tokens(); // [StartObject, PropertyName, StartArray]
+/// parser.feed("0] }");
+/// parser.drain_tokens(); // [Number, EndArray, EndObject]
+/// parser.finish()?;
+/// ```
+/// 
+/// Tokens can be drained once the text after them has been fed, since the last token may be continued by the next chunk.
+/// Errors at the end of the text fed so far (such as an unclosed string) are deferred until `finish`, since they may be resolved by the next chunk.
+/// 
+/// To read tokens one at a time, `read_token` returns `JsonhReadState::NeedMoreInput` until more text is fed:
+/// ```
+/// loop {
+///     match parser.read_token()? {
+///         JsonhReadState::Token(token) => handle(token),
+///         JsonhReadState::NeedMoreInput => parser.feed(&socket.receive()?),
+///         JsonhReadState::EndOfInput => break,
+///     }
+/// }
+/// ```
+/// 
+/// The reader is kept between chunks, so reading resumes from the start of the last incomplete token rather than the start of the input.
+pub struct JsonhPushParser {
+    /// The options to use when reading JSONH.
+    pub options: JsonhReaderOptions,
+    /// The reader reading tokens from `input`.
+    reader: JsonhReader<'static>,
+    /// The text fed so far, shared with the source of `reader`.
+    input: Rc<RefCell<PushInput>>,
+    /// The tokens read but not yet drained.
+    tokens: VecDeque<JsonhToken>,
+    /// The error from reading the text fed so far, if any.
+    error: Option<JsonhError>,
+    /// The character position after the last token drained.
+    drained_end: u64,
+    /// Whether the comments and whitespace after the element are being read.
+    is_element_read: bool,
+    /// Whether every token has been read.
+    is_ended: bool,
+}
+
+impl JsonhPushParser {
+    /// Constructs a parser with no text fed.
+    pub fn new(options: JsonhReaderOptions) -> Self {
+        let input: Rc<RefCell<PushInput>> = Rc::new(RefCell::new(PushInput { text: String::new(), offset: 0, position: 0, is_finished: false, is_starved: false }));
+        let reader: JsonhReader<'static> = JsonhReader::from_source(PushSource { input: input.clone() }, options);
+        return Self { options: options, reader: reader, input: input, tokens: VecDeque::new(), error: None, drained_end: 0, is_element_read: false, is_ended: false };
+    }
+    /// Appends a chunk of text and reads the tokens fed so far.
+    /// 
+    /// Chunks fed after `finish` are ignored.
+    pub fn feed(&mut self, chunk: &str) {
+        if self.is_finished() {
+            return;
+        }
+        self.append(chunk);
+        self.read_tokens();
+    }
+    /// Ends the input, so the last token can be drained.
+    /// 
+    /// Returns an error (located at where reading stopped) if the complete input cannot be read. The tokens before the error can still be drained.
+    pub fn finish(&mut self) -> Result<(), JsonhError> {
+        if !self.is_finished() {
+            self.input.borrow_mut().is_finished = true;
+            self.read_tokens();
+        }
+        return match &self.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        };
+    }
+    /// Returns whether `finish` has been called.
+    pub fn is_finished(&self) -> bool {
+        return self.input.borrow().is_finished;
+    }
+    /// Returns the text fed so far.
+    pub fn buffer(&self) -> Ref<'_, str> {
+        return Ref::map(self.input.borrow(), |input| input.text.as_str());
+    }
+
+    /// Removes and returns the next complete token, or `None` if more text is needed (or every token has been drained).
+    pub fn next_token(&mut self) -> Option<JsonhToken> {
+        let token: JsonhToken = self.tokens.pop_front()?;
+        self.drained_end = token.end;
+        return Some(token);
+    }
+    /// Removes and returns the next complete token, or the reason there is none.
+    /// 
+    /// Returns an error if the text fed so far cannot be read, unless the error may be resolved by the next chunk.
+    pub fn read_token(&mut self) -> Result<JsonhReadState, JsonhError> {
+        // Complete token
+        if let Some(token) = self.next_token() {
+            return Ok(JsonhReadState::Token(token));
+        }
+        // Error
+        if let Some(error) = &self.error {
+            return Err(error.clone());
+        }
+        // End of input
+        if self.is_ended && self.is_finished() {
+            return Ok(JsonhReadState::EndOfInput);
+        }
+        return Ok(JsonhReadState::NeedMoreInput);
+    }
+    /// Removes and returns every complete token.
+    pub fn drain_tokens(&mut self) -> Vec<JsonhToken> {
+        return std::iter::from_fn(|| self.next_token()).collect();
+    }
+
+    /// Returns the character position after the last token drained.
+    #[cfg(feature = "async")]
+    pub(crate) fn drained_end(&self) -> u64 {
+        return self.drained_end;
+    }
+    /// Marks every token as drained and stops reading tokens.
+    #[cfg(feature = "async")]
+    pub(crate) fn skip_tokens(&mut self) {
+        self.tokens.clear();
+        self.error = None;
+        self.is_ended = true;
+    }
+    /// Appends a chunk of text without reading tokens until the next chunk is fed or the input is finished.
+    pub(crate) fn append(&mut self, chunk: &str) {
+        self.input.borrow_mut().text.push_str(chunk);
+    }
+
+    fn read_tokens(&mut self) {
+        while !self.is_ended && self.error.is_none() {
+            // Save state before token
+            let checkpoint: ReaderCheckpoint = self.reader.checkpoint();
+            let (offset, position): (usize, u64) = {
+                let mut input: RefMut<'_, PushInput> = self.input.borrow_mut();
+                input.is_starved = false;
+                (input.offset, input.position)
+            };
+
+            let token_result: Option<Result<JsonhToken, &'static str>> = self.reader.read_next_token();
+
+            // Resume from token once more text is fed
+            if self.input.borrow().is_starved {
+                self.reader.restore(checkpoint);
+                let mut input: RefMut<'_, PushInput> = self.input.borrow_mut();
+                input.offset = offset;
+                input.position = position;
+                return;
+            }
+
+            match token_result {
+                // Token
+                Some(Ok(token)) => self.tokens.push_back(token),
+                // Error
+                Some(Err(error)) => self.error = Some(self.reader.locate_error(error)),
+                // End of element
+                None if !self.is_element_read && self.options.parse_single_element => {
+                    // Ensure exactly one element
+                    self.is_element_read = true;
+                    self.reader.begin_end_of_elements();
+                },
+                // End of input
+                None => self.is_ended = true,
+            }
+        }
+    }
+}
+
+/// The text fed to a `JsonhPushParser`.
+struct PushInput {
+    /// The text fed so far.
+    text: String,
+    /// The byte offset of the next character in `text`.
+    offset: usize,
+    /// The number of characters before the next character in `text`.
+    position: u64,
+    /// Whether the input has been finished, so no more text will be fed.
+    is_finished: bool,
+    /// Whether the reader reached the end of the text before the input was finished.
+    is_starved: bool,
+}
+
+/// A `JsonhSource` reading the text fed to a `JsonhPushParser`.
+struct PushSource {
+    input: Rc<RefCell<PushInput>>,
+}
+
+impl JsonhSource for PushSource {
+    fn peek(&mut self) -> Option<char> {
+        let mut input: RefMut<'_, PushInput> = self.input.borrow_mut();
+        let next: Option<char> = input.text[input.offset..].chars().next();
+        if next.is_none() && !input.is_finished {
+            input.is_starved = true;
+        }
+        return next;
+    }
+    fn next(&mut self) -> Option<char> {
+        let next: char = self.peek()?;
+        let mut input: RefMut<'_, PushInput> = self.input.borrow_mut();
+        input.offset += next.len_utf8();
+        input.position += 1;
+        return Some(next);
+    }
+    fn position(&self) -> u64 {
+        return self.input.borrow().position;
+    }
+}
\ No newline at end of file
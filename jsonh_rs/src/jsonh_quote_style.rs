@@ -0,0 +1,23 @@
+/// The quotes used to write strings and property names.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhQuoteStyle {
+    /// Double quotes.
+    /// 
+    /// Example: `"value"`
+    Double = 0,
+    /// Single quotes.
+    /// 
+    /// Example: `'value'`
+    Single = 1,
+}
+
+impl JsonhQuoteStyle {
+    /// Returns the quote character for the style.
+    pub fn quote(self) -> char {
+        return match self {
+            JsonhQuoteStyle::Double => '"',
+            JsonhQuoteStyle::Single => '\'',
+        };
+    }
+}
\ No newline at end of file
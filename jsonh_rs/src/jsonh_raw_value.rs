@@ -0,0 +1,83 @@
+use std::fmt;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de::DeserializeOwned;
+use serde::de::Visitor;
+use serde_json::Value;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhError;
+
+/// The exact source text of an element (including the comments before it), captured during deserialization so it can be parsed later.
+/// 
+/// ```
+/// struct Config {
+///     name: String,
+///     plugin: JsonhRawValue, // "/* options */ { level: 3 }"
+/// }
+/// let options: PluginOptions = config.plugin.parse(JsonhReaderOptions::new().with_version(JsonhVersion::V1))?;
+/// ```
+/// 
+/// Raw values are only captured by `JsonhDeserializer` with the source text (see `JsonhDeserializer::with_source`), as in `jsonh_rs::from_str`.
+/// Serializing a `JsonhRawValue` parses it and serializes the element.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct JsonhRawValue {
+    /// The source text of the element.
+    pub raw: String,
+}
+
+impl JsonhRawValue {
+    /// Constructs a raw value from the source text of an element.
+    pub fn new(raw: String) -> Self {
+        return Self { raw: raw };
+    }
+    /// Returns the source text of the element.
+    pub fn get(&self) -> &str {
+        return &self.raw;
+    }
+    /// Deserializes the element with the given options.
+    pub fn parse<T: DeserializeOwned>(&self, options: JsonhReaderOptions) -> Result<T, JsonhError> {
+        return crate::from_str_with_options(&self.raw, options);
+    }
+}
+
+impl fmt::Display for JsonhRawValue {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return formatter.write_str(&self.raw);
+    }
+}
+
+pub(crate) const RAW_VALUE_NAME: &str = "$__jsonh_private_RawValue";
+
+impl<'de> Deserialize<'de> for JsonhRawValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return deserializer.deserialize_newtype_struct(RAW_VALUE_NAME, RawValueVisitor);
+    }
+}
+
+impl Serialize for JsonhRawValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value: Value = JsonhReader::parse_element_from_str(&self.raw, JsonhReaderOptions::new()).map_err(serde::ser::Error::custom)?;
+        return value.serialize(serializer);
+    }
+}
+
+struct RawValueVisitor;
+
+impl Visitor<'_> for RawValueVisitor {
+    type Value = JsonhRawValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return formatter.write_str("a raw JSONH element");
+    }
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        return Ok(JsonhRawValue::new(value.to_string()));
+    }
+    fn visit_string<E: serde::de::Error>(self, value: String) -> Result<Self::Value, E> {
+        return Ok(JsonhRawValue::new(value));
+    }
+}
\ No newline at end of file
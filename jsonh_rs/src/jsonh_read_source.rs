@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::sync::Arc;
+
+use crate::JsonhSource;
+
+/// A `JsonhSource` reading UTF-8 text from a buffered byte reader, such as a file or a decompressor.
+/// 
+/// The text is decoded incrementally, so only the buffer of the byte reader is held in memory.
+/// Invalid UTF-8 sequences are read as the replacement character (`U+FFFD`).
+/// If the byte reader fails, the source ends and the reader returns an error caused by the I/O error.
+pub struct JsonhReadSource<R: BufRead> {
+    /// The buffered byte reader to read UTF-8 text from.
+    pub reader: R,
+    /// The number of characters read from `reader`.
+    pub position: u64,
+    /// The error returned by `reader`, if reading failed.
+    pub error: Option<Arc<io::Error>>,
+    /// The next character, if it has been peeked.
+    peeked: Option<Option<char>>,
+}
+
+impl<R: Read> JsonhReadSource<BufReader<R>> {
+    /// Constructs a source reading UTF-8 text from a byte reader, wrapping it in a `BufReader`.
+    pub fn new(reader: R) -> Self {
+        return Self::from_buf_read(BufReader::new(reader));
+    }
+}
+
+impl<R: BufRead> JsonhReadSource<R> {
+    /// Constructs a source reading UTF-8 text from a byte reader that is already buffered.
+    pub fn from_buf_read(reader: R) -> Self {
+        return Self { reader: reader, position: 0, error: None, peeked: None };
+    }
+
+    fn read_char(&mut self) -> Option<char> {
+        // Read first byte
+        let first: u8 = self.read_byte()?;
+        let length: usize = match first {
+            0x00..=0x7F => return Some(first as char),
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => return Some(char::REPLACEMENT_CHARACTER),
+        };
+
+        // Read continuation bytes
+        let mut bytes: [u8; 4] = [first, 0, 0, 0];
+        for continuation in bytes.iter_mut().take(length).skip(1) {
+            match self.peek_byte() {
+                Some(byte) if byte & 0xC0 == 0x80 => {
+                    *continuation = byte;
+                    self.reader.consume(1);
+                },
+                _ => return Some(char::REPLACEMENT_CHARACTER),
+            }
+        }
+        return Some(std::str::from_utf8(&bytes[..length]).ok().and_then(|string| string.chars().next()).unwrap_or(char::REPLACEMENT_CHARACTER));
+    }
+    fn read_byte(&mut self) -> Option<u8> {
+        let byte: u8 = self.peek_byte()?;
+        self.reader.consume(1);
+        return Some(byte);
+    }
+    fn peek_byte(&mut self) -> Option<u8> {
+        if self.error.is_some() {
+            return None;
+        }
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buffer) => return buffer.first().copied(),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.error = Some(Arc::new(error));
+                    return None;
+                },
+            }
+        }
+    }
+}
+
+impl<R: BufRead> JsonhSource for JsonhReadSource<R> {
+    fn peek(&mut self) -> Option<char> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.read_char());
+        }
+        return self.peeked.flatten();
+    }
+    fn next(&mut self) -> Option<char> {
+        let next: Option<char> = match self.peeked.take() {
+            Some(peeked) => peeked,
+            None => self.read_char(),
+        };
+        if next.is_some() {
+            self.position += 1;
+        }
+        return next;
+    }
+    fn position(&self) -> u64 {
+        return self.position;
+    }
+    fn error(&self) -> Option<&'static str> {
+        return self.error.as_ref().map(|_| "Failed to read input");
+    }
+    fn cause(&self) -> Option<Arc<dyn Error + Send + Sync>> {
+        return self.error.clone().map(|error| error as Arc<dyn Error + Send + Sync>);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use crate::JsonhToken;
+
+/// The result of reading the next token from a `JsonhPushParser`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum JsonhReadState {
+    /// The next complete token.
+    Token(JsonhToken),
+    /// The text fed so far ends partway through the element. Reading can be resumed after feeding more text.
+    /// 
+    /// The state of the reader (such as its depth, path and partially read structures) is kept, and only the incomplete token at the end of the text is read again.
+    NeedMoreInput,
+    /// Every token has been read and the input has been finished.
+    EndOfInput,
+}
\ No newline at end of file
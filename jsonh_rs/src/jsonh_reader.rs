@@ -1,22 +1,84 @@
-use std::{char, iter::Peekable, str::Chars};
+use std::{char, collections::VecDeque, fs::File, io::Read, iter::Peekable, ops::ControlFlow, path::Path, str::Chars, time::Instant};
+use serde::de::DeserializeOwned;
 use serde_json::{Value, Number};
-use yield_return::LocalIter;
 
 use crate::JsonhToken;
+use crate::JsonhTokenIter;
+use crate::jsonh_token_iter::TokenReader;
 use crate::JsonTokenType;
 use crate::JsonhReaderOptions;
 use crate::JsonhVersion;
 use crate::JsonhNumberParser;
+use crate::JsonhPath;
+use crate::JsonhPathSegment;
+use crate::JsonhParseStats;
+use crate::JsonhSourceMap;
+use crate::JsonhSourceMapping;
+use crate::JsonhComment;
+use crate::JsonhSource;
+use crate::JsonhCharSource;
+use crate::JsonhReadSource;
+use crate::JsonhBytesSource;
+use crate::JsonhTypeHint;
+use crate::JsonhError;
+use crate::JsonhVisitor;
+
+/// A callback invoked when a `JsonhReader` enters or exits an object or array.
+pub type JsonhStructureCallback<'a> = Box<dyn FnMut(JsonTokenType, &JsonhPath, i32) -> Result<(), &'static str> + 'a>;
+/// A callback invoked when a `JsonhReader` reads a comment.
+pub type JsonhCommentCallback<'a> = Box<dyn FnMut(&JsonhComment) -> Result<(), &'static str> + 'a>;
+/// A callback that reads a primitive element starting with a specific character from the source of a `JsonhReader`.
+pub type JsonhPrimitiveExtension<'a> = Box<dyn FnMut(&mut dyn JsonhSource) -> Result<JsonhToken, &'static str> + 'a>;
 
 pub struct JsonhReader<'a> {
-    /// The peekable character iterator to read characters from.
-    pub source: Peekable<Chars<'a>>,
+    /// The source to read characters from.
+    pub source: Box<dyn JsonhSource + 'a>,
     /// The options to use when reading JSONH.
     pub options: JsonhReaderOptions,
     /// The number of characters read from `source`.
     pub char_counter: u64,
+    /// The line number (starting from 1) of the next character in `source`.
+    pub line_counter: u64,
+    /// The column number (starting from 1) of the next character in `source`.
+    pub column_counter: u64,
     /// The current recursion depth of the reader.
     pub depth: i32,
+    /// The path from the root element to the element currently being read.
+    pub path: JsonhPath,
+    /// The callbacks invoked when the reader enters or exits an object or array.
+    /// 
+    /// Each callback is given the structure token type, the path to the structure and its depth.
+    /// Returning an error stops the reader.
+    pub structure_callbacks: Vec<JsonhStructureCallback<'a>>,
+    /// The callbacks invoked when the reader reads a comment.
+    /// 
+    /// Each callback is given the comment along with the path of the element being read.
+    /// Returning an error stops the reader.
+    pub comment_callbacks: Vec<JsonhCommentCallback<'a>>,
+    /// The additional characters that cannot be used unescaped in quoteless strings (see `with_reserved_chars`).
+    pub extra_reserved_chars: Vec<char>,
+    /// The callbacks that read primitive elements starting with each character (see `with_primitive_extension`).
+    pub primitive_extensions: Vec<(char, JsonhPrimitiveExtension<'a>)>,
+    /// The additional named literals matched in quoteless strings, along with the elements they represent (see `with_named_literal`).
+    pub named_literals: Vec<(String, Value)>,
+    /// The types that elements at each path are coerced to when parsing (see `with_type_hint`).
+    pub type_hints: Vec<(JsonhPath, JsonhTypeHint)>,
+    /// The approximate number of bytes allocated for token values and parsed elements (see `JsonhReaderOptions::max_allocation`).
+    pub allocated_bytes: usize,
+    /// The total number of bytes in comments read by the reader (see `JsonhReaderOptions::max_comment_bytes`).
+    pub comment_bytes: usize,
+    /// Statistics about every token read by the reader, such as the number of comments and the deepest level reached (the duration is not recorded).
+    /// 
+    /// Along with `char_counter`, this can be used to log metrics about untrusted input.
+    pub stats: JsonhParseStats,
+    /// The characters of the escape sequence currently being read, if they are being kept (see `JsonhReaderOptions::lazy_unescaping`).
+    raw_escape_builder: Option<String>,
+    /// The state of the element being read by `next_token`.
+    token_state: TokenState,
+    /// Whether the values of string and comment tokens are discarded rather than returned (see `discard_values`).
+    is_discarding_values: bool,
+    /// The buffer of the last discarded value, reused for the next value.
+    discarded_value: String,
 }
 
 impl<'a> JsonhReader<'a> {
@@ -26,6 +88,10 @@ impl<'a> JsonhReader<'a> {
     const RESERVED_CHARS_V1: &'static [char] = &['\\', ',', ':', '[', ']', '{', '}', '/', '#', '"', '\''];
     /// Characters that cannot be used unescaped in quoteless strings in JSONH V2.
     const RESERVED_CHARS_V2: &'static [char] = &['\\', ',', ':', '[', ']', '{', '}', '/', '#', '"', '\'', '@'];
+    /// The maximum number of bytes a string buffer is pre-sized to (see `JsonhReaderOptions::capacity_divisor`).
+    const MAX_PRESIZED_STRING_CAPACITY: usize = 256;
+    /// The maximum number of items an object or array is pre-sized to (see `JsonhReaderOptions::capacity_divisor`).
+    const MAX_PRESIZED_STRUCTURE_CAPACITY: usize = 16;
     /// Characters that are considered newlines.
     const NEWLINE_CHARS: &'static [char] = &['\n', '\r', '\u{2028}', '\u{2029}'];
     /// Characters that are considered whitespace.
@@ -35,9 +101,43 @@ impl<'a> JsonhReader<'a> {
         '\u{2029}', '\u{0009}', '\u{000A}', '\u{000B}', '\u{000C}', '\u{000D}', '\u{0085}',
     ];
 
+    /// Constructs a reader that reads JSONH from a custom source.
+    /// 
+    /// The character positions of tokens start from the position of the source.
+    pub fn from_source(source: impl JsonhSource + 'a, options: JsonhReaderOptions) -> Self {
+        let char_counter: u64 = source.position();
+        return Self { source: Box::new(source), options: options, char_counter: char_counter, line_counter: 1, column_counter: 1, depth: 0, path: JsonhPath::new(), structure_callbacks: Vec::new(), comment_callbacks: Vec::new(), extra_reserved_chars: Vec::new(), primitive_extensions: Vec::new(), named_literals: Vec::new(), type_hints: Vec::new(), allocated_bytes: 0, comment_bytes: 0, stats: JsonhParseStats::new(), raw_escape_builder: None, token_state: TokenState::new(), is_discarding_values: false, discarded_value: String::new() };
+    }
+    /// Constructs a reader that reads JSONH from UTF-8 bytes in a byte reader, such as a file or a decompressor.
+    /// 
+    /// ```
+    /// let reader: JsonhReader<'_> = JsonhReader::from_read(GzDecoder::new(File::open("config.jsonh.gz")?), JsonhReaderOptions::new());
+    /// ```
+    /// 
+    /// The bytes are decoded incrementally, so large files are parsed without loading them into memory.
+    /// If the byte reader fails, the reader returns an error caused by the I/O error.
+    /// 
+    /// The byte reader is wrapped in a `BufReader`. To read from a byte reader that is already buffered, use `JsonhReadSource::from_buf_read` with `from_source`.
+    pub fn from_read(source: impl Read + 'a, options: JsonhReaderOptions) -> Self {
+        return Self::from_source(JsonhReadSource::new(source), options);
+    }
+    /// Constructs a reader that reads JSONH from UTF-8 bytes, validating them as they are read.
+    /// 
+    /// Invalid UTF-8 sequences are returned as errors at their position, rather than replaced.
+    pub fn from_bytes(source: &'a [u8], options: JsonhReaderOptions) -> Self {
+        return Self::from_source(JsonhBytesSource::new(source), options);
+    }
+    /// Constructs a reader that reads JSONH from any character iterator, such as a decoder or a generated stream.
+    /// 
+    /// ```
+    /// let reader: JsonhReader<'_> = JsonhReader::from_char_iter(rope.chars(), JsonhReaderOptions::new());
+    /// ```
+    pub fn from_char_iter(source: impl Iterator<Item = char> + 'a, options: JsonhReaderOptions) -> Self {
+        return Self::from_source(JsonhCharSource::new(source), options);
+    }
     /// Constructs a reader that reads JSONH from a peekable character iterator.
     pub fn from_peekable_chars(source: Peekable<Chars<'a>>, options: JsonhReaderOptions) -> Self {
-        return Self { source: source, options: options, char_counter: 0, depth: 0 };
+        return Self::from_source(JsonhCharSource::new(source), options);
     }
     /// Constructs a reader that reads JSONH from a character iterator.
     pub fn from_chars(source: Chars<'a>, options: JsonhReaderOptions) -> Self {
@@ -51,81 +151,403 @@ impl<'a> JsonhReader<'a> {
     pub fn from_string(source: &'a String, options: JsonhReaderOptions) -> Self {
         return Self::from_str(source.as_str(), options);
     }
+    /// Replaces the source and clears the state of the reader, so it can read another document without being constructed again.
+    /// 
+    /// The options, callbacks, extensions, named literals and type hints are kept, and the buffers used to read tokens are reused.
+    /// ```
+    /// for payload in &payloads {
+    ///     reader.reset(JsonhCharSource::new(payload.chars()));
+    ///     let element: Value = reader.parse_element()?;
+    /// }
+    /// ```
+    pub fn reset(&mut self, source: impl JsonhSource + 'a) {
+        self.char_counter = source.position();
+        self.source = Box::new(source);
+        self.line_counter = 1;
+        self.column_counter = 1;
+        self.depth = 0;
+        self.path.segments.clear();
+        self.allocated_bytes = 0;
+        self.comment_bytes = 0;
+        self.stats = JsonhParseStats::new();
+        self.raw_escape_builder = None;
+        self.token_state.clear();
+    }
 
+    /// Registers a callback invoked when the reader enters or exits an object or array.
+    /// 
+    /// For example, to reject objects with a `secret` path:
+    /// ```
+    /// reader.with_structure_callback(|json_type, path, depth| {
+    ///     if path.to_string() == "secret" { Err("Secrets are forbidden") } else { Ok(()) }
+    /// })
+    /// ```
+    pub fn with_structure_callback(mut self, callback: impl FnMut(JsonTokenType, &JsonhPath, i32) -> Result<(), &'static str> + 'a) -> Self {
+        self.structure_callbacks.push(Box::new(callback));
+        return self;
+    }
+    /// Registers a callback invoked when the reader reads a comment, so comments can be collected without handling the tokens.
+    /// 
+    /// The comment's path is the path of the element being read (e.g. the property whose value follows the comment), or of the enclosing object or array.
+    /// 
+    /// For example, to collect every comment:
+    /// ```
+    /// let mut comments: Vec<JsonhComment> = Vec::new();
+    /// let element: Value = JsonhReader::from_str(jsonh, JsonhReaderOptions::new())
+    ///     .with_comment_callback(|comment| { comments.push(comment.clone()); Ok(()) })
+    ///     .parse_element()?;
+    /// ```
+    pub fn with_comment_callback(mut self, callback: impl FnMut(&JsonhComment) -> Result<(), &'static str> + 'a) -> Self {
+        self.comment_callbacks.push(Box::new(callback));
+        return self;
+    }
+    /// Registers additional characters that cannot be used unescaped in quoteless strings, such as sigils of an experimental dialect.
+    /// 
+    /// ```
+    /// reader.with_reserved_chars(&['$'])
+    /// ```
+    /// ```
+    /// [a$b] // Error
+    /// [a\$b] // "a$b"
+    /// ```
+    pub fn with_reserved_chars(mut self, reserved_chars: &[char]) -> Self {
+        self.extra_reserved_chars.extend_from_slice(reserved_chars);
+        return self;
+    }
+    /// Registers a callback that reads primitive elements starting with the given character, before the reader tries to read a number or string.
+    /// 
+    /// The callback reads the element directly from the source, starting at the given character, and returns a primitive token (a string, number or named literal).
+    /// The span of the token is set by the reader.
+    /// 
+    /// For example, to read `$name` as the string `${name}`:
+    /// ```
+    /// reader.with_primitive_extension('$', |source| {
+    ///     source.next();
+    ///     let mut name: String = String::new();
+    ///     while let Some(next) = source.peek().filter(char::is_ascii_alphanumeric) {
+    ///         name.push(next);
+    ///         source.next();
+    ///     }
+    ///     Ok(JsonhToken::new(JsonTokenType::String, format!("${{{name}}}")))
+    /// })
+    /// ```
+    /// 
+    /// Property names are read as strings, so they are not affected.
+    pub fn with_primitive_extension(mut self, start_char: char, callback: impl FnMut(&mut dyn JsonhSource) -> Result<JsonhToken, &'static str> + 'a) -> Self {
+        self.primitive_extensions.push((start_char, Box::new(callback)));
+        return self;
+    }
+    /// Registers an additional named literal that is read as the given element, like `null`, `true` and `false`.
+    /// 
+    /// For example, to read `yes` and `no` as booleans:
+    /// ```
+    /// reader.with_named_literal("yes", Value::Bool(true)).with_named_literal("no", Value::Bool(false))
+    /// ```
+    /// 
+    /// Named literals are only matched in quoteless strings without escape sequences, so `'yes'` and `\u0079es` are still read as strings.
+    /// Reading fails if the element is an object or array.
+    pub fn with_named_literal(mut self, literal: &str, element: Value) -> Self {
+        self.named_literals.push((literal.to_string(), element));
+        return self;
+    }
+    /// Registers a type that the element at the given path (formatted like `a.b[2].c`) is coerced to when parsing.
+    /// 
+    /// For example, to keep `version` as a string and parse `port` as a number:
+    /// ```
+    /// reader.with_type_hint("version", JsonhTypeHint::String).with_type_hint("port", JsonhTypeHint::Number)
+    /// ```
+    /// ```
+    /// {
+    ///   version: 1.20, // "1.20"
+    ///   port: "8080", // 8080
+    /// }
+    /// ```
+    /// 
+    /// Parsing fails if the element cannot be coerced. Paths that cannot be parsed (see `JsonhPath::from_str`) are ignored.
+    pub fn with_type_hint(mut self, path: &str, type_hint: JsonhTypeHint) -> Self {
+        if let Ok(path) = path.parse() {
+            self.type_hints.push((path, type_hint));
+        }
+        return self;
+    }
+
+    /// Parses a single element from a custom source.
+    pub fn parse_element_from_source(source: impl JsonhSource + 'a, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
+        return Self::from_source(source, options).parse_element();
+    }
+    /// Parses a single element from UTF-8 bytes in a byte reader (see `from_read`).
+    pub fn parse_element_from_read(source: impl Read + 'a, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
+        return Self::from_read(source, options).parse_element();
+    }
+    /// Parses a single element from UTF-8 bytes (see `from_bytes`).
+    pub fn parse_element_from_bytes(source: &'a [u8], options: JsonhReaderOptions) -> Result<Value, JsonhError> {
+        return Self::from_bytes(source, options).parse_element();
+    }
+    /// Parses a single element from a file.
+    /// 
+    /// Files ending in `.gz` are decompressed with gzip (requires the `gzip` feature), and files ending in `.zst` are decompressed with Zstandard (requires the `zstd` feature).
+    /// The file is read incrementally rather than loaded into memory. If reading fails, the reader returns an error caused by the I/O error.
+    /// 
+    /// Errors include the path of the file, along with the line and column of syntax errors or the I/O error that caused them:
+    /// ```
+    /// Expected `:` after property name in object in config.jsonh (line 12, column 8)
+    /// ```
+    pub fn parse_element_from_path(path: impl AsRef<Path>, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
+        let path: &Path = path.as_ref();
+        let file: File = File::open(path).map_err(|error| JsonhError::from("Failed to open file").with_file_path(path).with_cause(error))?;
+
+        // Decompress
+        let decompressed: Box<dyn Read> = match path.extension().and_then(|extension| extension.to_str()) {
+            #[cfg(feature = "gzip")]
+            Some("gz") => Box::new(flate2::read::MultiGzDecoder::new(file)),
+            #[cfg(not(feature = "gzip"))]
+            Some("gz") => return Err(JsonhError::from("Reading gzip files requires the `gzip` feature").with_file_path(path)),
+            #[cfg(feature = "zstd")]
+            Some("zst") => Box::new(zstd::stream::read::Decoder::new(file).map_err(|error| JsonhError::from("Failed to read file").with_file_path(path).with_cause(error))?),
+            #[cfg(not(feature = "zstd"))]
+            Some("zst") => return Err(JsonhError::from("Reading Zstandard files requires the `zstd` feature").with_file_path(path)),
+            _ => Box::new(file),
+        };
+
+        return JsonhReader::parse_element_from_read(decompressed, options).map_err(|error| error.with_file_path(path));
+    }
+    /// Parses a single element from the standard input, so JSONH can be piped into a program.
+    /// 
+    /// ```
+    /// // cat config.jsonh | my-tool
+    /// let config: Value = JsonhReader::parse_element_from_stdin(JsonhReaderOptions::new())?;
+    /// ```
+    /// 
+    /// The standard input is locked while it is read incrementally.
+    /// If reading fails, the error is `"Failed to read input"`, caused by the I/O error.
+    pub fn parse_element_from_stdin(options: JsonhReaderOptions) -> Result<Value, JsonhError> {
+        return Self::parse_element_from_source(JsonhReadSource::from_buf_read(std::io::stdin().lock()), options);
+    }
+    /// Parses a single element from any character iterator (see `from_char_iter`).
+    pub fn parse_element_from_char_iter(source: impl Iterator<Item = char> + 'a, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
+        return Self::from_char_iter(source, options).parse_element();
+    }
     /// Parses a single element from a peekable character iterator.
-    pub fn parse_element_from_peekable_chars(source: Peekable<Chars<'a>>, options: JsonhReaderOptions) -> Result<Value, &'static str> {
+    pub fn parse_element_from_peekable_chars(source: Peekable<Chars<'a>>, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
         return Self::from_peekable_chars(source, options).parse_element();
     }
     /// Parses a single element from a character iterator.
-    pub fn parse_element_from_chars(source: Chars<'a>, options: JsonhReaderOptions) -> Result<Value, &'static str> {
+    pub fn parse_element_from_chars(source: Chars<'a>, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
         return Self::from_chars(source, options).parse_element();
     }
     /// Parses a single element from a string slice.
-    pub fn parse_element_from_str(source: &'a str, options: JsonhReaderOptions) -> Result<Value, &'static str> {
+    pub fn parse_element_from_str(source: &'a str, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
         return Self::from_str(source, options).parse_element();
     }
     /// Parses a single element from a string.
-    pub fn parse_element_from_string(source: &'a String, options: JsonhReaderOptions) -> Result<Value, &'static str> {
+    pub fn parse_element_from_string(source: &'a String, options: JsonhReaderOptions) -> Result<Value, JsonhError> {
         return Self::from_string(source, options).parse_element();
     }
+    /// Parses a single element from the start of a string slice and returns it along with the rest of the string after it.
+    /// 
+    /// Useful for embedding a JSONH element at the start of a custom format:
+    /// ```
+    /// let (header, body): (Value, &str) = JsonhReader::parse_element_prefix("{ version: 2 }\n---\nbody", JsonhReaderOptions::new())?;
+    /// // header: {"version": 2}, body: "\n---\nbody"
+    /// ```
+    /// 
+    /// The rest of the string is not read, regardless of `JsonhReaderOptions::parse_single_element`.
+    /// Whitespace and comments after a primitive element are read to check for a braceless object, so are not included in the rest.
+    pub fn parse_element_prefix(source: &'a str, options: JsonhReaderOptions) -> Result<(Value, &'a str), JsonhError> {
+        let mut reader: JsonhReader<'_> = Self::from_str(source, options.with_parse_single_element(false));
+        let element: Value = reader.parse_element()?;
+
+        // Rest after element
+        let offset: usize = source.char_indices().nth(reader.char_counter as usize).map_or(source.len(), |(offset, _)| offset);
+        return Ok((element, &source[offset..]));
+    }
 
     /// Parses a single element from the source.
-    pub fn parse_element(&mut self) -> Result<Value, &'static str> {
-        let mut current_elements: Vec<Value> = Vec::new();
-        let mut current_property_name: Option<String> = None;
+    /// 
+    /// Errors include the position, line and column the reader stopped at:
+    /// ```
+    /// Expected `:` after property name in object (line 12, column 8)
+    /// ```
+    pub fn parse_element(&mut self) -> Result<Value, JsonhError> {
+        return self.parse_element_and_record(None).map_err(|error| self.locate_error(error));
+    }
+    /// Parses a single element from the source and collects statistics about the tokens read.
+    /// 
+    /// The statistics are returned even if parsing fails.
+    pub fn parse_element_with_stats(&mut self) -> (Result<Value, JsonhError>, JsonhParseStats) {
+        let start_time: Instant = Instant::now();
+        let mut stats: JsonhParseStats = JsonhParseStats::new();
 
-        let submit_element = |current_elements: &mut Vec<Value>, current_property_name: &mut Option<String>, element: Value| -> bool {
-            // Root value
-            if current_elements.is_empty() {
-                return true;
-            }
-            // Array item
-            if current_property_name.is_none() {
-                current_elements.last_mut().unwrap().as_array_mut().unwrap().push(element);
-                return false;
+        let result: Result<Value, &'static str> = self.parse_element_and_record(Some(&mut stats));
+        stats.duration = start_time.elapsed();
+
+        return (result.map_err(|error| self.locate_error(error)), stats);
+    }
+    /// Parses a single element from the source and collects every comment in it, along with the path of the element each comment precedes.
+    /// 
+    /// Comments before the end of an object or array are associated with the object or array.
+    /// Comments after the element are associated with the root.
+    /// 
+    /// ```
+    /// // The server
+    /// {
+    ///   # The port
+    ///   port: 8080
+    /// }
+    /// ```
+    /// ```
+    /// [JsonhComment { path: $, value: " The server" }, JsonhComment { path: port, value: " The port" }]
+    /// ```
+    /// 
+    /// The comments can be added to the element with `JsonhComment::inject_all`.
+    pub fn parse_element_with_comments(&mut self) -> Result<(Value, Vec<JsonhComment>), JsonhError> {
+        return self.parse_element_and_comments().map_err(|error| self.locate_error(error));
+    }
+    fn parse_element_and_comments(&mut self) -> Result<(Value, Vec<JsonhComment>), &'static str> {
+        let mut comment_tracker: CommentTracker = CommentTracker::new();
+
+        let element: Value = self.parse_element_and_record_comments(None, Some(&mut comment_tracker))?;
+
+        // Trailing comments
+        if !self.options.parse_single_element {
+            for token_result in self.read_comments_and_whitespace().raw() {
+                comment_tracker.record_token(&token_result?);
             }
-            // Object property
-            else {
-                current_elements.last_mut().unwrap()[current_property_name.as_ref().unwrap()] = element;
-                *current_property_name = None;
-                return false;
+        }
+
+        return Ok((element, comment_tracker.finish()));
+    }
+    fn parse_element_and_record(&mut self, stats: Option<&mut JsonhParseStats>) -> Result<Value, &'static str> {
+        return self.parse_element_and_record_comments(stats, None);
+    }
+    fn parse_element_and_record_comments(&mut self, mut stats: Option<&mut JsonhParseStats>, mut comment_tracker: Option<&mut CommentTracker>) -> Result<Value, &'static str> {
+        let mut current_elements: Vec<Value> = Vec::new();
+        let mut current_property_name: Option<String> = None;
+        let structure_capacity: usize = self.estimate_capacity(Self::MAX_PRESIZED_STRUCTURE_CAPACITY);
+        let max_allocation: Option<usize> = self.options.max_allocation;
+        let max_properties: Option<usize> = self.options.max_properties;
+        let max_items: Option<usize> = self.options.max_items;
+        let mut element_bytes: usize = 0;
+
+        let submit_element = |current_elements: &mut Vec<Value>, current_property_name: &mut Option<String>, element: Value| -> Result<bool, &'static str> {
+            match (current_elements.last_mut(), current_property_name.take()) {
+                // Root value
+                (None, _) => {
+                    return Ok(true);
+                },
+                // Array item
+                (Some(Value::Array(array)), None) => {
+                    // Check exceeded max items
+                    if max_items.is_some_and(|max_items| array.len() >= max_items) {
+                        return Err("Exceeded max items");
+                    }
+                    array.push(element);
+                    return Ok(false);
+                },
+                // Object property
+                (Some(Value::Object(object)), Some(property_name)) => {
+                    object.insert(property_name, element);
+                    return Ok(false);
+                },
+                // Property name mismatch
+                (Some(Value::Object(_)), None) => {
+                    return Err("Expected property name in object");
+                },
+                (Some(_), _) => {
+                    return Err("Unexpected property name in array");
+                },
             }
         };
-        let start_element = |current_elements: &mut Vec<Value>, current_property_name: &mut Option<String>, element: Value| -> () {
-            submit_element(current_elements, current_property_name, element.clone());
+        let start_element = |current_elements: &mut Vec<Value>, current_property_name: &mut Option<String>, structure_property_names: &mut Vec<Option<String>>, element: Value| {
+            // Submit to parent at end of structure
+            structure_property_names.push(current_property_name.take());
             current_elements.push(element);
         };
+        let current_segment = |current_elements: &Vec<Value>, current_property_name: &Option<String>| -> Option<JsonhPathSegment> {
+            match current_elements.last() {
+                Some(Value::Array(array)) => return Some(JsonhPathSegment::Index(array.len())),
+                Some(_) => return current_property_name.clone().map(JsonhPathSegment::Property),
+                None => return None,
+            }
+        };
+        let type_hints: Vec<(JsonhPath, JsonhTypeHint)> = self.type_hints.clone();
         let mut parse_next_element = |current_elements: &mut Vec<Value>, current_property_name: &mut Option<String>| -> Result<Value, &'static str> {
-            for token_result in self.read_element() {
+            let mut structure_property_names: Vec<Option<String>> = Vec::new();
+            // Number of property names read in each structure (including duplicates)
+            let mut structure_property_counts: Vec<usize> = Vec::new();
+            let mut element_path: JsonhPath = JsonhPath::new();
+
+            for token_result in self.read_element().raw() {
                 // Check error
                 let token: JsonhToken = token_result?;
 
+                // Check exceeded max allocation
+                if !matches!(token.json_type, JsonTokenType::Comment | JsonTokenType::EndObject | JsonTokenType::EndArray) {
+                    element_bytes += size_of::<Value>();
+                    if token.is_escaped {
+                        element_bytes += token.value.len();
+                    }
+                    if max_allocation.is_some_and(|max_allocation| element_bytes > max_allocation) {
+                        return Err("Exceeded max allocation");
+                    }
+                }
+
+                // Record statistics
+                if let Some(stats) = stats.as_deref_mut() {
+                    stats.record_token(&token);
+                }
+                // Record comments
+                if let Some(comment_tracker) = comment_tracker.as_deref_mut() {
+                    comment_tracker.record_token(&token);
+                }
+
+                // Coerce element with type hint
+                if !type_hints.is_empty() && !matches!(token.json_type, JsonTokenType::Comment | JsonTokenType::PropertyName | JsonTokenType::EndObject | JsonTokenType::EndArray) {
+                    let segment: Option<JsonhPathSegment> = current_segment(current_elements, current_property_name);
+                    let is_segment: bool = segment.is_some();
+                    if let Some(segment) = segment {
+                        element_path.push(segment);
+                    }
+                    let type_hint: Option<JsonhTypeHint> = type_hints.iter().find(|(hint_path, _)| *hint_path == element_path).map(|(_, type_hint)| *type_hint);
+                    if is_segment {
+                        element_path.pop();
+                    }
+
+                    if let Some(type_hint) = type_hint {
+                        let element: Value = Self::coerce_token(&token, type_hint)?;
+                        if submit_element(current_elements, current_property_name, element.clone())? {
+                            return Ok(element);
+                        }
+                        continue;
+                    }
+                }
+
                 match token.json_type {
                     // Null
                     JsonTokenType::Null => {
                         let element: Value = Value::Null;
-                        if submit_element(current_elements, current_property_name, element.clone()) {
+                        if submit_element(current_elements, current_property_name, element.clone())? {
                             return Ok(element);
                         }
                     },
                     // True
                     JsonTokenType::True => {
                         let element: Value = Value::Bool(true);
-                        if submit_element(current_elements, current_property_name, element.clone()) {
+                        if submit_element(current_elements, current_property_name, element.clone())? {
                             return Ok(element);
                         }
                     },
                     // False
                     JsonTokenType::False => {
                         let element: Value = Value::Bool(false);
-                        if submit_element(current_elements, current_property_name, element.clone()) {
+                        if submit_element(current_elements, current_property_name, element.clone())? {
                             return Ok(element);
                         }
                     },
                     // String
                     JsonTokenType::String => {
-                        let element: Value = Value::String(token.value);
-                        if submit_element(current_elements, current_property_name, element.clone()) {
+                        let element: Value = Value::String(token.unescaped_value()?.into_owned());
+                        if submit_element(current_elements, current_property_name, element.clone())? {
                             return Ok(element);
                         }
                     },
@@ -136,34 +558,56 @@ impl<'a> JsonhReader<'a> {
                             return Err("Infinity and NaN are not supported");
                         };
                         let element: Value = Value::Number(number);
-                        if submit_element(current_elements, current_property_name, element.clone()) {
+                        if submit_element(current_elements, current_property_name, element.clone())? {
                             return Ok(element);
                         }
                     },
                     // Start Object
                     JsonTokenType::StartObject => {
-                        let element: Value = Value::Object(serde_json::Map::new());
-                        start_element(current_elements, current_property_name, element);
+                        if let Some(segment) = current_segment(current_elements, current_property_name) {
+                            element_path.push(segment);
+                        }
+                        let element: Value = Value::Object(serde_json::Map::with_capacity(structure_capacity));
+                        start_element(current_elements, current_property_name, &mut structure_property_names, element);
+                        structure_property_counts.push(0);
                     },
                     // Start Array
                     JsonTokenType::StartArray => {
-                        let element: Value = Value::Array(Vec::new());
-                        start_element(current_elements, current_property_name, element);
+                        if let Some(segment) = current_segment(current_elements, current_property_name) {
+                            element_path.push(segment);
+                        }
+                        let element: Value = Value::Array(Vec::with_capacity(structure_capacity));
+                        start_element(current_elements, current_property_name, &mut structure_property_names, element);
+                        structure_property_counts.push(0);
                     },
                     // End Object/Array
                     JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                        structure_property_counts.pop();
+
                         // Nested element
                         if current_elements.len() > 1 {
-                            current_elements.pop();
+                            let Some(element) = current_elements.pop() else {
+                                return Err("Unexpected end of structure");
+                            };
+                            *current_property_name = structure_property_names.pop().flatten();
+                            element_path.pop();
+                            submit_element(current_elements, current_property_name, element)?;
                         }
                         // Root element
                         else {
-                            return Ok(current_elements.last().unwrap().clone());
+                            return current_elements.pop().ok_or("Unexpected end of structure");
                         }
                     },
                     // Property Name
                     JsonTokenType::PropertyName => {
-                        *current_property_name = Some(token.value);
+                        // Check exceeded max properties
+                        if let Some(property_count) = structure_property_counts.last_mut() {
+                            *property_count += 1;
+                            if max_properties.is_some_and(|max_properties| *property_count > max_properties) {
+                                return Err("Exceeded max properties");
+                            }
+                        }
+                        *current_property_name = Some(token.unescaped_value()?.into_owned());
                     },
                     // Comment
                     JsonTokenType::Comment => (),
@@ -179,12 +623,31 @@ impl<'a> JsonhReader<'a> {
         // Parse next element
         let next_element: Result<Value, &'static str> = parse_next_element(&mut current_elements, &mut current_property_name);
 
+        // Check source failed after last token (e.g. truncating a quoteless string)
+        if next_element.is_ok() && let Some(source_error) = self.source_error() {
+            return Err(source_error);
+        }
+
+        // Check exceeded max allocation (including token values)
+        self.allocated_bytes += element_bytes;
+        if next_element.is_ok() && max_allocation.is_some_and(|max_allocation| self.allocated_bytes > max_allocation) {
+            return Err("Exceeded max allocation");
+        }
+
         // Ensure exactly one element
         if next_element.is_ok() {
             if self.options.parse_single_element {
-                for token_result in self.read_end_of_elements() {
-                    if let Err(token_error) = token_result {
-                        return Err(token_error);
+                for token_result in self.read_end_of_elements().raw() {
+                    match token_result {
+                        Ok(token) => {
+                            if let Some(stats) = stats.as_deref_mut() {
+                                stats.record_token(&token);
+                            }
+                            if let Some(comment_tracker) = comment_tracker.as_deref_mut() {
+                                comment_tracker.record_token(&token);
+                            }
+                        },
+                        Err(token_error) => return Err(token_error),
                     }
                 }
             }
@@ -192,6 +655,46 @@ impl<'a> JsonhReader<'a> {
 
         return next_element;
     }
+    fn coerce_token(token: &JsonhToken, type_hint: JsonhTypeHint) -> Result<Value, &'static str> {
+        match type_hint {
+            // String
+            JsonhTypeHint::String => {
+                match token.json_type {
+                    JsonTokenType::String => return Ok(Value::String(token.unescaped_value()?.into_owned())),
+                    JsonTokenType::Number | JsonTokenType::True | JsonTokenType::False | JsonTokenType::Null => return Ok(Value::String(token.value.clone())),
+                    _ => return Err("Expected string for type hint"),
+                }
+            },
+            // Number
+            JsonhTypeHint::Number => {
+                let number_string: String = match token.json_type {
+                    JsonTokenType::Number => token.value.clone(),
+                    JsonTokenType::String => token.unescaped_value()?.trim().to_string(),
+                    _ => return Err("Expected number for type hint"),
+                };
+                let result: f64 = JsonhNumberParser::parse(number_string).map_err(|_| "Expected number for type hint")?;
+                let Some(number) = Number::from_f64(result) else {
+                    return Err("Infinity and NaN are not supported");
+                };
+                return Ok(Value::Number(number));
+            },
+            // Bool
+            JsonhTypeHint::Bool => {
+                match token.json_type {
+                    JsonTokenType::True => return Ok(Value::Bool(true)),
+                    JsonTokenType::False => return Ok(Value::Bool(false)),
+                    JsonTokenType::String => {
+                        match token.unescaped_value()?.trim() {
+                            "true" => return Ok(Value::Bool(true)),
+                            "false" => return Ok(Value::Bool(false)),
+                            _ => return Err("Expected boolean for type hint"),
+                        }
+                    },
+                    _ => return Err("Expected boolean for type hint"),
+                }
+            },
+        }
+    }
     /// Parses a single element as JSON from the reader.
     /// 
     /// If `include_comments` is true, comments are included (`/*` and `*/` are escaped as `/ *` and `* /`).
@@ -199,15 +702,27 @@ impl<'a> JsonhReader<'a> {
     /// If `indent` is not None, the output is pretty-printed with the given indentation.
     /// 
     /// Note: The result is **NOT** safe to embed in HTML. To safely embed in HTML, you need to escape characters like `<`, `>` and `&`.
-    pub fn parse_json(&mut self, include_comments: bool, indent: Option<&str>) -> Result<String, &'static str> {
+    pub fn parse_json(&mut self, include_comments: bool, indent: Option<&str>) -> Result<String, JsonhError> {
+        return self.parse_json_and_map(include_comments, indent, None).map_err(|error| self.locate_error(error));
+    }
+    /// Parses a single element as JSON from the reader, along with a source map relating ranges of the JSON to ranges of the JSONH.
+    /// 
+    /// See `parse_json`.
+    pub fn parse_json_with_source_map(&mut self, include_comments: bool, indent: Option<&str>) -> Result<(String, JsonhSourceMap), JsonhError> {
+        let mut source_map: JsonhSourceMap = JsonhSourceMap::new();
+        let json: String = self.parse_json_and_map(include_comments, indent, Some(&mut source_map)).map_err(|error| self.locate_error(error))?;
+        return Ok((json, source_map));
+    }
+    fn parse_json_and_map(&mut self, include_comments: bool, indent: Option<&str>, mut source_map: Option<&mut JsonhSourceMap>) -> Result<String, &'static str> {
         let mut parse_next_element_as_json = || -> Result<String, &'static str> {
             let mut current_depth: i64 = 0;
             let mut is_start_of_structure: bool = true;
             let mut is_property_value: bool = false;
 
             let mut result_builder: String = String::new();
+            let mut structure_starts: Vec<(usize, u64)> = Vec::new();
 
-            for token_result in self.read_element() {
+            for token_result in self.read_element().raw() {
                 // Check error
                 let token: JsonhToken = token_result?;
 
@@ -255,42 +770,29 @@ impl<'a> JsonhReader<'a> {
                     is_start_of_structure = true;
                 }
 
+                let output_start: usize = result_builder.len();
+
                 match token.json_type {
                     // Null
                     JsonTokenType::Null => {
                         result_builder += "null";
-                        if current_depth == 0 {
-                            return Ok(result_builder);
-                        }
                     }
                     // True
                     JsonTokenType::True => {
                         result_builder += "true";
-                        if current_depth == 0 {
-                            return Ok(result_builder);
-                        }
                     }
                     // False
                     JsonTokenType::False => {
                         result_builder += "false";
-                        if current_depth == 0 {
-                            return Ok(result_builder);
-                        }
                     }
                     // String
                     JsonTokenType::String => {
-                        result_builder += &serde_json::to_string(&token.value).unwrap();
-                        if current_depth == 0 {
-                            return Ok(result_builder);
-                        }
+                        result_builder += &serde_json::to_string(&token.unescaped_value()?).unwrap();
                     }
                     // Number
                     JsonTokenType::Number => {
                         let result: f64 = JsonhNumberParser::parse(token.value)?;
                         result_builder += &result.to_string();
-                        if current_depth == 0 {
-                            return Ok(result_builder);
-                        }
                     }
                     // Start Object
                     JsonTokenType::StartObject => {
@@ -306,21 +808,15 @@ impl<'a> JsonhReader<'a> {
                     JsonTokenType::EndObject => {
                         result_builder.push('}');
                         current_depth -= 1;
-                        if current_depth == 0 {
-                            return Ok(result_builder);
-                        }
                     }
                     // End Array
                     JsonTokenType::EndArray => {
                         result_builder.push(']');
                         current_depth -= 1;
-                        if current_depth == 0 {
-                            return Ok(result_builder);
-                        }
                     }
                     // Property Name
                     JsonTokenType::PropertyName => {
-                        result_builder += &serde_json::to_string(&token.value).unwrap();
+                        result_builder += &serde_json::to_string(&token.unescaped_value()?).unwrap();
                         result_builder.push(':');
                         if indent.is_some() {
                             result_builder.push(' ');
@@ -340,6 +836,39 @@ impl<'a> JsonhReader<'a> {
                     }
                 }
 
+                // Map output to input
+                if let Some(source_map) = &mut source_map {
+                    match token.json_type {
+                        // Comment
+                        JsonTokenType::Comment => {
+                        }
+                        // Start structure
+                        JsonTokenType::StartObject | JsonTokenType::StartArray => {
+                            structure_starts.push((output_start, token.start));
+                        }
+                        // End structure
+                        JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                            if let Some((structure_output_start, structure_input_start)) = structure_starts.pop() {
+                                source_map.mappings.push(JsonhSourceMapping::new(structure_output_start, result_builder.len(), structure_input_start, token.end));
+                            }
+                        }
+                        // Property name (excluding colon)
+                        JsonTokenType::PropertyName => {
+                            let output_end: usize = result_builder.trim_end_matches([':', ' ']).len();
+                            source_map.mappings.push(JsonhSourceMapping::new(output_start, output_end, token.start, token.end));
+                        }
+                        // Primitive
+                        _ => {
+                            source_map.mappings.push(JsonhSourceMapping::new(output_start, result_builder.len(), token.start, token.end));
+                        }
+                    }
+                }
+
+                // End of element
+                if current_depth == 0 && token.json_type != JsonTokenType::Comment {
+                    return Ok(result_builder);
+                }
+
                 if token.json_type != JsonTokenType::Comment {
                     is_property_value = token.json_type == JsonTokenType::PropertyName;
                 }
@@ -355,7 +884,7 @@ impl<'a> JsonhReader<'a> {
         // Ensure exactly one element
         if next_element_as_json.is_ok() {
             if self.options.parse_single_element {
-                for token_result in self.read_end_of_elements() {
+                for token_result in self.read_end_of_elements().raw() {
                     if let Err(token_error) = token_result {
                         return Err(token_error);
                     }
@@ -378,10 +907,13 @@ impl<'a> JsonhReader<'a> {
     ///   "c":/* Final position */ "3"
     /// }
     /// ```
+    /// 
+    /// Only properties of the root object are found. To find nested values, use `find_path_value`.
     pub fn find_property_value(&mut self, property_name: &str) -> bool {
+        let ignore_case: bool = self.options.ignore_property_name_case;
         let mut current_depth: i64 = 0;
 
-        for token_result in self.read_element() {
+        for token_result in self.read_element().raw() {
             // Check error
             let token: JsonhToken = match token_result {
                 Ok(token) => token,
@@ -399,7 +931,7 @@ impl<'a> JsonhReader<'a> {
                 },
                 // Property name
                 JsonTokenType::PropertyName => {
-                    if current_depth == 1 && token.value == property_name {
+                    if current_depth == 1 && Self::is_property_name_match(&token, property_name, ignore_case) {
                         // Path found
                         return true;
                     }
@@ -412,407 +944,794 @@ impl<'a> JsonhReader<'a> {
         // Path not found
         return false;
     }
-    /// Reads whitespace and returns whether the reader contains another token.
-    pub fn has_token(&mut self) -> bool {
-        // Whitespace
-        self.read_whitespace();
+    /// Tries to find the value at the given path in the reader, descending through nested objects and arrays.
+    /// 
+    /// For example, to find `b[1].c`:
+    /// ```
+    /// // Original position
+    /// {
+    ///   "a": { "c": "1" },
+    ///   "b": [
+    ///     { "c": "2" },
+    ///     { "c":/* Final position */ "3" }
+    ///   ]
+    /// }
+    /// ```
+    /// ```
+    /// let path: JsonhPath = "b[1].c".parse()?;
+    /// if reader.find_path_value(&path) {
+    ///     let value: Value = reader.parse_element()?;
+    /// }
+    /// ```
+    /// 
+    /// Structures outside the path are skipped. The reader stops before the value, so it can be read with `parse_element` (or the tokens of the value with `read_element`).
+    pub fn find_path_value(&mut self, path: &JsonhPath) -> bool {
+        let segments: &[JsonhPathSegment] = &path.segments;
 
-        // Peek char
-        return self.peek().is_some();
+        // Root element
+        if segments.is_empty() {
+            return true;
+        }
+
+        // Number of open structures
+        let mut current_depth: usize = 0;
+        // Number of open structures on the path
+        let mut matched_depth: usize = 0;
+        // Whether the next value in the innermost structure on the path is also on the path
+        let mut is_match: bool = true;
+
+        self.begin_tokens(TokenFrame::Element(ElementStage::Start));
+        let found: bool = 'tokens: loop {
+            // Check error
+            let token: JsonhToken = match self.read_next_token() {
+                Some(Ok(token)) => token,
+                _ => break 'tokens false,
+            };
+
+            match token.json_type {
+                // Comment
+                JsonTokenType::Comment => {},
+                // Property name
+                JsonTokenType::PropertyName => {
+                    if current_depth == matched_depth {
+                        is_match = matches!(&segments[matched_depth - 1], JsonhPathSegment::Property(name) if Self::is_property_name_match(&token, name, self.options.ignore_property_name_case));
+                        // Path found
+                        if is_match && matched_depth == segments.len() {
+                            break 'tokens true;
+                        }
+                        // Skip value outside the path
+                        if !is_match && self.skip_next_value().is_err() {
+                            break 'tokens false;
+                        }
+                    }
+                },
+                // Start structure
+                JsonTokenType::StartObject | JsonTokenType::StartArray => {
+                    current_depth += 1;
+
+                    // Enter structure on the path
+                    if current_depth == matched_depth + 1 && is_match {
+                        matched_depth += 1;
+                        is_match = false;
+                        match (token.json_type, &segments[matched_depth - 1]) {
+                            (JsonTokenType::StartObject, JsonhPathSegment::Property(_)) => {},
+                            (JsonTokenType::StartArray, JsonhPathSegment::Index(index)) => {
+                                // Skip previous items
+                                for _ in 0..*index {
+                                    if !self.step_to_array_item() || self.skip_next_value().is_err() {
+                                        break 'tokens false;
+                                    }
+                                }
+                                if !self.step_to_array_item() {
+                                    break 'tokens false;
+                                }
+                                // Path found
+                                if matched_depth == segments.len() {
+                                    break 'tokens true;
+                                }
+                                is_match = true;
+                            },
+                            _ => break 'tokens false,
+                        }
+                    }
+                },
+                // End structure
+                JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                    current_depth -= 1;
+
+                    // Structure on the path ended
+                    if current_depth < matched_depth {
+                        break 'tokens false;
+                    }
+                },
+                // Primitive
+                _ => {
+                    // Primitive on the path
+                    if current_depth == matched_depth && is_match {
+                        break 'tokens false;
+                    }
+                },
+            }
+        };
+
+        self.token_state.clear();
+        return found;
     }
-    /// Reads comments and whitespace and errors if the reader contains another element.
-    pub fn read_end_of_elements(&mut self) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Comments & whitespace
-            for token_result in self.read_comments_and_whitespace() {
-                if token_result.is_err() {
-                    y.ret(token_result).await;
-                    return;
-                }
-                y.ret(token_result).await;
+    /// Tries to find the value at the given path in the reader and deserializes it (see `find_path_value`).
+    /// 
+    /// ```
+    /// let port: Option<u16> = reader.find_and_parse_property(&"server.port".parse()?)?;
+    /// let hosts: Option<Value> = reader.find_and_parse_property(&"server.hosts".parse()?)?;
+    /// ```
+    /// 
+    /// Returns `None` if the path is not found. Property names are matched ignoring case if `JsonhReaderOptions::ignore_property_name_case` is enabled.
+    pub fn find_and_parse_property<T: DeserializeOwned>(&mut self, path: &JsonhPath) -> Result<Option<T>, JsonhError> {
+        if !self.find_path_value(path) {
+            return Ok(None);
+        }
+        return crate::deserialize::deserialize_value(self, None).map(Some);
+    }
+    /// Reads a single element from the source and returns whether it is valid, without building a `Value`.
+    /// 
+    /// The values of strings and comments are read into a single reused buffer rather than kept, unless callbacks are registered (which are given property names and comments).
+    /// The grammar and the limits in the options are checked as when parsing, except `JsonhReaderOptions::max_allocation` since the values are not kept.
+    /// This is faster than `parse_element` for checking many files:
+    /// ```
+    /// for path in paths {
+    ///     JsonhReader::from_read(File::open(path)?, options).validate()?;
+    /// }
+    /// ```
+    pub fn validate(&mut self) -> Result<(), JsonhError> {
+        return self.validate_element().map_err(|error| self.locate_error(error));
+    }
+    fn validate_element(&mut self) -> Result<(), &'static str> {
+        return self.discard_values(|reader| {
+            for token_result in reader.read_element().raw() {
+                // Check error
+                token_result?;
             }
 
-            // Peek char
-            if self.peek().is_some() {
-                y.ret(Err("Expected end of elements")).await;
+            // Ensure exactly one element
+            if reader.options.parse_single_element {
+                for token_result in reader.read_end_of_elements().raw() {
+                    // Check error
+                    token_result?;
+                }
             }
+            return Ok(());
         });
     }
-    /// Reads a single element from the reader.
-    pub fn read_element(&mut self) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Comments & whitespace
-            for token_result in self.read_comments_and_whitespace() {
-                if token_result.is_err() {
-                    y.ret(token_result).await;
-                    return;
-                }
-                y.ret(token_result).await;
-            }
-
-            // Peek char
-            let Some(next) = self.peek() else {
-                y.ret(Err("Expected token, got end of input")).await;
-                return;
+    /// Reads a single element from the source, calling the visitor for each token as it is read.
+    /// 
+    /// ```
+    /// let mut visitor: SumVisitor = SumVisitor { sum: 0.0 };
+    /// reader.drive(&mut visitor)?;
+    /// ```
+    /// 
+    /// If the visitor returns `ControlFlow::Break`, reading stops after the token and `Ok` is returned.
+    pub fn drive(&mut self, visitor: &mut impl JsonhVisitor) -> Result<(), JsonhError> {
+        return self.drive_element(visitor).map_err(|error| self.locate_error(error));
+    }
+    fn drive_element(&mut self, visitor: &mut impl JsonhVisitor) -> Result<(), &'static str> {
+        for token_result in self.read_element().raw() {
+            // Check error
+            let token: JsonhToken = token_result?;
+
+            // Visit token
+            let control_flow: ControlFlow<()> = match token.json_type {
+                JsonTokenType::StartObject => visitor.on_start_object(&token),
+                JsonTokenType::EndObject => visitor.on_end_object(&token),
+                JsonTokenType::StartArray => visitor.on_start_array(&token),
+                JsonTokenType::EndArray => visitor.on_end_array(&token),
+                JsonTokenType::PropertyName => visitor.on_property_name(&token.unescaped_value()?, &token),
+                JsonTokenType::String => visitor.on_string(&token.unescaped_value()?, &token),
+                JsonTokenType::Number => visitor.on_number(&token.value, &token),
+                JsonTokenType::True => visitor.on_bool(true, &token),
+                JsonTokenType::False => visitor.on_bool(false, &token),
+                JsonTokenType::Null => visitor.on_null(&token),
+                JsonTokenType::Comment => visitor.on_comment(&token.value, &token),
+                JsonTokenType::None => ControlFlow::Continue(()),
             };
 
-            // Object
-            if next == '{' {
-                for token_result in self.read_object() {
-                    if token_result.is_err() {
-                        y.ret(token_result).await;
-                        return;
-                    }
-                    y.ret(token_result).await;
-                }
+            // Stop early
+            if control_flow.is_break() {
+                return Ok(());
             }
-            // Array
-            else if next == '[' {
-                for token_result in self.read_array() {
-                    if token_result.is_err() {
-                        y.ret(token_result).await;
-                        return;
-                    }
-                    y.ret(token_result).await;
+        }
+        return Ok(());
+    }
+    /// Reads and discards the next element, without building a `Value`.
+    /// 
+    /// If an element is being read with `next_token`, the next value in it is skipped, so unwanted properties can be skipped:
+    /// ```
+    /// while let Some(token) = reader.next_token() {
+    ///     let token: JsonhToken = token?;
+    ///     if token.json_type == JsonTokenType::PropertyName && token.value != "id" {
+    ///         reader.skip_value()?;
+    ///     }
+    /// }
+    /// ```
+    /// Comments directly after a primitive value are skipped with it.
+    /// Only the depth is tracked and the values of strings and comments are read into a single reused buffer (unless callbacks are registered), so skipping a large element uses little memory.
+    pub fn skip_value(&mut self) -> Result<(), JsonhError> {
+        return self.skip_next_value().map_err(|error| self.locate_error(error));
+    }
+    fn skip_next_value(&mut self) -> Result<(), &'static str> {
+        return self.discard_values(|reader| {
+            // Next element
+            if !reader.token_state.is_reading_element && reader.token_state.peeked_token.is_none() {
+                for token_result in reader.read_element().raw() {
+                    // Check error
+                    token_result?;
                 }
+                return Ok(());
             }
-            // Primitive value (null, true, false, string, number)
-            else {
-                let token_result: Result<JsonhToken, &'static str> = self.read_primitive_element();
-                if token_result.is_err() {
-                    y.ret(token_result).await;
-                    return;
+
+            // Next value in element
+            let mut current_depth: i64 = 0;
+            while let Some(token_result) = reader.read_next_token() {
+                // Check error
+                let token: JsonhToken = token_result?;
+
+                match token.json_type {
+                    // Start structure
+                    JsonTokenType::StartObject | JsonTokenType::StartArray => {
+                        current_depth += 1;
+                    },
+                    // End structure
+                    JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                        current_depth -= 1;
+                    },
+                    // Comment
+                    JsonTokenType::Comment => {
+                        continue;
+                    },
+                    // Other
+                    _ => ()
                 }
 
-                // Detect braceless object from property name
-                for token_result2 in self.read_braceless_object_or_end_of_primitive(token_result.unwrap()) {
-                    if token_result2.is_err() {
-                        y.ret(token_result2).await;
-                        return;
-                    }
-                    y.ret(token_result2).await;
+                // End of value
+                if current_depth <= 0 {
+                    // Skip comments read after value
+                    reader.token_state.queued_tokens.retain(|token_result| !matches!(token_result, Ok(token) if token.json_type == JsonTokenType::Comment));
+                    return Ok(());
                 }
             }
+            return Ok(());
         });
     }
+    /// Converts an error message to a `JsonhError` with the position, line and column the reader stopped at.
+    /// 
+    /// Errors returned by the parse methods are already located. This is useful for errors found by callers reading tokens:
+    /// ```
+    /// if token.json_type != JsonTokenType::StartObject {
+    ///     return Err(reader.locate_error("Expected object"));
+    /// }
+    /// ```
+    pub fn locate_error(&self, message: &'static str) -> JsonhError {
+        let mut error: JsonhError = JsonhError::from(message).with_position(self.char_counter).with_location(self.line_counter, self.column_counter);
 
-    fn read_object(&mut self) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Opening brace
-            if !self.read_one('{') {
-                // Braceless object
-                for token_result in self.read_braceless_object(None) {
-                    if token_result.is_err() {
-                        y.ret(token_result).await;
-                        return;
-                    }
-                    y.ret(token_result).await;
+        // Include the I/O error that ended the source
+        if self.source.error() == Some(message) {
+            error.cause = self.source.cause();
+        }
+        return error;
+    }
+    /// Reads the rest of the source as a string, such as the text after an element.
+    /// 
+    /// To slice the original string instead, use `char_counter` (see `JsonhLineIndex::position_to_offset`).
+    pub fn read_rest(&mut self) -> String {
+        let mut rest: String = String::new();
+        while let Some(next) = self.read() {
+            rest.push(next);
+        }
+        return rest;
+    }
+    /// Reads whitespace and returns whether the reader contains another token.
+    pub fn has_token(&mut self) -> bool {
+        // Whitespace
+        self.read_whitespace();
+
+        // Peek char
+        return self.peek().is_some();
+    }
+    /// Reads comments and whitespace and errors if the reader contains another element.
+    pub fn read_end_of_elements(&mut self) -> JsonhTokenIter<'_> {
+        return self.start_tokens(TokenFrame::EndOfElements);
+    }
+    /// Reads a single element from the reader.
+    /// 
+    /// If the source fails (see `JsonhSource::error`), its error is returned instead of any error caused by the input ending early.
+    pub fn read_element(&mut self) -> JsonhTokenIter<'_> {
+        return self.start_tokens(TokenFrame::Element(ElementStage::Start));
+    }
+
+    /// Reads the next token of the element being read, starting a new element if none is being read.
+    /// 
+    /// Returns `None` at the end of the element, after which the next call reads the next element.
+    /// This is equivalent to iterating `read_element`, but can be driven one token at a time by hand-written decoders.
+    /// 
+    /// ```
+    /// while let Some(token) = reader.next_token() {
+    ///     let token: JsonhToken = token?;
+    ///     // ...
+    /// }
+    /// ```
+    pub fn next_token(&mut self) -> Option<Result<JsonhToken, JsonhError>> {
+        let token_result: Result<JsonhToken, &'static str> = self.read_next_token()?;
+        return Some(token_result.map_err(|error| self.locate_error(error)));
+    }
+    /// Returns the next token of the element being read without consuming it (see `next_token`).
+    /// 
+    /// The reader has already read past the peeked token, so `char_counter` is after it.
+    pub fn peek_token(&mut self) -> Option<Result<&JsonhToken, JsonhError>> {
+        if self.token_state.peeked_token.is_none() {
+            let next_token: Option<Result<JsonhToken, &'static str>> = self.read_next_token();
+            self.token_state.peeked_token = Some(next_token);
+        }
+        return match self.token_state.peeked_token.as_ref()?.as_ref()? {
+            Ok(token) => Some(Ok(token)),
+            Err(error) => Some(Err(self.locate_error(error))),
+        };
+    }
+    /// Reads the next token of the element being read with an error message that is not located (see `next_token`).
+    pub(crate) fn read_next_token(&mut self) -> Option<Result<JsonhToken, &'static str>> {
+        // Peeked token
+        if let Some(peeked_token) = self.token_state.peeked_token.take() {
+            return peeked_token;
+        }
+
+        loop {
+            // Queued token
+            if let Some(token_result) = self.token_state.queued_tokens.pop_front() {
+                if let Ok(token) = &token_result {
+                    self.stats.record_token(token);
                 }
-                return;
+                return Some(token_result);
             }
-            // Start of object
-            y.ret(Ok(JsonhToken::new_empty(JsonTokenType::StartObject))).await;
-            self.depth += 1;
 
-            // Check exceeded max depth
-            if self.depth > self.options.max_depth {
-                y.ret(Err("Exceeded max depth")).await;
-                return;
+            // Start of element
+            if !self.token_state.is_reading_element {
+                self.token_state.is_reading_element = true;
+                self.token_state.frames.push(TokenFrame::Element(ElementStage::Start));
+                continue;
             }
 
-            loop {
-                // Comments & whitespace
-                for token_result in self.read_comments_and_whitespace() {
-                    if token_result.is_err() {
-                        y.ret(token_result).await;
-                        return;
-                    }
-                    y.ret(token_result).await;
+            // End of element
+            if self.token_state.frames.is_empty() {
+                if !self.token_state.is_failed && let Some(source_error) = self.source_error() {
+                    self.token_state.is_failed = true;
+                    return Some(Err(source_error));
                 }
+                self.token_state.is_reading_element = false;
+                self.token_state.is_failed = false;
+                return None;
+            }
 
-                let Some(next) = self.peek() else {
-                    // End of incomplete object
-                    if self.options.incomplete_inputs {
-                        self.depth -= 1;
-                        y.ret(Ok(JsonhToken::new_empty(JsonTokenType::EndObject))).await;
-                        return;
-                    }
-                    // Missing closing brace
-                    y.ret(Err("Expected `}` to end object, got end of input")).await;
-                    return;
-                };
+            // Next step
+            self.step_token_frames();
+        }
+    }
+    fn step_token_frames(&mut self) {
+        let Some(frame) = self.token_state.frames.pop() else {
+            return;
+        };
 
-                // Closing brace
-                if next == '}' {
-                    // End of object
-                    self.read();
-                    self.depth -= 1;
-                    y.ret(Ok(JsonhToken::new_empty(JsonTokenType::EndObject))).await;
+        match frame {
+            // Comments & whitespace
+            TokenFrame::CommentsAndWhitespace => {
+                self.read_whitespace();
+                // JSON whitespace
+                if self.options.strict_json && self.peek().is_some_and(char::is_whitespace) {
+                    self.fail_token_frames("Invalid whitespace in JSON");
                     return;
                 }
-                // Property
-                else {
-                    for token_result in self.read_property(None) {
-                        if token_result.is_err() {
-                            y.ret(token_result).await;
-                            return;
-                        }
-                        y.ret(token_result).await;
+                if matches!(self.peek(), Some('#') | Some('/')) {
+                    match self.read_comment_with_callbacks() {
+                        Ok(comment_token) => {
+                            self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+                            self.queue_token(comment_token);
+                        },
+                        Err(comment_error) => self.fail_token_frames(comment_error),
                     }
                 }
-            }
-        });
-    }
-    fn read_braceless_object(&mut self, property_name_tokens: Option<Vec<JsonhToken>>) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Start of object
-            y.ret(Ok(JsonhToken::new_empty(JsonTokenType::StartObject))).await;
-            self.depth += 1;
-
-            // Check exceeded max depth
-            if self.depth > self.options.max_depth {
-                y.ret(Err("Exceeded max depth")).await;
-                return;
-            }
-
-            // Initial tokens
-            if property_name_tokens.is_some() {
-                for initial_token_result in self.read_property(property_name_tokens) {
-                    if initial_token_result.is_err() {
-                        y.ret(initial_token_result).await;
-                        return;
+            },
+            // End of elements
+            TokenFrame::EndOfElements => {
+                self.token_state.frames.push(TokenFrame::EndOfElementsPeek);
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::EndOfElementsPeek => {
+                // Peek char
+                if self.peek().is_some() {
+                    self.fail_token_frames("Expected end of elements");
+                }
+            },
+            // Element
+            TokenFrame::Element(ElementStage::Start) => {
+                // Unknown version
+                if !self.options.version.is_known() && !self.options.unknown_versions {
+                    self.fail_token_frames("Unsupported JSONH version");
+                    return;
+                }
+                self.token_state.frames.push(TokenFrame::Element(ElementStage::Peek));
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::Element(ElementStage::Peek) => {
+                match self.peek() {
+                    None => self.fail_token_frames("Expected token, got end of input"),
+                    // Object
+                    Some('{') => self.token_state.frames.push(TokenFrame::Object(StructureStage::Start)),
+                    // Array
+                    Some('[') => self.token_state.frames.push(TokenFrame::Array(StructureStage::Start, 0)),
+                    // Primitive value (null, true, false, string, number)
+                    Some(_) => match self.read_primitive_element() {
+                        Ok(primitive_token) => self.token_state.frames.push(TokenFrame::PrimitiveOrBracelessObject(primitive_token)),
+                        Err(primitive_error) => self.fail_token_frames(primitive_error),
+                    },
+                }
+            },
+            // Detect braceless object from property name
+            TokenFrame::PrimitiveOrBracelessObject(primitive_token) => {
+                // Comments & whitespace
+                let mut property_name_tokens: Vec<JsonhToken> = Vec::new();
+                loop {
+                    self.read_whitespace();
+                    if !matches!(self.peek(), Some('#') | Some('/')) {
+                        break;
+                    }
+                    match self.read_comment_with_callbacks() {
+                        Ok(comment_token) => property_name_tokens.push(comment_token),
+                        Err(comment_error) => {
+                            self.fail_token_frames(comment_error);
+                            return;
+                        },
                     }
-                    y.ret(initial_token_result).await;
                 }
-            }
 
-            loop {
-                // Comments & whitespace
-                for token_result in self.read_comments_and_whitespace() {
-                    if token_result.is_err() {
-                        y.ret(token_result).await;
-                        return;
+                // Primitive
+                if !self.read_one(':') {
+                    self.queue_token(primitive_token);
+                    for comment_token in property_name_tokens {
+                        self.queue_token(comment_token);
                     }
-                    y.ret(token_result).await;
+                    return;
                 }
 
-                if self.peek().is_none() {
-                    // End of braceless object
-                    self.depth -= 1;
-                    y.ret(Ok(JsonhToken::new_empty(JsonTokenType::EndObject))).await;
+                // JSON braceless object
+                if self.options.strict_json {
+                    self.fail_token_frames("Braceless objects are not allowed in JSON");
                     return;
-                };
+                }
 
-                // Property
-                for token_result in self.read_property(None) {
-                    if token_result.is_err() {
-                        y.ret(token_result).await;
-                        return;
-                    }
-                    y.ret(token_result).await;
+                // Property name
+                match self.convert_property_name(primitive_token) {
+                    Ok(property_name_token) => {
+                        property_name_tokens.push(property_name_token);
+                        self.token_state.frames.push(TokenFrame::BracelessObject(StructureStage::Start, Some(property_name_tokens)));
+                    },
+                    Err(property_name_error) => self.fail_token_frames(property_name_error),
                 }
-            }
-        });
-    }
-    fn read_braceless_object_or_end_of_primitive(&mut self, primitive_token: JsonhToken) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Comments & whitespace
-            let mut property_name_tokens: Vec<JsonhToken> = Vec::new();
-            for comment_or_whitespace_token_result in self.read_comments_and_whitespace() {
-                if comment_or_whitespace_token_result.is_err() {
-                    y.ret(comment_or_whitespace_token_result).await;
+            },
+            // Object
+            TokenFrame::Object(StructureStage::Start) => {
+                // Opening brace
+                let start: u64 = self.char_counter;
+                if !self.read_one('{') {
+                    self.token_state.frames.push(TokenFrame::BracelessObject(StructureStage::Start, None));
                     return;
                 }
-                property_name_tokens.push(comment_or_whitespace_token_result.unwrap());
-            }
-
-            // Primitive
-            if !self.read_one(':') {
-                // Primitive
-                y.ret(Ok(primitive_token)).await;
-                // Comments & whitespace
-                for comment_or_whitespace_token in property_name_tokens {
-                    y.ret(Ok(comment_or_whitespace_token)).await;
+                self.token_state.frames.push(TokenFrame::Object(StructureStage::Opened));
+                self.queue_token(JsonhToken::new_empty(JsonTokenType::StartObject).with_span(start, self.char_counter));
+            },
+            TokenFrame::Object(StructureStage::Opened) => {
+                if let Err(structure_error) = self.open_token_structure(JsonTokenType::StartObject) {
+                    self.fail_token_frames(structure_error);
+                    return;
                 }
-                // End of primitive
-                return;
-            }
-
-            // Property name
-            property_name_tokens.push(JsonhToken::new(JsonTokenType::PropertyName, primitive_token.value));
-
+                self.token_state.frames.push(TokenFrame::Object(StructureStage::Next));
+            },
+            TokenFrame::Object(StructureStage::Next) => {
+                self.token_state.frames.push(TokenFrame::Object(StructureStage::Peek));
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::Object(_) => {
+                match self.peek() {
+                    // End of incomplete object
+                    None if self.options.incomplete_inputs => self.close_token_structure(JsonTokenType::EndObject, self.char_counter),
+                    // Missing closing brace
+                    None => self.fail_token_frames("Expected `}` to end object, got end of input"),
+                    // Closing brace
+                    Some('}') => {
+                        let end_start: u64 = self.char_counter;
+                        self.read();
+                        self.close_token_structure(JsonTokenType::EndObject, end_start);
+                    },
+                    // Property
+                    Some(_) => {
+                        self.token_state.frames.push(TokenFrame::Object(StructureStage::Next));
+                        self.token_state.frames.push(TokenFrame::Property(PropertyStage::Start(None)));
+                    },
+                }
+            },
             // Braceless object
-            for object_token in self.read_braceless_object(Some(property_name_tokens)) {
-                if object_token.is_err() {
-                    y.ret(object_token).await;
+            TokenFrame::BracelessObject(StructureStage::Start, property_name_tokens) => {
+                // Start of object (from start of first property name)
+                let start: u64 = match &property_name_tokens {
+                    Some(tokens) => tokens.iter().map(|token| token.start).min().unwrap_or(self.char_counter),
+                    None => self.char_counter,
+                };
+                self.token_state.frames.push(TokenFrame::BracelessObject(StructureStage::Opened, property_name_tokens));
+                self.queue_token(JsonhToken::new_empty(JsonTokenType::StartObject).with_span(start, start));
+            },
+            TokenFrame::BracelessObject(StructureStage::Opened, property_name_tokens) => {
+                if let Err(structure_error) = self.open_token_structure(JsonTokenType::StartObject) {
+                    self.fail_token_frames(structure_error);
                     return;
                 }
-                y.ret(object_token).await;
-            }
-        });
-    }
-    fn read_property(&mut self, property_name_tokens: Option<Vec<JsonhToken>>) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Property name
-            if !property_name_tokens.is_none() {
-                for token in property_name_tokens.unwrap() {
-                    y.ret(Ok(token)).await;
+                self.token_state.frames.push(TokenFrame::BracelessObject(StructureStage::Next, None));
+                // Initial tokens
+                if property_name_tokens.is_some() {
+                    self.token_state.frames.push(TokenFrame::Property(PropertyStage::Start(property_name_tokens)));
                 }
-            }
-            else {
-                for token in self.read_property_name() {
-                    if token.is_err() {
-                        y.ret(token).await;
-                        return;
+            },
+            TokenFrame::BracelessObject(StructureStage::Next, _) => {
+                self.token_state.frames.push(TokenFrame::BracelessObject(StructureStage::Peek, None));
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::BracelessObject(_, _) => {
+                // End of braceless object
+                if self.peek().is_none() {
+                    self.close_token_structure(JsonTokenType::EndObject, self.char_counter);
+                    return;
+                }
+                // Property
+                self.token_state.frames.push(TokenFrame::BracelessObject(StructureStage::Next, None));
+                self.token_state.frames.push(TokenFrame::Property(PropertyStage::Start(None)));
+            },
+            // Property name
+            TokenFrame::Property(PropertyStage::Start(Some(property_name_tokens))) => {
+                let mut property_name: String = String::new();
+                for token in property_name_tokens {
+                    if token.json_type == JsonTokenType::PropertyName {
+                        property_name = token.unescaped_value().map_or_else(|_| token.value.clone(), |name| name.into_owned());
                     }
-                    y.ret(token).await;
+                    self.queue_token(token);
                 }
-            }
-
-            // Comments & whitespace
-            for token_result in self.read_comments_and_whitespace() {
-                if token_result.is_err() {
-                    y.ret(token_result).await;
+                self.token_state.frames.push(TokenFrame::Property(PropertyStage::Named(property_name)));
+            },
+            TokenFrame::Property(PropertyStage::Start(None)) => {
+                match self.read_string_token() {
+                    Ok(string_token) => {
+                        self.token_state.frames.push(TokenFrame::Property(PropertyStage::Colon(string_token)));
+                        self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+                    },
+                    Err(string_error) => self.fail_token_frames(string_error),
+                }
+            },
+            TokenFrame::Property(PropertyStage::Colon(string_token)) => {
+                // Colon
+                if !self.read_one(':') {
+                    self.fail_token_frames("Expected `:` after property name in object");
                     return;
                 }
-                y.ret(token_result).await;
-            }
-
+                // End of property name
+                match self.convert_property_name(string_token) {
+                    Ok(property_name_token) => {
+                        let property_name: String = property_name_token.unescaped_value().map_or_else(|_| property_name_token.value.clone(), |name| name.into_owned());
+                        self.token_state.frames.push(TokenFrame::Property(PropertyStage::Named(property_name)));
+                        self.queue_token(property_name_token);
+                    },
+                    Err(property_name_error) => self.fail_token_frames(property_name_error),
+                }
+            },
             // Property value
-            for token_result in self.read_element() {
-                if token_result.is_err() {
-                    y.ret(token_result).await;
+            TokenFrame::Property(PropertyStage::Named(property_name)) => {
+                self.path.push(JsonhPathSegment::Property(property_name));
+                self.token_state.frames.push(TokenFrame::Property(PropertyStage::Value));
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::Property(PropertyStage::Value) => {
+                self.token_state.frames.push(TokenFrame::Property(PropertyStage::AfterValue));
+                self.token_state.frames.push(TokenFrame::Element(ElementStage::Start));
+            },
+            TokenFrame::Property(PropertyStage::AfterValue) => {
+                // Source error
+                if let Some(source_error) = self.source_error() {
+                    self.fail_token_frames(source_error);
                     return;
                 }
-                y.ret(token_result).await;
-            }
-
-            // Comments & whitespace
-            for token_result in self.read_comments_and_whitespace() {
-                if token_result.is_err() {
-                    y.ret(token_result).await;
-                    return;
+                self.path.pop();
+                self.token_state.frames.push(TokenFrame::Property(PropertyStage::Comma));
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::Property(PropertyStage::Comma) => {
+                // Optional comma
+                if let Err(comma_error) = self.read_comma('}') {
+                    self.fail_token_frames(comma_error);
                 }
-                y.ret(token_result).await;
-            }
-
-            // Optional comma
-            self.read_one(',');
-        });
-    }
-    fn read_property_name(&mut self) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // String
-            let string_result: Result<JsonhToken, &'static str> = self.read_string();
-            if string_result.is_err() {
-                y.ret(string_result).await;
-                return;
-            }
-
-            // Comments & whitespace
-            for token_result in self.read_comments_and_whitespace() {
-                if token_result.is_err() {
-                    y.ret(token_result).await;
+            },
+            // Array
+            TokenFrame::Array(StructureStage::Start, item_index) => {
+                // Opening bracket
+                let start: u64 = self.char_counter;
+                if !self.read_one('[') {
+                    self.fail_token_frames("Expected `[` to start array");
                     return;
                 }
-                y.ret(token_result).await;
-            }
-
-            // Colon
-            if !self.read_one(':') {
-                y.ret(Err("Expected `:` after property name in object")).await;
-                return;
-            }
-
-            // End of property name
-            y.ret(Ok(JsonhToken::new(JsonTokenType::PropertyName, string_result.unwrap().value))).await;
-        });
-    }
-    fn read_array(&mut self) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Opening bracket
-            if !self.read_one('[') {
-                y.ret(Err("Expected `[` to start array")).await;
-                return;
-            }
-            // Start of array
-            y.ret(Ok(JsonhToken::new_empty(JsonTokenType::StartArray))).await;
-            self.depth += 1;
-
-            // Check exceeded max depth
-            if self.depth > self.options.max_depth {
-                y.ret(Err("Exceeded max depth")).await;
-                return;
-            }
-
-            loop {
-                // Comments & whitespace
-                for token_result in self.read_comments_and_whitespace() {
-                    if token_result.is_err() {
-                        y.ret(token_result).await;
-                        return;
-                    }
-                    y.ret(token_result).await;
+                self.token_state.frames.push(TokenFrame::Array(StructureStage::Opened, item_index));
+                self.queue_token(JsonhToken::new_empty(JsonTokenType::StartArray).with_span(start, self.char_counter));
+            },
+            TokenFrame::Array(StructureStage::Opened, item_index) => {
+                if let Err(structure_error) = self.open_token_structure(JsonTokenType::StartArray) {
+                    self.fail_token_frames(structure_error);
+                    return;
                 }
-
-                let Some(next) = self.peek() else {
+                self.token_state.frames.push(TokenFrame::Array(StructureStage::Next, item_index));
+            },
+            TokenFrame::Array(StructureStage::Next, item_index) => {
+                self.token_state.frames.push(TokenFrame::Array(StructureStage::Peek, item_index));
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::Array(StructureStage::Peek, item_index) => {
+                match self.peek() {
                     // End of incomplete array
-                    if self.options.incomplete_inputs {
-                        self.depth -= 1;
-                        y.ret(Ok(JsonhToken::new_empty(JsonTokenType::EndArray))).await;
-                        return;
-                    }
+                    None if self.options.incomplete_inputs => self.close_token_structure(JsonTokenType::EndArray, self.char_counter),
                     // Missing closing bracket
-                    y.ret(Err("Expected `]` to end array, got end of input")).await;
-                    return;
-                };
-
-                // Closing bracket
-                if next == ']' {
-                    // End of array
-                    self.read();
-                    self.depth -= 1;
-                    y.ret(Ok(JsonhToken::new_empty(JsonTokenType::EndArray))).await;
-                    return;
+                    None => self.fail_token_frames("Expected `]` to end array, got end of input"),
+                    // Closing bracket
+                    Some(']') => {
+                        let end_start: u64 = self.char_counter;
+                        self.read();
+                        self.close_token_structure(JsonTokenType::EndArray, end_start);
+                    },
+                    // Item
+                    Some(_) => {
+                        self.path.push(JsonhPathSegment::Index(item_index));
+                        self.token_state.frames.push(TokenFrame::Array(StructureStage::ItemEnd, item_index));
+                        self.token_state.frames.push(TokenFrame::Element(ElementStage::Start));
+                    },
                 }
-                // Item
-                else {
-                    for token_result in self.read_item() {
-                        if token_result.is_err() {
-                            y.ret(token_result).await;
-                            return;
-                        }
-                        y.ret(token_result).await;
-                    }
+            },
+            TokenFrame::Array(StructureStage::ItemEnd, item_index) => {
+                // Source error
+                if let Some(source_error) = self.source_error() {
+                    self.fail_token_frames(source_error);
+                    return;
                 }
-            }
-        });
-    }
-    fn read_item(&mut self) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            // Element
-            for token_result in self.read_element() {
-                if token_result.is_err() {
-                    y.ret(token_result).await;
+                self.token_state.frames.push(TokenFrame::Array(StructureStage::ItemComma, item_index));
+                self.token_state.frames.push(TokenFrame::CommentsAndWhitespace);
+            },
+            TokenFrame::Array(StructureStage::ItemComma, item_index) => {
+                // Optional comma
+                if let Err(comma_error) = self.read_comma(']') {
+                    self.fail_token_frames(comma_error);
                     return;
                 }
-                y.ret(token_result).await;
+                self.path.pop();
+                self.token_state.frames.push(TokenFrame::Array(StructureStage::Next, item_index + 1));
+            },
+        }
+    }
+    /// Starts reading the comments and whitespace after the last element with `read_next_token` (see `read_end_of_elements`).
+    pub(crate) fn begin_end_of_elements(&mut self) {
+        self.begin_tokens(TokenFrame::EndOfElements);
+    }
+    /// Returns a snapshot of the state of the reader, so reading can be resumed from it after the source ends early.
+    pub(crate) fn checkpoint(&self) -> ReaderCheckpoint {
+        return ReaderCheckpoint {
+            char_counter: self.char_counter,
+            line_counter: self.line_counter,
+            column_counter: self.column_counter,
+            depth: self.depth,
+            path: self.path.clone(),
+            allocated_bytes: self.allocated_bytes,
+            comment_bytes: self.comment_bytes,
+            stats: self.stats.clone(),
+            raw_escape_builder: self.raw_escape_builder.clone(),
+            token_state: self.token_state.clone(),
+        };
+    }
+    /// Restores the state of the reader from a snapshot (see `checkpoint`).
+    /// 
+    /// The source must be restored to the same position separately.
+    pub(crate) fn restore(&mut self, checkpoint: ReaderCheckpoint) {
+        self.char_counter = checkpoint.char_counter;
+        self.line_counter = checkpoint.line_counter;
+        self.column_counter = checkpoint.column_counter;
+        self.depth = checkpoint.depth;
+        self.path = checkpoint.path;
+        self.allocated_bytes = checkpoint.allocated_bytes;
+        self.comment_bytes = checkpoint.comment_bytes;
+        self.stats = checkpoint.stats;
+        self.raw_escape_builder = checkpoint.raw_escape_builder;
+        self.token_state = checkpoint.token_state;
+    }
+    fn start_tokens(&mut self, frame: TokenFrame) -> JsonhTokenIter<'_> {
+        self.begin_tokens(frame);
+        return JsonhTokenIter::new(self);
+    }
+    fn begin_tokens(&mut self, frame: TokenFrame) {
+        self.token_state.clear();
+        self.token_state.is_reading_element = true;
+        self.token_state.frames.push(frame);
+    }
+    fn is_property_name_match(token: &JsonhToken, property_name: &str, ignore_case: bool) -> bool {
+        let Ok(name) = token.unescaped_value() else {
+            return false;
+        };
+        // Ignore case
+        if ignore_case {
+            return name.to_lowercase() == property_name.to_lowercase();
+        }
+        return name == property_name;
+    }
+    fn step_to_array_item(&mut self) -> bool {
+        loop {
+            // Start of item
+            if let Some(TokenFrame::Array(StructureStage::Peek, _)) = self.token_state.frames.last() {
+                return !matches!(self.peek(), None | Some(']'));
+            }
+            if self.token_state.frames.is_empty() {
+                return false;
             }
 
             // Comments & whitespace
-            for token_result in self.read_comments_and_whitespace() {
+            self.step_token_frames();
+            for token_result in self.token_state.queued_tokens.drain(..) {
                 if token_result.is_err() {
-                    y.ret(token_result).await;
-                    return;
+                    return false;
                 }
-                y.ret(token_result).await;
             }
+        }
+    }
+    fn queue_token(&mut self, token: JsonhToken) {
+        self.token_state.queued_tokens.push_back(Ok(token));
+    }
+    fn fail_token_frames(&mut self, error: &'static str) {
+        self.token_state.frames.clear();
+        self.token_state.is_failed = true;
+        let error: &'static str = self.source_error().unwrap_or(error);
+        self.token_state.queued_tokens.push_back(Err(error));
+    }
+    fn open_token_structure(&mut self, json_type: JsonTokenType) -> Result<(), &'static str> {
+        self.depth += 1;
 
-            // Optional comma
-            self.read_one(',');
-        });
+        // Check exceeded max depth
+        if self.depth > self.options.max_depth {
+            return Err("Exceeded max depth");
+        }
+
+        // Structure callbacks
+        return self.invoke_structure_callbacks(json_type);
+    }
+    fn close_token_structure(&mut self, json_type: JsonTokenType, start: u64) {
+        if let Err(callback_error) = self.invoke_structure_callbacks(json_type) {
+            self.fail_token_frames(callback_error);
+            return;
+        }
+        self.depth -= 1;
+        self.queue_token(JsonhToken::new_empty(json_type).with_span(start, self.char_counter));
+    }
+
+    fn convert_property_name(&self, string_token: JsonhToken) -> Result<JsonhToken, &'static str> {
+        // Keep escape sequences
+        if self.options.retain_escapes {
+            return Ok(JsonhToken::new(JsonTokenType::PropertyName, string_token.value).with_span(string_token.start, string_token.end).with_escaped(string_token.is_escaped));
+        }
+        let property_name: String = string_token.unescaped_value()?.into_owned();
+        return Ok(JsonhToken::new(JsonTokenType::PropertyName, property_name).with_span(string_token.start, string_token.end));
     }
-    fn read_string(&mut self) -> Result<JsonhToken, &'static str> {
+    /// Reads a single string from the current position, without reading any surrounding whitespace or comments.
+    /// 
+    /// The string can be quoted, multi-quoted, verbatim or quoteless (quoteless strings can also be read as named literals).
+    /// Useful for lexing JSONH strings appearing outside of a document, such as in command-line arguments.
+    pub fn read_string(&mut self) -> Result<JsonhToken, JsonhError> {
+        return self.read_string_token().map_err(|error| self.locate_error(error));
+    }
+    fn read_string_token(&mut self) -> Result<JsonhToken, &'static str> {
+        let start: u64 = self.char_counter;
+
         // Verbatim
         let mut is_verbatim: bool = false;
         if self.options.supports_version(JsonhVersion::V2) && self.read_one('@') {
@@ -825,30 +1744,55 @@ impl<'a> JsonhReader<'a> {
             }
         }
 
+        // JSON verbatim string
+        if is_verbatim && self.options.strict_json {
+            return Err("Verbatim strings are not allowed in JSON");
+        }
+
         // Start quote
         let Some(start_quote) = self.read_any(&['"', '\'']) else {
-            return self.read_quoteless_string("", is_verbatim);
+            return self.read_quoteless_string("", is_verbatim, start);
         };
 
+        // JSON single-quoted string
+        if start_quote == '\'' && self.options.strict_json {
+            return Err("Single-quoted strings are not allowed in JSON");
+        }
+
         // Count multiple start quotes
         let mut start_quote_counter: usize = 1;
         while self.read_one(start_quote) {
             start_quote_counter += 1;
         }
 
+        // JSON multi-quoted string
+        if start_quote_counter >= 3 && self.options.strict_json {
+            return Err("Multi-quoted strings are not allowed in JSON");
+        }
+
         // Empty string
         if start_quote_counter == 2 {
-            return Ok(JsonhToken::new(JsonTokenType::String, String::new()));
+            return Ok(JsonhToken::new(JsonTokenType::String, String::new()).with_span(start, self.char_counter));
         }
 
         // Count multiple end quotes
         let mut end_quote_counter: usize = 0;
 
         // Read string
-        let mut string_builder: String = String::new();
+        let mut string_builder: String = self.take_value_builder(Self::MAX_PRESIZED_STRING_CAPACITY);
+        let is_lazy: bool = ((self.options.lazy_unescaping && start_quote_counter == 1) || self.options.retain_escapes) && !self.is_discarding_values;
+        let mut is_escaped: bool = false;
+        let mut is_incomplete: bool = false;
 
         loop {
+            self.check_string_length(string_builder.len())?;
+
             let Some(next) = self.read() else {
+                // End of incomplete string
+                if self.options.incomplete_inputs {
+                    is_incomplete = true;
+                    break;
+                }
                 return Err("Expected end of string, got end of input");
             };
 
@@ -869,9 +1813,20 @@ impl<'a> JsonhReader<'a> {
             }
             // Escape sequence
             else if next == '\\' {
+                // End of incomplete string (before escape sequence)
+                if self.options.incomplete_inputs && self.peek().is_none() {
+                    is_incomplete = true;
+                    break;
+                }
                 if is_verbatim {
                     string_builder.push(next);
                 }
+                // Keep escape sequence for later
+                else if is_lazy {
+                    string_builder.push(next);
+                    string_builder += &self.read_raw_escape_sequence()?;
+                    is_escaped = true;
+                }
                 else {
                     match self.read_escape_sequence(None) {
                         Ok(Some(escape_sequence_char)) => string_builder.push(escape_sequence_char),
@@ -880,14 +1835,23 @@ impl<'a> JsonhReader<'a> {
                     }
                 }
             }
+            // JSON control character
+            else if self.options.strict_json && next < '\u{20}' {
+                return Err("Control characters must be escaped in JSON");
+            }
             // Literal character
             else {
                 string_builder.push(next);
             }
         }
 
-        // Condition: skip remaining steps unless started with multiple quotes
-        if start_quote_counter > 1 {
+        // Escaped string
+        if is_escaped && start_quote_counter == 1 {
+            return self.allocate(JsonhToken::new(JsonTokenType::String, string_builder).with_span(start, self.char_counter).with_escaped(true));
+        }
+
+        // Condition: skip remaining steps unless started with multiple quotes (and kept and complete)
+        if start_quote_counter > 1 && !self.is_discarding_values && !is_incomplete {
             // Get chars from string builder
             let mut string_builder_chars: Vec<char> = string_builder.chars().collect();
 
@@ -951,13 +1915,13 @@ impl<'a> JsonhReader<'a> {
                     index2 += 1;
                 }
 
-                // Condition: skip remaining steps if pass 2 failed
-                if has_trailing_newline_whitespace {
+                // Condition: skip remaining steps if pass 2 failed (or the leading and trailing newlines are the same, e.g. `"""\n"""`)
+                if has_trailing_newline_whitespace && last_newline_index >= leading_whitespace_newline_counter {
                     // Pass 3: strip trailing newline -> whitespace
-                    string_builder_chars.drain(last_newline_index..string_builder_chars.len());
+                    string_builder_chars.truncate(last_newline_index);
 
                     // Pass 4: strip leading whitespace -> newline
-                    string_builder_chars.drain(..leading_whitespace_newline_counter);
+                    string_builder_chars.drain(..leading_whitespace_newline_counter.min(string_builder_chars.len()));
 
                     // Condition: skip remaining steps if no trailing whitespace
                     if trailing_whitespace_counter > 0 {
@@ -1011,15 +1975,21 @@ impl<'a> JsonhReader<'a> {
         }
 
         // End of string
-        return Ok(JsonhToken::new(JsonTokenType::String, string_builder.to_string()));
+        let value: String = self.finish_value(string_builder);
+        return self.allocate(JsonhToken::new(JsonTokenType::String, value).with_span(start, self.char_counter).with_escaped(is_escaped));
     }
-    fn read_quoteless_string(&mut self, initial_chars: &str, is_verbatim: bool) -> Result<JsonhToken, &'static str> {
+    fn read_quoteless_string(&mut self, initial_chars: &str, is_verbatim: bool, start: u64) -> Result<JsonhToken, &'static str> {
         let mut is_named_literal_possible: bool = !is_verbatim;
+        let mut is_escaped: bool = false;
 
         // Read quoteless string
-        let mut string_builder: String = String::from(initial_chars);
+        let mut string_builder: String = self.take_value_builder(Self::MAX_PRESIZED_STRING_CAPACITY);
+        string_builder.push_str(initial_chars);
+        let mut end: u64 = self.char_counter;
 
         loop {
+            self.check_string_length(string_builder.len())?;
+
             // Peek char
             let Some(next) = self.peek() else {
                 break;
@@ -1031,6 +2001,12 @@ impl<'a> JsonhReader<'a> {
                 if is_verbatim {
                     string_builder.push(next);
                 }
+                // Keep escape sequence
+                else if self.options.retain_escapes {
+                    string_builder.push(next);
+                    string_builder += &self.read_raw_escape_sequence()?;
+                    is_escaped = true;
+                }
                 else {
                     match self.read_escape_sequence(None) {
                         Ok(Some(escape_sequence_char)) => string_builder.push(escape_sequence_char),
@@ -1039,9 +2015,10 @@ impl<'a> JsonhReader<'a> {
                     }
                 }
                 is_named_literal_possible = false;
+                end = self.char_counter;
             }
             // End on reserved character
-            else if self.reserved_chars().contains(&next) {
+            else if self.is_reserved_char(next) {
                 break;
             }
             // End on newline
@@ -1052,32 +2029,64 @@ impl<'a> JsonhReader<'a> {
             else {
                 self.read();
                 string_builder.push(next);
+                // Exclude trailing whitespace from span
+                if !Self::WHITESPACE_CHARS.contains(&next) {
+                    end = self.char_counter;
+                }
             }
         }
 
         // Ensure not empty
         if string_builder.is_empty() {
-            return Err("Empty quoteless string");
+            return Err(if self.options.strict_json { "Expected element in JSON" } else { "Empty quoteless string" });
+        }
+
+        // JSON whitespace (e.g. after a named literal)
+        if self.options.strict_json && string_builder.chars().any(|next| Self::WHITESPACE_CHARS.contains(&next) && !matches!(next, ' ' | '\t')) {
+            return Err("Invalid whitespace in JSON");
         }
 
         // Trim whitespace
-        string_builder = string_builder.trim_matches(Self::WHITESPACE_CHARS).to_string();
+        string_builder.truncate(string_builder.trim_end_matches(Self::WHITESPACE_CHARS).len());
+        string_builder.drain(..string_builder.len() - string_builder.trim_start_matches(Self::WHITESPACE_CHARS).len());
+
+        // Remove escape of trimmed whitespace
+        if is_escaped && string_builder.chars().rev().take_while(|next| *next == '\\').count() % 2 == 1 {
+            string_builder.pop();
+        }
 
         // Match named literal
         if is_named_literal_possible {
             if string_builder == "null" {
-                return Ok(JsonhToken::new(JsonTokenType::Null, "null".to_string()));
+                return Ok(JsonhToken::new(JsonTokenType::Null, "null".to_string()).with_span(start, end));
             }
             else if string_builder == "true" {
-                return Ok(JsonhToken::new(JsonTokenType::True, "true".to_string()));
+                return Ok(JsonhToken::new(JsonTokenType::True, "true".to_string()).with_span(start, end));
             }
             else if string_builder == "false" {
-                return Ok(JsonhToken::new(JsonTokenType::False, "false".to_string()));
+                return Ok(JsonhToken::new(JsonTokenType::False, "false".to_string()).with_span(start, end));
+            }
+            else if let Some((_, element)) = self.named_literals.iter().find(|(literal, _)| *literal == string_builder) {
+                let token: JsonhToken = match element {
+                    Value::Null => JsonhToken::new(JsonTokenType::Null, "null".to_string()),
+                    Value::Bool(true) => JsonhToken::new(JsonTokenType::True, "true".to_string()),
+                    Value::Bool(false) => JsonhToken::new(JsonTokenType::False, "false".to_string()),
+                    Value::Number(number) => JsonhToken::new(JsonTokenType::Number, number.to_string()),
+                    Value::String(string) => JsonhToken::new(JsonTokenType::String, string.clone()),
+                    Value::Array(_) | Value::Object(_) => return Err("Named literal must not be an object or array"),
+                };
+                return self.allocate(token.with_span(start, end));
             }
         }
 
+        // JSON quoteless string
+        if self.options.strict_json {
+            return Err("Quoteless strings are not allowed in JSON");
+        }
+
         // End of quoteless string
-        return Ok(JsonhToken::new(JsonTokenType::String, string_builder.to_string()));
+        let value: String = self.finish_value(string_builder);
+        return self.allocate(JsonhToken::new(JsonTokenType::String, value).with_span(start, end).with_escaped(is_escaped));
     }
     fn detect_quoteless_string(&mut self, whitespace_builder: &mut String) -> bool {
         loop {
@@ -1104,11 +2113,20 @@ impl<'a> JsonhReader<'a> {
 
         // Found quoteless string if found backslash or non-reserved char
         if let Some(next_char) = self.peek() {
-            return next_char == '\\' || !self.reserved_chars().contains(&next_char);
+            return next_char == '\\' || !self.is_reserved_char(next_char);
         }
         return false;
     }
-    fn read_number(&mut self, mut number_builder: &mut String) -> Result<JsonhToken, &'static str> {
+    /// Reads a single number from the current position, without reading any surrounding whitespace or comments.
+    /// 
+    /// Unlike elements, numbers are never read as quoteless strings (for example, `5 apples` is read as `5`).
+    pub fn read_number(&mut self) -> Result<JsonhToken, JsonhError> {
+        let mut number_builder: String = String::new();
+        return self.read_number_into(&mut number_builder).map_err(|error| self.locate_error(error));
+    }
+    fn read_number_into(&mut self, mut number_builder: &mut String) -> Result<JsonhToken, &'static str> {
+        let start: u64 = self.char_counter;
+
         // Read sign
         if let Some(sign) = self.read_any(&['-', '+']) {
             number_builder.push(sign);
@@ -1148,7 +2166,7 @@ impl<'a> JsonhReader<'a> {
         }
 
         // Possible hexadecimal exponent
-        if matches!(number_builder.chars().last().unwrap(), 'e' | 'E') {
+        if number_builder.ends_with(['e', 'E']) {
             // Read sign (mandatory)
             if let Some(exponent_sign) = self.read_any(&['-', '+']) {
                 number_builder.push(exponent_sign);
@@ -1180,7 +2198,8 @@ impl<'a> JsonhReader<'a> {
         }
 
         // End of number
-        return Ok(JsonhToken::new(JsonTokenType::Number, number_builder.clone()));
+        self.check_string_length(number_builder.len())?;
+        return self.allocate(JsonhToken::new(JsonTokenType::Number, number_builder.clone()).with_span(start, self.char_counter));
     }
     fn read_number_no_exponent(&mut self, number_builder: &mut String, base_digits: &str, has_base_specifier: bool, has_leading_zero: bool) -> Result<(), &'static str> {
         // Leading underscore
@@ -1197,6 +2216,8 @@ impl<'a> JsonhReader<'a> {
         }
 
         loop {
+            self.check_string_length(number_builder.len())?;
+
             // Peek char
             let Some(next) = self.peek() else {
                 break;
@@ -1261,14 +2282,24 @@ impl<'a> JsonhReader<'a> {
         return Ok(());
     }
     fn read_number_or_quoteless_string(&mut self) -> Result<JsonhToken, &'static str> {
+        let start: u64 = self.char_counter;
+
         // Read number
         let mut number_builder: String = String::new();
-        let number: Result<JsonhToken, &'static str> = self.read_number(&mut number_builder);
+        let number: Result<JsonhToken, &'static str> = self.read_number_into(&mut number_builder);
         if number.is_ok() {
             // Try read quoteless string starting with number
             let mut whitespace_chars: String = String::new();
             if self.detect_quoteless_string(&mut whitespace_chars) {
-                return self.read_quoteless_string((number.unwrap().value + whitespace_chars.as_str()).as_str(), false);
+                return self.read_quoteless_string((number.unwrap().value + whitespace_chars.as_str()).as_str(), false, start);
+            }
+            // JSON number
+            else if self.options.strict_json && !Self::is_json_number(&number.as_ref().unwrap().value) {
+                return Err("Invalid number in JSON");
+            }
+            // JSON whitespace after number
+            else if self.options.strict_json && whitespace_chars.chars().any(|next| !matches!(next, ' ' | '\t')) {
+                return Err("Invalid whitespace in JSON");
             }
             // Otherwise, accept number
             else {
@@ -1277,7 +2308,7 @@ impl<'a> JsonhReader<'a> {
         }
         // Read quoteless string starting with malformed number
         else {
-            return self.read_quoteless_string(number_builder.as_str(), false);
+            return self.read_quoteless_string(number_builder.as_str(), false, start);
         }
     }
     fn read_primitive_element(&mut self) -> Result<JsonhToken, &'static str> {
@@ -1286,42 +2317,61 @@ impl<'a> JsonhReader<'a> {
             return Err("Expected primitive element, got end of input");
         };
 
+        // Extension
+        if let Some(index) = self.primitive_extensions.iter().position(|(start_char, _)| *start_char == next) {
+            let start: u64 = self.char_counter;
+            let start_position: u64 = self.source.position();
+            let token: JsonhToken = (self.primitive_extensions[index].1)(&mut *self.source)?;
+            self.char_counter += self.source.position().saturating_sub(start_position);
+            self.column_counter += self.source.position().saturating_sub(start_position);
+
+            if !matches!(token.json_type, JsonTokenType::String | JsonTokenType::Number | JsonTokenType::True | JsonTokenType::False | JsonTokenType::Null) {
+                return Err("Primitive extension must read a primitive element");
+            }
+            return self.allocate(token.with_span(start, self.char_counter));
+        }
         // Number
         if matches!(next, '0'..='9' | '-' | '+' | '.') {
             return self.read_number_or_quoteless_string();
         }
         // String
         else if matches!(next, '"' | '\'') || (self.options.supports_version(JsonhVersion::V2) && next == '@') {
-            return self.read_string();
+            return self.read_string_token();
         }
         // Quoteless string (or named literal)
         else {
-            return self.read_quoteless_string("", false);
+            let start: u64 = self.char_counter;
+            return self.read_quoteless_string("", false, start);
         }
     }
-    fn read_comments_and_whitespace(&mut self) -> LocalIter<'_, Result<JsonhToken, &'static str>> {
-        return LocalIter::new(|mut y| async move {
-            loop {
-                // Whitespace
-                self.read_whitespace();
+    fn read_comments_and_whitespace(&mut self) -> JsonhTokenIter<'_> {
+        return self.start_tokens(TokenFrame::CommentsAndWhitespace);
+    }
+    /// Reads a single comment from the current position, without reading any surrounding whitespace.
+    /// 
+    /// The comment can be a hash-style, line-style, block-style or nestable block-style comment.
+    pub fn read_comment(&mut self) -> Result<JsonhToken, JsonhError> {
+        return self.read_comment_with_callbacks().map_err(|error| self.locate_error(error));
+    }
+    fn read_comment_with_callbacks(&mut self) -> Result<JsonhToken, &'static str> {
+        let token: JsonhToken = self.read_comment_token()?;
 
-                // Comment
-                if matches!(self.peek(), Some('#') | Some('/')) {
-                    let comment_result: Result<JsonhToken, &'static str> = self.read_comment();
-                    if comment_result.is_err() {
-                        y.ret(comment_result).await;
-                        return;
-                    }
-                    y.ret(comment_result).await;
-                }
-                // End of comments
-                else {
-                    return;
-                }
+        // Invoke comment callbacks
+        if !self.comment_callbacks.is_empty() {
+            let comment: JsonhComment = JsonhComment::new(self.path.clone(), token.value.clone()).with_span(token.start, token.end);
+            for comment_callback in self.comment_callbacks.iter_mut() {
+                comment_callback(&comment)?;
             }
-        });
+        }
+        return Ok(token);
     }
-    fn read_comment(&mut self) -> Result<JsonhToken, &'static str> {
+    fn read_comment_token(&mut self) -> Result<JsonhToken, &'static str> {
+        // Disallowed comment
+        if !self.options.allow_comments {
+            return Err("Comments are not allowed");
+        }
+
+        let start: u64 = self.char_counter;
         let mut block_comment: bool = false;
         let mut start_nest_counter: i32 = 0;
 
@@ -1355,9 +2405,12 @@ impl<'a> JsonhReader<'a> {
         }
 
         // Read comment
-        let mut comment_builder: String = String::new();
+        let mut comment_builder: String = self.take_value_builder(Self::MAX_PRESIZED_STRING_CAPACITY);
 
         loop {
+            // Check exceeded max comment length
+            self.check_comment_limits(comment_builder.len())?;
+
             // Read char
             let next: Option<char> = self.read();
 
@@ -1389,14 +2442,20 @@ impl<'a> JsonhReader<'a> {
 
                     // End of block comment
                     if self.read_one('/') {
-                        return Ok(JsonhToken::new(JsonTokenType::Comment, comment_builder));
+                        self.comment_bytes += comment_builder.len();
+                        let comment: String = self.finish_value(comment_builder);
+                        return self.allocate(JsonhToken::new(JsonTokenType::Comment, comment).with_span(start, self.char_counter));
                     }
                 }
             }
             else {
                 // End of line comment
                 if next.is_none() || Self::NEWLINE_CHARS.contains(&next.unwrap()) {
-                    return Ok(JsonhToken::new(JsonTokenType::Comment, comment_builder));
+                    // Exclude newline from span
+                    let end: u64 = if next.is_none() { self.char_counter } else { self.char_counter - 1 };
+                    self.comment_bytes += comment_builder.len();
+                    let comment: String = self.finish_value(comment_builder);
+                    return self.allocate(JsonhToken::new(JsonTokenType::Comment, comment).with_span(start, end));
                 }
             }
 
@@ -1411,8 +2470,8 @@ impl<'a> JsonhReader<'a> {
                 return;
             };
 
-            // Whitespace
-            if char::is_whitespace(next) {
+            // Whitespace (only space, tab and newlines in JSON)
+            if char::is_whitespace(next) && (!self.options.strict_json || matches!(next, ' ' | '\t' | '\n' | '\r')) {
                 self.read();
             }
             // End of whitespace
@@ -1451,6 +2510,34 @@ impl<'a> JsonhReader<'a> {
         // Return aggregated value
         return Ok(value);
     }
+    fn read_raw_escape_sequence(&mut self) -> Result<String, &'static str> {
+        // Validate escape sequence and keep the characters read
+        self.raw_escape_builder = Some(String::new());
+        let result: Result<Option<char>, &'static str> = self.read_escape_sequence(None);
+        let raw_escape_sequence: String = self.raw_escape_builder.take().unwrap_or_default();
+
+        result?;
+        return Ok(raw_escape_sequence);
+    }
+    /// Decodes the escape sequences in the contents of a string.
+    pub(crate) fn unescape(value: &str) -> Result<String, &'static str> {
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(value, JsonhReaderOptions::new());
+        let mut string_builder: String = String::with_capacity(value.len());
+
+        while let Some(next) = reader.read() {
+            // Escape sequence
+            if next == '\\' {
+                if let Some(escape_sequence_char) = reader.read_escape_sequence(None)? {
+                    string_builder.push(escape_sequence_char);
+                }
+            }
+            // Literal character
+            else {
+                string_builder.push(next);
+            }
+        }
+        return Ok(string_builder);
+    }
     fn read_escape_sequence(&mut self, high_surrogate: Option<u32>) -> Result<Option<char>, &'static str> {
         let Some(escape_char) = self.read() else {
             return Err("Expected escape sequence, got end of input");
@@ -1460,6 +2547,10 @@ impl<'a> JsonhReader<'a> {
         if high_surrogate.is_some() && !matches!(escape_char, 'u' | 'x' | 'U') {
             return Err("Expected low surrogate after high surrogate");
         }
+        // JSON escape sequence
+        if self.options.strict_json && !matches!(escape_char, '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't' | 'u') {
+            return Err("Invalid escape sequence in JSON");
+        }
 
         // Reverse solidus
         if escape_char == '\\' {
@@ -1557,11 +2648,150 @@ impl<'a> JsonhReader<'a> {
             }
         }
     }
+    fn allocate(&mut self, token: JsonhToken) -> Result<JsonhToken, &'static str> {
+        self.allocated_bytes += token.value.len();
+
+        // Check exceeded max allocation
+        if self.options.max_allocation.is_some_and(|max_allocation| self.allocated_bytes > max_allocation) {
+            return Err("Exceeded max allocation");
+        }
+        return Ok(token);
+    }
+    /// Returns whether the character cannot be used unescaped in quoteless strings in the version being read, including the characters registered with `with_reserved_chars`.
+    pub fn is_reserved_char(&self, next: char) -> bool {
+        return self.reserved_chars().contains(&next) || self.extra_reserved_chars.contains(&next);
+    }
+    fn discard_values<T>(&mut self, read: impl FnOnce(&mut Self) -> T) -> T {
+        // Callbacks are given property names and comments, so values are only discarded without callbacks
+        let was_discarding_values: bool = self.is_discarding_values;
+        self.is_discarding_values = was_discarding_values || (self.structure_callbacks.is_empty() && self.comment_callbacks.is_empty());
+        let result: T = read(self);
+        self.is_discarding_values = was_discarding_values;
+        return result;
+    }
+    fn take_value_builder(&mut self, max_capacity: usize) -> String {
+        // Reuse buffer of last discarded value
+        if self.is_discarding_values {
+            let mut value_builder: String = std::mem::take(&mut self.discarded_value);
+            value_builder.clear();
+            return value_builder;
+        }
+        return String::with_capacity(self.estimate_capacity(max_capacity));
+    }
+    fn finish_value(&mut self, value: String) -> String {
+        // Keep buffer for next discarded value
+        if self.is_discarding_values {
+            self.discarded_value = value;
+            return String::new();
+        }
+        return value;
+    }
+    fn estimate_capacity(&self, max_capacity: usize) -> usize {
+        let Some(capacity_divisor) = self.options.capacity_divisor else {
+            return 0;
+        };
+        let Some(remaining) = self.source.size_hint() else {
+            return 0;
+        };
+        return (remaining / capacity_divisor.max(1)).min(max_capacity);
+    }
+    fn check_string_length(&self, string_length: usize) -> Result<(), &'static str> {
+        if self.options.max_string_length.is_some_and(|max_string_length| string_length > max_string_length) {
+            return Err("Exceeded max string length");
+        }
+        return Ok(());
+    }
+    fn check_comment_limits(&self, comment_length: usize) -> Result<(), &'static str> {
+        if self.options.max_comment_length.is_some_and(|max_comment_length| comment_length > max_comment_length) {
+            return Err("Exceeded max comment length");
+        }
+        if self.options.max_comment_bytes.is_some_and(|max_comment_bytes| self.comment_bytes + comment_length > max_comment_bytes) {
+            return Err("Exceeded max comment bytes");
+        }
+        return Ok(());
+    }
+    fn invoke_structure_callbacks(&mut self, json_type: JsonTokenType) -> Result<(), &'static str> {
+        for structure_callback in self.structure_callbacks.iter_mut() {
+            structure_callback(json_type, &self.path, self.depth)?;
+        }
+        return Ok(());
+    }
     fn peek(&mut self) -> Option<char> {
-        return self.source.peek().copied();
+        if self.is_max_chars_reached() {
+            return None;
+        }
+        return self.source.peek();
     }
     fn read(&mut self) -> Option<char> {
-        return self.source.next();
+        if self.is_max_chars_reached() {
+            return None;
+        }
+        let next: Option<char> = self.source.next();
+        if let Some(next) = next {
+            self.char_counter += 1;
+            // Newline (joining CR LF)
+            if matches!(next, '\n' | '\u{2028}' | '\u{2029}') || (next == '\r' && self.peek() != Some('\n')) {
+                self.line_counter += 1;
+                self.column_counter = 1;
+            }
+            else if next != '\r' {
+                self.column_counter += 1;
+            }
+            if let Some(raw_escape_builder) = self.raw_escape_builder.as_mut() {
+                raw_escape_builder.push(next);
+            }
+        }
+        return next;
+    }
+    fn is_max_chars_reached(&self) -> bool {
+        return self.options.max_chars.is_some_and(|max_chars| self.char_counter >= max_chars);
+    }
+    fn source_error(&mut self) -> Option<&'static str> {
+        // Input ended early by max chars
+        if self.is_max_chars_reached() && self.source.peek().is_some() {
+            return Some("Exceeded max chars");
+        }
+        return self.source.error();
+    }
+    fn read_comma(&mut self, end_char: char) -> Result<(), &'static str> {
+        let has_comma: bool = self.read_one(',');
+
+        // JSON commas
+        if self.options.strict_json {
+            self.read_whitespace();
+            match self.peek() {
+                Some(next) if char::is_whitespace(next) => return Err("Invalid whitespace in JSON"),
+                Some(next) if has_comma && next == end_char => return Err("Trailing commas are not allowed in JSON"),
+                Some(',') if has_comma => return Err("Expected element after `,` in JSON"),
+                Some(next) if !has_comma && next != end_char && !matches!(next, '#' | '/') => return Err("Expected `,` between elements in JSON"),
+                _ => {},
+            }
+        }
+        return Ok(());
+    }
+    fn is_json_number(number: &str) -> bool {
+        let unsigned: &str = number.strip_prefix('-').unwrap_or(number);
+        let is_digits = |digits: &str| -> bool { !digits.is_empty() && digits.chars().all(|next| next.is_ascii_digit()) };
+
+        // Integer
+        let integer_length: usize = unsigned.find(['.', 'e', 'E']).unwrap_or(unsigned.len());
+        let (integer, mut rest) = unsigned.split_at(integer_length);
+        if !is_digits(integer) || (integer.len() > 1 && integer.starts_with('0')) {
+            return false;
+        }
+        // Fraction
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let fraction_length: usize = fraction.find(['e', 'E']).unwrap_or(fraction.len());
+            if !is_digits(&fraction[..fraction_length]) {
+                return false;
+            }
+            rest = &fraction[fraction_length..];
+        }
+        // Exponent
+        if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+            return is_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent));
+        }
+        return rest.is_empty();
     }
     fn read_one(&mut self, option: char) -> bool {
         if self.peek() == Some(option) {
@@ -1596,4 +2826,180 @@ impl<'a> JsonhReader<'a> {
     const fn is_utf16_low_surrogate(code_point: u32) -> bool {
         return code_point >= 0xDC00 && code_point <= 0xDFFF;
     }
+}
+
+/// Associates comments with the path of the element they precede.
+struct CommentTracker {
+    path: JsonhPath,
+    structures: Vec<CommentTrackerStructure>,
+    pending_comments: Vec<JsonhToken>,
+    comments: Vec<JsonhComment>,
+}
+
+/// An object or array being tracked by a `CommentTracker`.
+struct CommentTrackerStructure {
+    is_array: bool,
+    item_index: usize,
+}
+
+impl CommentTracker {
+    fn new() -> Self {
+        return Self { path: JsonhPath::new(), structures: Vec::new(), pending_comments: Vec::new(), comments: Vec::new() };
+    }
+    fn record_token(&mut self, token: &JsonhToken) {
+        match token.json_type {
+            // Comment
+            JsonTokenType::Comment => {
+                self.pending_comments.push(token.clone());
+            },
+            // Property name
+            JsonTokenType::PropertyName => {
+                self.path.push(JsonhPathSegment::Property(token.value.clone()));
+                self.submit_comments();
+            },
+            // End of structure
+            JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                self.submit_comments();
+                self.structures.pop();
+                if !self.structures.is_empty() {
+                    self.path.pop();
+                }
+            },
+            // Start of structure or primitive
+            _ => {
+                // Array item
+                if let Some(structure) = self.structures.last_mut() && structure.is_array {
+                    self.path.push(JsonhPathSegment::Index(structure.item_index));
+                    structure.item_index += 1;
+                }
+                self.submit_comments();
+
+                // Start of structure
+                if matches!(token.json_type, JsonTokenType::StartObject | JsonTokenType::StartArray) {
+                    self.structures.push(CommentTrackerStructure { is_array: token.json_type == JsonTokenType::StartArray, item_index: 0 });
+                }
+                // End of primitive
+                else if !self.structures.is_empty() {
+                    self.path.pop();
+                }
+            },
+        }
+    }
+    fn submit_comments(&mut self) {
+        for comment in self.pending_comments.drain(..) {
+            self.comments.push(JsonhComment::new(self.path.clone(), comment.value).with_span(comment.start, comment.end));
+        }
+    }
+    fn finish(mut self) -> Vec<JsonhComment> {
+        // Remaining comments belong to root
+        self.path = JsonhPath::new();
+        self.submit_comments();
+        return self.comments;
+    }
+}
+
+impl TokenReader for JsonhReader<'_> {
+    fn next_token(&mut self) -> Option<Result<JsonhToken, &'static str>> {
+        return self.read_next_token();
+    }
+    fn locate_error(&self, message: &'static str) -> JsonhError {
+        return JsonhReader::locate_error(self, message);
+    }
+    fn cancel_tokens(&mut self) {
+        self.token_state.clear();
+    }
+}
+
+/// A snapshot of the state of a `JsonhReader` (see `JsonhReader::checkpoint`).
+pub(crate) struct ReaderCheckpoint {
+    char_counter: u64,
+    line_counter: u64,
+    column_counter: u64,
+    depth: i32,
+    path: JsonhPath,
+    allocated_bytes: usize,
+    comment_bytes: usize,
+    stats: JsonhParseStats,
+    raw_escape_builder: Option<String>,
+    token_state: TokenState,
+}
+
+/// The state of the element being read by `JsonhReader::next_token`.
+#[derive(Clone)]
+struct TokenState {
+    /// The partially read structures of the element, innermost last.
+    frames: Vec<TokenFrame>,
+    /// The tokens read but not yet returned.
+    queued_tokens: VecDeque<Result<JsonhToken, &'static str>>,
+    /// The next token, if it has been peeked.
+    peeked_token: Option<Option<Result<JsonhToken, &'static str>>>,
+    /// Whether an element is being read.
+    is_reading_element: bool,
+    /// Whether reading the element failed.
+    is_failed: bool,
+}
+
+impl TokenState {
+    fn new() -> Self {
+        return Self { frames: Vec::new(), queued_tokens: VecDeque::new(), peeked_token: None, is_reading_element: false, is_failed: false };
+    }
+    fn clear(&mut self) {
+        self.frames.clear();
+        self.queued_tokens.clear();
+        self.peeked_token = None;
+        self.is_reading_element = false;
+        self.is_failed = false;
+    }
+}
+
+/// A partially read part of an element, resumed by `JsonhReader::step_token_frames`.
+#[derive(Clone)]
+enum TokenFrame {
+    /// Comments and whitespace.
+    CommentsAndWhitespace,
+    /// Comments and whitespace after the last element.
+    EndOfElements,
+    /// The end of the input after the last element.
+    EndOfElementsPeek,
+    /// An element of any type.
+    Element(ElementStage),
+    /// A primitive element, which may be the first property name of a braceless object.
+    PrimitiveOrBracelessObject(JsonhToken),
+    /// An object with braces.
+    Object(StructureStage),
+    /// An object without braces, along with the tokens of its first property name if already read.
+    BracelessObject(StructureStage, Option<Vec<JsonhToken>>),
+    /// A property of an object.
+    Property(PropertyStage),
+    /// An array, along with the index of the next item.
+    Array(StructureStage, usize),
+}
+
+/// The progress through an element.
+#[derive(Clone)]
+enum ElementStage {
+    Start,
+    Peek,
+}
+
+/// The progress through an object or array.
+#[derive(Clone)]
+enum StructureStage {
+    Start,
+    Opened,
+    Next,
+    Peek,
+    ItemEnd,
+    ItemComma,
+}
+
+/// The progress through a property.
+#[derive(Clone)]
+enum PropertyStage {
+    Start(Option<Vec<JsonhToken>>),
+    Colon(JsonhToken),
+    Named(String),
+    Value,
+    AfterValue,
+    Comma,
 }
\ No newline at end of file
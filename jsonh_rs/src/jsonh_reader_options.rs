@@ -30,6 +30,26 @@ pub struct JsonhReaderOptions {
     /// 
     /// The default value is 64 to defend against DOS attacks.
     pub max_depth: i32,
+    /// Sets the maximum number of properties in a single object when parsing, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max properties: Some(2)
+    /// { a: 1, b: 2, c: 3 } // Error: Exceeded max properties
+    /// { a: 1, a: 2, a: 3 } // Error: Exceeded max properties
+    /// ```
+    /// 
+    /// Every property name is counted, including duplicates (which replace the earlier value).
+    /// This defends against inputs with huge numbers of small properties exhausting memory in `JsonhReader::parse_element`.
+    pub max_properties: Option<usize>,
+    /// Sets the maximum number of items in a single array when parsing, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max items: Some(2)
+    /// [1, 2, 3] // Error: Exceeded max items
+    /// ```
+    /// 
+    /// This defends against inputs with huge numbers of small items exhausting memory in `JsonhReader::parse_element`.
+    pub max_items: Option<usize>,
     /// Enables/disables parsing unclosed inputs.
     /// 
     /// ```
@@ -40,21 +60,157 @@ pub struct JsonhReaderOptions {
     /// This is potentially useful for large language models that stream responses.<br/>
     /// Only some tokens can be incomplete in this mode, so it should not be relied upon.
     pub incomplete_inputs: bool,
+    /// Enables/disables reading unknown versions of the specification (see `JsonhVersion::Other`).
+    /// 
+    /// If enabled, unknown versions are read on a best-effort basis using the latest known version.
+    /// If disabled, reading fails with an error.
+    /// 
+    /// This is potentially useful for reading documents written by newer tools.
+    pub unknown_versions: bool,
+    /// Enables/disables deferring the decoding of escape sequences in strings until their values are used.
+    /// 
+    /// If enabled, escape sequences in quoted strings are validated but kept as written, and the token is marked with `JsonhToken::is_escaped`.
+    /// The value can be decoded with `JsonhToken::unescaped_value`.
+    /// Multi-quoted strings, quoteless strings and property names are always decoded.
+    /// 
+    /// ```
+    /// "a\tb" // Value: `a\tb`, Unescaped value: `a<tab>b`
+    /// ```
+    /// 
+    /// This is potentially useful for skipping over most of a document (e.g. with `JsonhReader::find_property_value`).
+    pub lazy_unescaping: bool,
+    /// Sets the maximum number of bytes the reader may allocate for token values and parsed elements, or `None` for no limit.
+    /// 
+    /// The number of bytes is approximate and accumulates over every element read by the reader.
+    /// 
+    /// ```
+    /// // Max allocation: Some(8)
+    /// "a very long string" // Error: Exceeded max allocation
+    /// ```
+    /// 
+    /// Unlike limiting the size of the input, this bounds the memory used by escape-heavy or deeply nested content.
+    pub max_allocation: Option<usize>,
+    /// Sets the maximum number of characters to read from the source, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max chars: Some(8)
+    /// [1, 2, 3, 4, 5] // Error: Exceeded max chars
+    /// ```
+    /// 
+    /// The limit is checked against `JsonhReader::char_counter`, so the reader never consumes more of an untrusted source than this, regardless of its content.
+    pub max_chars: Option<u64>,
+    /// Sets the maximum number of bytes in a single string, property name or comment, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max string length: Some(8)
+    /// "a very long string" // Error: Exceeded max string length
+    /// ```
+    /// 
+    /// This applies to quoted strings, quoteless strings and numbers, so the reader stops before growing a buffer unboundedly on hostile input.
+    /// Comments are limited by `max_comment_length` instead.
+    pub max_string_length: Option<usize>,
+    /// Sets the maximum number of bytes in a single comment, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max comment length: Some(8)
+    /// /* a very long comment */ // Error: Exceeded max comment length
+    /// ```
+    pub max_comment_length: Option<usize>,
+    /// Sets the maximum total number of bytes in comments, or `None` for no limit.
+    /// 
+    /// The number of bytes accumulates over every comment read by the reader.
+    /// 
+    /// Since comments are discarded when parsing, this defends against inputs hiding large amounts of data in comments.
+    pub max_comment_bytes: Option<usize>,
+    /// Sets the fraction of the remaining input length used to pre-size buffers, or `None` to not pre-size buffers.
+    /// 
+    /// When the length of the input is known (e.g. when reading from a string), string buffers are pre-sized to `remaining / capacity_divisor` bytes (up to 256),
+    /// and objects and arrays are pre-sized to `remaining / capacity_divisor` items (up to 16) when parsing.
+    /// 
+    /// This reduces reallocations when reading large documents with long strings or large structures, at the cost of unused capacity.
+    pub capacity_divisor: Option<usize>,
+    /// Enables/disables keeping escape sequences in strings and property names exactly as written.
+    /// 
+    /// If enabled, escape sequences in every string (including multi-quoted strings, quoteless strings and property names) are validated but never decoded,
+    /// and tokens containing escape sequences are marked with `JsonhToken::is_escaped`. The value can be decoded with `JsonhToken::unescaped_value`.
+    /// 
+    /// ```
+    /// "\u0041" // Value: `\u0041`
+    /// ```
+    /// 
+    /// This is potentially useful for transcoders and formatters that preserve the original text.
+    /// Since escape sequences are not decoded, escaped whitespace is not considered when stripping indentation from multi-quoted strings.
+    pub retain_escapes: bool,
+    /// Enables/disables ignoring case when matching property names to find values.
+    /// 
+    /// ```
+    /// { "Port": 8080 } // find_path_value("port"): found
+    /// ```
+    /// 
+    /// This option only applies when finding values (e.g. with `JsonhReader::find_path_value`), not when reading or parsing elements.
+    pub ignore_property_name_case: bool,
+    /// Enables/disables comments.
+    /// 
+    /// ```
+    /// # comment // Error: Comments are not allowed
+    /// ```
+    /// 
+    /// Disabling comments is useful for machine-generated documents, where comments are unexpected.
+    pub allow_comments: bool,
+    /// Enables/disables rejecting syntax that is not valid in JSON (RFC 8259).
+    /// 
+    /// ```
+    /// { 'a': 0x10 } // Error: Single-quoted strings are not allowed in JSON
+    /// ```
+    /// 
+    /// Quoteless strings, single-quoted strings, multi-quoted strings, verbatim strings, braceless objects, trailing or missing commas
+    /// and numbers that are not valid in JSON (e.g. with underscores or base prefixes) are rejected. Comments are controlled by `allow_comments`.
+    /// 
+    /// Escape sequences other than `\" \\ \/ \b \f \n \r \t \uXXXX`, unescaped control characters in strings
+    /// and whitespace other than space, tab, line feed and carriage return are also rejected.
+    pub strict_json: bool,
 }
 
 impl JsonhReaderOptions {
     /// Constructs a `JsonhReaderOptions` with some default values.
     pub fn new() -> Self {
-        return Self { version: JsonhVersion::Latest, parse_single_element: false, max_depth: 64, incomplete_inputs: false };
+        return Self { version: JsonhVersion::Latest, parse_single_element: false, max_depth: 64, max_properties: None, max_items: None, incomplete_inputs: false, unknown_versions: false, lazy_unescaping: false, max_allocation: None, max_chars: None, max_string_length: None, max_comment_length: None, max_comment_bytes: None, capacity_divisor: None, retain_escapes: false, ignore_property_name_case: false, allow_comments: true, strict_json: false };
+    }
+    /// Constructs a `JsonhReaderOptions` that also accepts input written for unknown (newer) versions of JSONH.
+    /// 
+    /// Unknown versions are read as the latest version, and input after the first element is ignored.
+    /// Incomplete inputs are still rejected (see `llm_streaming`).
+    pub fn lenient() -> Self {
+        return Self::new().with_unknown_versions(true).with_parse_single_element(false);
+    }
+    /// Constructs a `JsonhReaderOptions` that rejects anything other than a single complete JSONH element.
+    /// 
+    /// Unknown versions are rejected, and exactly one element is expected when parsing.
+    pub fn strict() -> Self {
+        return Self::new().with_unknown_versions(false).with_parse_single_element(true).incomplete_inputs(false);
+    }
+    /// Constructs a `JsonhReaderOptions` for reading output streamed by a language model, which may be cut off or followed by other text.
+    /// 
+    /// ```
+    /// { "answer": "Paris", "sources": [1, 2 // Parsed: {"answer": "Paris", "sources": [1, 2]}
+    /// ```
+    /// 
+    /// Unclosed objects, arrays and strings are accepted (see `incomplete_inputs`), input after the first element is ignored and unknown versions are read as the latest version.
+    /// To parse the element as each chunk arrives, use these options with `JsonhPartialParser`.
+    pub fn llm_streaming() -> Self {
+        return Self::lenient().incomplete_inputs(true);
+    }
+    /// Constructs a `JsonhReaderOptions` that only accepts JSON (RFC 8259), so the same reader can serve JSONH and JSON.
+    /// 
+    /// Comments and JSONH syntax are rejected (see `strict_json`), and exactly one element is expected when parsing.
+    pub fn strict_json() -> Self {
+        return Self::new().with_allow_comments(false).with_strict_json(true).with_parse_single_element(true);
     }
     /// Returns whether `version` is greater than or equal to `minimum_version`.
+    /// 
+    /// Unknown versions newer than the latest known version support every known version.
     pub fn supports_version(&self, minimum_version: JsonhVersion) -> bool {
-        const LATEST_VERSION: JsonhVersion = JsonhVersion::V2;
-
-        let options_version: JsonhVersion = if self.version == JsonhVersion::Latest { LATEST_VERSION } else { self.version };
-        let given_version: JsonhVersion = if minimum_version == JsonhVersion::Latest { LATEST_VERSION } else { minimum_version };
-
-        return options_version >= given_version;
+        return self.version.number() >= minimum_version.number();
     }
     /// Specifies the major version of the JSONH specification to use.
     pub fn with_version(mut self, value: JsonhVersion) -> Self {
@@ -91,6 +247,32 @@ impl JsonhReaderOptions {
         self.max_depth = value;
         return self;
     }
+    /// Sets the maximum number of properties in a single object when parsing, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max properties: Some(2)
+    /// { a: 1, b: 2, c: 3 } // Error: Exceeded max properties
+    /// { a: 1, a: 2, a: 3 } // Error: Exceeded max properties
+    /// ```
+    /// 
+    /// Every property name is counted, including duplicates (which replace the earlier value).
+    /// This defends against inputs with huge numbers of small properties exhausting memory in `JsonhReader::parse_element`.
+    pub fn with_max_properties(mut self, value: Option<usize>) -> Self {
+        self.max_properties = value;
+        return self;
+    }
+    /// Sets the maximum number of items in a single array when parsing, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max items: Some(2)
+    /// [1, 2, 3] // Error: Exceeded max items
+    /// ```
+    /// 
+    /// This defends against inputs with huge numbers of small items exhausting memory in `JsonhReader::parse_element`.
+    pub fn with_max_items(mut self, value: Option<usize>) -> Self {
+        self.max_items = value;
+        return self;
+    }
     /// Enables/disables parsing unclosed inputs.
     /// 
     /// ```
@@ -104,4 +286,149 @@ impl JsonhReaderOptions {
         self.incomplete_inputs = value;
         return self;
     }
+    /// Enables/disables reading unknown versions of the specification (see `JsonhVersion::Other`).
+    /// 
+    /// If enabled, unknown versions are read on a best-effort basis using the latest known version.
+    /// If disabled, reading fails with an error.
+    /// 
+    /// This is potentially useful for reading documents written by newer tools.
+    pub fn with_unknown_versions(mut self, value: bool) -> Self {
+        self.unknown_versions = value;
+        return self;
+    }
+    /// Enables/disables deferring the decoding of escape sequences in strings until their values are used.
+    /// 
+    /// If enabled, escape sequences in quoted strings are validated but kept as written, and the token is marked with `JsonhToken::is_escaped`.
+    /// The value can be decoded with `JsonhToken::unescaped_value`.
+    /// Multi-quoted strings, quoteless strings and property names are always decoded.
+    /// 
+    /// ```
+    /// "a\tb" // Value: `a\tb`, Unescaped value: `a<tab>b`
+    /// ```
+    /// 
+    /// This is potentially useful for skipping over most of a document (e.g. with `JsonhReader::find_property_value`).
+    pub fn with_lazy_unescaping(mut self, value: bool) -> Self {
+        self.lazy_unescaping = value;
+        return self;
+    }
+    /// Sets the maximum number of bytes the reader may allocate for token values and parsed elements, or `None` for no limit.
+    /// 
+    /// The number of bytes is approximate and accumulates over every element read by the reader.
+    /// 
+    /// ```
+    /// // Max allocation: Some(8)
+    /// "a very long string" // Error: Exceeded max allocation
+    /// ```
+    /// 
+    /// Unlike limiting the size of the input, this bounds the memory used by escape-heavy or deeply nested content.
+    pub fn with_max_allocation(mut self, value: Option<usize>) -> Self {
+        self.max_allocation = value;
+        return self;
+    }
+    /// Sets the maximum number of characters to read from the source, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max chars: Some(8)
+    /// [1, 2, 3, 4, 5] // Error: Exceeded max chars
+    /// ```
+    /// 
+    /// The limit is checked against `JsonhReader::char_counter`, so the reader never consumes more of an untrusted source than this, regardless of its content.
+    pub fn with_max_chars(mut self, value: Option<u64>) -> Self {
+        self.max_chars = value;
+        return self;
+    }
+    /// Sets the maximum number of bytes in a single string, property name or comment, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max string length: Some(8)
+    /// "a very long string" // Error: Exceeded max string length
+    /// ```
+    /// 
+    /// This applies to quoted strings, quoteless strings and numbers, so the reader stops before growing a buffer unboundedly on hostile input.
+    /// Comments are limited by `max_comment_length` instead.
+    pub fn with_max_string_length(mut self, value: Option<usize>) -> Self {
+        self.max_string_length = value;
+        return self;
+    }
+    /// Sets the maximum number of bytes in a single comment, or `None` for no limit.
+    /// 
+    /// ```
+    /// // Max comment length: Some(8)
+    /// /* a very long comment */ // Error: Exceeded max comment length
+    /// ```
+    pub fn with_max_comment_length(mut self, value: Option<usize>) -> Self {
+        self.max_comment_length = value;
+        return self;
+    }
+    /// Sets the maximum total number of bytes in comments, or `None` for no limit.
+    /// 
+    /// The number of bytes accumulates over every comment read by the reader.
+    /// 
+    /// Since comments are discarded when parsing, this defends against inputs hiding large amounts of data in comments.
+    pub fn with_max_comment_bytes(mut self, value: Option<usize>) -> Self {
+        self.max_comment_bytes = value;
+        return self;
+    }
+    /// Sets the fraction of the remaining input length used to pre-size buffers, or `None` to not pre-size buffers.
+    /// 
+    /// When the length of the input is known (e.g. when reading from a string), string buffers are pre-sized to `remaining / capacity_divisor` bytes (up to 256),
+    /// and objects and arrays are pre-sized to `remaining / capacity_divisor` items (up to 16) when parsing.
+    /// 
+    /// This reduces reallocations when reading large documents with long strings or large structures, at the cost of unused capacity.
+    pub fn with_capacity_divisor(mut self, value: Option<usize>) -> Self {
+        self.capacity_divisor = value;
+        return self;
+    }
+    /// Enables/disables keeping escape sequences in strings and property names exactly as written.
+    /// 
+    /// If enabled, escape sequences in every string (including multi-quoted strings, quoteless strings and property names) are validated but never decoded,
+    /// and tokens containing escape sequences are marked with `JsonhToken::is_escaped`. The value can be decoded with `JsonhToken::unescaped_value`.
+    /// 
+    /// ```
+    /// "\u0041" // Value: `\u0041`
+    /// ```
+    /// 
+    /// This is potentially useful for transcoders and formatters that preserve the original text.
+    /// Since escape sequences are not decoded, escaped whitespace is not considered when stripping indentation from multi-quoted strings.
+    pub fn with_retain_escapes(mut self, value: bool) -> Self {
+        self.retain_escapes = value;
+        return self;
+    }
+    /// Enables/disables ignoring case when matching property names to find values.
+    /// 
+    /// ```
+    /// { "Port": 8080 } // find_path_value("port"): found
+    /// ```
+    /// 
+    /// This option only applies when finding values (e.g. with `JsonhReader::find_path_value`), not when reading or parsing elements.
+    pub fn with_ignore_property_name_case(mut self, value: bool) -> Self {
+        self.ignore_property_name_case = value;
+        return self;
+    }
+    /// Enables/disables comments.
+    /// 
+    /// ```
+    /// # comment // Error: Comments are not allowed
+    /// ```
+    /// 
+    /// Disabling comments is useful for machine-generated documents, where comments are unexpected.
+    pub fn with_allow_comments(mut self, value: bool) -> Self {
+        self.allow_comments = value;
+        return self;
+    }
+    /// Enables/disables rejecting syntax that is not valid in JSON (RFC 8259).
+    /// 
+    /// ```
+    /// { 'a': 0x10 } // Error: Single-quoted strings are not allowed in JSON
+    /// ```
+    /// 
+    /// Quoteless strings, single-quoted strings, multi-quoted strings, verbatim strings, braceless objects, trailing or missing commas
+    /// and numbers that are not valid in JSON (e.g. with underscores or base prefixes) are rejected. Comments are controlled by `allow_comments`.
+    /// 
+    /// Escape sequences other than `\" \\ \/ \b \f \n \r \t \uXXXX`, unescaped control characters in strings
+    /// and whitespace other than space, tab, line feed and carriage return are also rejected.
+    pub fn with_strict_json(mut self, value: bool) -> Self {
+        self.strict_json = value;
+        return self;
+    }
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::str::Chars;
+
+use ropey::RopeSlice;
+use ropey::iter::Chunks;
+
+use crate::JsonhSource;
+
+/// A `JsonhSource` reading from the chunks of a rope, as used by text editors (requires the `ropey` feature).
+/// 
+/// Since ropes are indexed by character, the spans of tokens are character indices in the rope.
+/// 
+/// ```
+/// let rope: Rope = Rope::from_str(text);
+/// let mut reader: JsonhReader<'_> = JsonhReader::from_source(JsonhRopeSource::new(rope.slice(..)), JsonhReaderOptions::new());
+/// ```
+pub struct JsonhRopeSource<'a> {
+    /// The remaining chunks of the rope.
+    chunks: Chunks<'a>,
+    /// The remaining characters of the current chunk.
+    chars: Chars<'a>,
+    /// The number of characters read from the rope, plus the starting position.
+    position: u64,
+    /// The number of bytes remaining in the rope.
+    remaining_bytes: usize,
+}
+
+impl<'a> JsonhRopeSource<'a> {
+    /// Constructs a source reading from a slice of a rope.
+    pub fn new(slice: RopeSlice<'a>) -> Self {
+        return Self::with_position(slice, 0);
+    }
+    /// Constructs a source reading from a slice of a rope that starts at the given character index.
+    /// 
+    /// For example, to read from character 100 with spans relative to the whole rope:
+    /// ```
+    /// JsonhRopeSource::with_position(rope.slice(100..), 100)
+    /// ```
+    pub fn with_position(slice: RopeSlice<'a>, position: u64) -> Self {
+        return Self { chunks: slice.chunks(), chars: "".chars(), position: position, remaining_bytes: slice.len_bytes() };
+    }
+
+    fn current_chars(&mut self) -> Option<&mut Chars<'a>> {
+        // Skip to next non-empty chunk
+        while self.chars.as_str().is_empty() {
+            self.chars = self.chunks.next()?.chars();
+        }
+        return Some(&mut self.chars);
+    }
+}
+
+impl JsonhSource for JsonhRopeSource<'_> {
+    fn peek(&mut self) -> Option<char> {
+        return self.current_chars()?.clone().next();
+    }
+    fn next(&mut self) -> Option<char> {
+        let next: char = self.current_chars()?.next()?;
+        self.position += 1;
+        self.remaining_bytes -= next.len_utf8();
+        return Some(next);
+    }
+    fn position(&self) -> u64 {
+        return self.position;
+    }
+    fn size_hint(&self) -> Option<usize> {
+        return Some(self.remaining_bytes);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use crate::JsonhVersion;
+
+/// Options for comparing and hashing JSONH elements by meaning rather than by formatting (see `semantic_eq` and `semantic_hash`).
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[non_exhaustive]
+pub struct JsonhSemanticOptions {
+    /// Specifies the major version of the JSONH specification to use when parsing documents.
+    pub version: JsonhVersion,
+    /// Enables/disables ignoring the order of properties in objects.
+    /// 
+    /// ```
+    /// { a: 1, b: 2 } == { b: 2, a: 1 }
+    /// ```
+    /// 
+    /// Property order is only kept in elements if the `preserve_order` feature of `serde_json` is enabled.
+    pub ignore_key_order: bool,
+}
+
+impl JsonhSemanticOptions {
+    /// Constructs a `JsonhSemanticOptions` with some default values.
+    pub fn new() -> Self {
+        return Self { version: JsonhVersion::Latest, ignore_key_order: true };
+    }
+    /// Specifies the major version of the JSONH specification to use when parsing documents.
+    pub fn with_version(mut self, value: JsonhVersion) -> Self {
+        self.version = value;
+        return self;
+    }
+    /// Enables/disables ignoring the order of properties in objects.
+    /// 
+    /// ```
+    /// { a: 1, b: 2 } == { b: 2, a: 1 }
+    /// ```
+    /// 
+    /// Property order is only kept in elements if the `preserve_order` feature of `serde_json` is enabled.
+    pub fn with_ignore_key_order(mut self, value: bool) -> Self {
+        self.ignore_key_order = value;
+        return self;
+    }
+}
+
+impl Default for JsonhSemanticOptions {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,302 @@
+use std::io::Write;
+
+use serde::Serialize;
+use serde::Serializer;
+use serde::ser::SerializeMap;
+use serde::ser::SerializeSeq;
+use serde::ser::SerializeStruct;
+use serde::ser::SerializeStructVariant;
+use serde::ser::SerializeTuple;
+use serde::ser::SerializeTupleStruct;
+use serde::ser::SerializeTupleVariant;
+use serde_json::Number;
+use serde_json::Value;
+
+use crate::JsonhWriter;
+use crate::JsonhError;
+use crate::JsonhBytesEncoding;
+use crate::radix::RADIX_NAME;
+
+/// A serde serializer writing a single JSONH element to a `JsonhWriter`.
+/// 
+/// ```
+/// let mut writer: JsonhWriter<File> = JsonhWriter::new(File::create("config.jsonh")?, JsonhWriterOptions::new());
+/// config.serialize(&mut JsonhSerializer::new(&mut writer))?;
+/// ```
+/// 
+/// Enums are externally tagged (e.g. `{ "Variant": value }`), and unit variants are written as strings.
+/// Integers serialized with `jsonh_rs::radix` are written as numbers in their radix (e.g. `0x1F00`).
+pub struct JsonhSerializer<'w, W: Write> {
+    writer: &'w mut JsonhWriter<W>,
+    bytes_encoding: JsonhBytesEncoding,
+}
+
+impl<'w, W: Write> JsonhSerializer<'w, W> {
+    /// Constructs a serializer writing the next element to the writer.
+    pub fn new(writer: &'w mut JsonhWriter<W>) -> Self {
+        return Self { writer: writer, bytes_encoding: JsonhBytesEncoding::Base64 };
+    }
+    /// Sets the encoding used to encode bytes as strings (e.g. with `jsonh_rs::bytes`).
+    /// 
+    /// ```
+    /// // Bytes encoding: Hex
+    /// [0x48, 0x69] -> "4869"
+    /// ```
+    pub fn with_bytes_encoding(mut self, value: JsonhBytesEncoding) -> Self {
+        self.bytes_encoding = value;
+        return self;
+    }
+
+    fn serialize_radix_integer(&mut self, formatted: &str) -> Result<(), JsonhError> {
+        let unsigned: &str = formatted.trim_start_matches('-');
+        let radix: u32 = match unsigned.get(..2) {
+            Some("0x") => 16,
+            Some("0b") => 2,
+            Some("0o") => 8,
+            _ => 10,
+        };
+        match crate::radix::parse::<i128>(formatted) {
+            Some(integer) => self.writer.write_integer_in_radix(integer, radix)?,
+            None => self.writer.write_integer_in_radix(crate::radix::parse::<u128>(formatted).ok_or("Integer out of range")?, radix)?,
+        }
+        return Ok(());
+    }
+    fn write_variant_name(&mut self, variant: &str) -> Result<(), JsonhError> {
+        self.writer.write_start_object()?;
+        self.writer.write_property_name(variant)?;
+        return Ok(());
+    }
+}
+
+impl<W: Write> Serializer for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, value: bool) -> Result<(), JsonhError> {
+        self.writer.write_bool(value)?;
+        return Ok(());
+    }
+    fn serialize_i8(self, value: i8) -> Result<(), JsonhError> {
+        return self.serialize_i64(value as i64);
+    }
+    fn serialize_i16(self, value: i16) -> Result<(), JsonhError> {
+        return self.serialize_i64(value as i64);
+    }
+    fn serialize_i32(self, value: i32) -> Result<(), JsonhError> {
+        return self.serialize_i64(value as i64);
+    }
+    fn serialize_i64(self, value: i64) -> Result<(), JsonhError> {
+        self.writer.write_number(&Number::from(value))?;
+        return Ok(());
+    }
+    fn serialize_i128(self, value: i128) -> Result<(), JsonhError> {
+        self.writer.write_integer(value)?;
+        return Ok(());
+    }
+    fn serialize_u8(self, value: u8) -> Result<(), JsonhError> {
+        return self.serialize_u64(value as u64);
+    }
+    fn serialize_u16(self, value: u16) -> Result<(), JsonhError> {
+        return self.serialize_u64(value as u64);
+    }
+    fn serialize_u32(self, value: u32) -> Result<(), JsonhError> {
+        return self.serialize_u64(value as u64);
+    }
+    fn serialize_u64(self, value: u64) -> Result<(), JsonhError> {
+        self.writer.write_number(&Number::from(value))?;
+        return Ok(());
+    }
+    fn serialize_u128(self, value: u128) -> Result<(), JsonhError> {
+        self.writer.write_integer(value)?;
+        return Ok(());
+    }
+    fn serialize_f32(self, value: f32) -> Result<(), JsonhError> {
+        return self.serialize_f64(value as f64);
+    }
+    fn serialize_f64(self, value: f64) -> Result<(), JsonhError> {
+        let number: Number = Number::from_f64(value).ok_or("Infinity and NaN are not supported")?;
+        self.writer.write_number(&number)?;
+        return Ok(());
+    }
+    fn serialize_char(self, value: char) -> Result<(), JsonhError> {
+        return self.serialize_str(&value.to_string());
+    }
+    fn serialize_str(self, value: &str) -> Result<(), JsonhError> {
+        self.writer.write_string(value)?;
+        return Ok(());
+    }
+    fn serialize_bytes(self, value: &[u8]) -> Result<(), JsonhError> {
+        let encoded: String = self.bytes_encoding.encode(value);
+        return self.serialize_str(&encoded);
+    }
+    fn serialize_none(self) -> Result<(), JsonhError> {
+        return self.serialize_unit();
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), JsonhError> {
+        return value.serialize(self);
+    }
+    fn serialize_unit(self) -> Result<(), JsonhError> {
+        self.writer.write_null()?;
+        return Ok(());
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), JsonhError> {
+        return self.serialize_unit();
+    }
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<(), JsonhError> {
+        return self.serialize_str(variant);
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, name: &'static str, value: &T) -> Result<(), JsonhError> {
+        // Radix integer
+        if name == RADIX_NAME {
+            let Ok(Value::String(formatted)) = serde_json::to_value(value) else {
+                return Err(JsonhError::from("Expected radix integer"));
+            };
+            return self.serialize_radix_integer(&formatted);
+        }
+        return value.serialize(self);
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<(), JsonhError> {
+        self.write_variant_name(variant)?;
+        value.serialize(&mut *self)?;
+        self.writer.write_end_object()?;
+        return Ok(());
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, JsonhError> {
+        self.writer.write_start_array()?;
+        return Ok(self);
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Self, JsonhError> {
+        return self.serialize_seq(Some(len));
+    }
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self, JsonhError> {
+        return self.serialize_seq(Some(len));
+    }
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Self, JsonhError> {
+        self.write_variant_name(variant)?;
+        self.writer.write_start_array()?;
+        return Ok(self);
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, JsonhError> {
+        self.writer.write_start_object()?;
+        return Ok(self);
+    }
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self, JsonhError> {
+        return self.serialize_map(Some(len));
+    }
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, _len: usize) -> Result<Self, JsonhError> {
+        self.write_variant_name(variant)?;
+        self.writer.write_start_object()?;
+        return Ok(self);
+    }
+}
+
+impl<W: Write> SerializeSeq for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonhError> {
+        return value.serialize(&mut **self);
+    }
+    fn end(self) -> Result<(), JsonhError> {
+        self.writer.write_end_array()?;
+        return Ok(());
+    }
+}
+
+impl<W: Write> SerializeTuple for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonhError> {
+        return value.serialize(&mut **self);
+    }
+    fn end(self) -> Result<(), JsonhError> {
+        return SerializeSeq::end(self);
+    }
+}
+
+impl<W: Write> SerializeTupleStruct for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonhError> {
+        return value.serialize(&mut **self);
+    }
+    fn end(self) -> Result<(), JsonhError> {
+        return SerializeSeq::end(self);
+    }
+}
+
+impl<W: Write> SerializeTupleVariant for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonhError> {
+        return value.serialize(&mut **self);
+    }
+    fn end(self) -> Result<(), JsonhError> {
+        self.writer.write_end_array()?;
+        self.writer.write_end_object()?;
+        return Ok(());
+    }
+}
+
+impl<W: Write> SerializeMap for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), JsonhError> {
+        // Property names can be strings, numbers or booleans
+        let property_name: String = match serde_json::to_value(key) {
+            Ok(Value::String(string)) => string,
+            Ok(Value::Number(number)) => number.to_string(),
+            Ok(Value::Bool(boolean)) => boolean.to_string(),
+            _ => return Err(JsonhError::from("Property name must be a string")),
+        };
+        self.writer.write_property_name(&property_name)?;
+        return Ok(());
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), JsonhError> {
+        return value.serialize(&mut **self);
+    }
+    fn end(self) -> Result<(), JsonhError> {
+        self.writer.write_end_object()?;
+        return Ok(());
+    }
+}
+
+impl<W: Write> SerializeStruct for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), JsonhError> {
+        self.writer.write_property_name(key)?;
+        return value.serialize(&mut **self);
+    }
+    fn end(self) -> Result<(), JsonhError> {
+        return SerializeMap::end(self);
+    }
+}
+
+impl<W: Write> SerializeStructVariant for &mut JsonhSerializer<'_, W> {
+    type Ok = ();
+    type Error = JsonhError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), JsonhError> {
+        self.writer.write_property_name(key)?;
+        return value.serialize(&mut **self);
+    }
+    fn end(self) -> Result<(), JsonhError> {
+        self.writer.write_end_object()?;
+        self.writer.write_end_object()?;
+        return Ok(());
+    }
+}
\ No newline at end of file
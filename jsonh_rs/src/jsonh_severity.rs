@@ -0,0 +1,11 @@
+/// The severity of a `JsonhDiagnostic`.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum JsonhSeverity {
+    /// The document is invalid or will be misinterpreted.
+    Error = 0,
+    /// The document is valid but likely contains a mistake.
+    Warning = 1,
+    /// The document is valid but could be written more clearly.
+    Info = 2,
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::sync::Arc;
+
+/// A source of characters for a `JsonhReader`.
+/// 
+/// Implement this to read from custom sources (such as decompressors or the rope structures of text editors) without converting them to `Chars` first.
+/// 
+/// For example, a source that counts the characters read:
+/// ```
+/// struct CountingSource<S: JsonhSource> {
+///     source: S,
+///     count: u64,
+/// }
+/// 
+/// impl<S: JsonhSource> JsonhSource for CountingSource<S> {
+///     fn peek(&mut self) -> Option<char> {
+///         return self.source.peek();
+///     }
+///     fn next(&mut self) -> Option<char> {
+///         self.count += 1;
+///         return self.source.next();
+///     }
+///     fn position(&self) -> u64 {
+///         return self.source.position();
+///     }
+/// }
+/// ```
+pub trait JsonhSource {
+    /// Returns the next character without consuming it, or `None` at the end of the source.
+    fn peek(&mut self) -> Option<char>;
+    /// Consumes and returns the next character, or `None` at the end of the source.
+    fn next(&mut self) -> Option<char>;
+    /// Returns the number of characters before the next character.
+    /// 
+    /// The reader starts counting from this position, so sources starting partway through a document can report their offset.
+    fn position(&self) -> u64;
+    /// Returns an upper bound on the number of bytes remaining in the source, or `None` if unknown.
+    /// 
+    /// The reader uses this to pre-size buffers (see `JsonhReaderOptions::capacity_divisor`).
+    fn size_hint(&self) -> Option<usize> {
+        return None;
+    }
+    /// Returns the error that ended the source early (such as invalid UTF-8), or `None` if the source has not failed.
+    /// 
+    /// The reader returns this error instead of an unexpected end of input.
+    fn error(&self) -> Option<&'static str> {
+        return None;
+    }
+    /// Returns the error that caused `error` (such as an I/O error), or `None` if there is none.
+    /// 
+    /// The reader includes this as the cause of the error it returns (see `JsonhError::cause`).
+    fn cause(&self) -> Option<Arc<dyn Error + Send + Sync>> {
+        return None;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+use crate::JsonhSourceMapping;
+
+/// Relates ranges of text produced from a JSONH document (such as JSON) to ranges of the JSONH document.
+/// 
+/// ```
+/// let (json, source_map): (String, JsonhSourceMap) = reader.parse_json_with_source_map(false, None)?;
+/// // Error at byte 42 of JSON
+/// if let Some(mapping) = source_map.find(42) {
+///     println!("Error at character {} of JSONH", mapping.input_start);
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct JsonhSourceMap {
+    /// The mappings of every element and property name, in the order they were completed.
+    pub mappings: Vec<JsonhSourceMapping>,
+}
+
+impl JsonhSourceMap {
+    /// Constructs an empty source map.
+    pub fn new() -> Self {
+        return Self { mappings: Vec::new() };
+    }
+    /// Returns the innermost mapping containing the given byte offset in the output.
+    pub fn find(&self, output_offset: usize) -> Option<&JsonhSourceMapping> {
+        return self.mappings.iter()
+            .filter(|mapping| output_offset >= mapping.output_start && output_offset < mapping.output_end)
+            .min_by_key(|mapping| mapping.output_end - mapping.output_start);
+    }
+}
\ No newline at end of file
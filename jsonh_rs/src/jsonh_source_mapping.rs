@@ -0,0 +1,19 @@
+/// A range of output text and the range of the JSONH document it was produced from.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct JsonhSourceMapping {
+    /// The number of bytes in the output before the start of the range.
+    pub output_start: usize,
+    /// The number of bytes in the output before the end of the range.
+    pub output_end: usize,
+    /// The number of characters in the input before the start of the range.
+    pub input_start: u64,
+    /// The number of characters in the input before the end of the range.
+    pub input_end: u64,
+}
+
+impl JsonhSourceMapping {
+    /// Constructs a mapping between the given ranges.
+    pub fn new(output_start: usize, output_end: usize, input_start: u64, input_end: u64) -> Self {
+        return Self { output_start: output_start, output_end: output_end, input_start: input_start, input_end: input_end };
+    }
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::JsonhReader;
+use crate::JsonhDeserializer;
+use crate::JsonTokenType;
+use crate::JsonhPathSegment;
+use crate::JsonhError;
+use crate::JsonhBytesEncoding;
+
+/// An iterator deserializing the items of a root array one at a time, without reading the whole array into memory.
+/// 
+/// ```
+/// let mut reader: JsonhReader = JsonhReader::from_read(File::open("logs.jsonh")?, JsonhReaderOptions::new());
+/// for entry_result in JsonhStreamDeserializer::<LogEntry>::new(&mut reader) {
+///     let entry: LogEntry = entry_result?;
+/// }
+/// ```
+/// 
+/// The iterator ends after the end of the array or after the first error.
+pub struct JsonhStreamDeserializer<'r, T: DeserializeOwned> {
+    deserializer: JsonhDeserializer<'r>,
+    is_started: bool,
+    is_finished: bool,
+    index: usize,
+    item_type: PhantomData<T>,
+}
+
+impl<'r, T: DeserializeOwned> JsonhStreamDeserializer<'r, T> {
+    /// Constructs a stream deserializer reading the items of the next element from the reader, which must be an array.
+    pub fn new(reader: &'r mut JsonhReader<'_>) -> Self {
+        return Self { deserializer: JsonhDeserializer::new(reader), is_started: false, is_finished: false, index: 0, item_type: PhantomData };
+    }
+    /// Sets the encoding used to decode strings deserialized as bytes (e.g. with `jsonh_rs::bytes`).
+    pub fn with_bytes_encoding(mut self, value: JsonhBytesEncoding) -> Self {
+        self.deserializer = self.deserializer.with_bytes_encoding(value);
+        return self;
+    }
+
+    fn next_item(&mut self) -> Result<Option<T>, JsonhError> {
+        // Start of array
+        if !self.is_started {
+            self.is_started = true;
+            if self.deserializer.next_token()?.json_type != JsonTokenType::StartArray {
+                return Err(JsonhError::from("Expected array"));
+            }
+        }
+
+        // End of array
+        if self.deserializer.peek_type()? == Some(JsonTokenType::EndArray) {
+            self.deserializer.next_token()?;
+            return Ok(None);
+        }
+
+        // Item
+        self.index += 1;
+        return self.deserializer.deserialize_at(JsonhPathSegment::Index(self.index - 1), |deserializer| T::deserialize(deserializer)).map(Some);
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for JsonhStreamDeserializer<'_, T> {
+    type Item = Result<T, JsonhError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_finished {
+            return None;
+        }
+        let item_result: Result<Option<T>, JsonhError> = self.next_item();
+        if !matches!(item_result, Ok(Some(_))) {
+            self.is_finished = true;
+        }
+        return item_result.transpose();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+/// The syntax used to write a string.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhStringStyle {
+    /// A double-quoted string.
+    /// 
+    /// Example: `"value"`
+    Double = 0,
+    /// A single-quoted string.
+    /// 
+    /// Example: `'value'`
+    Single = 1,
+    /// A quoteless string.
+    /// 
+    /// Example: `value`
+    Quoteless = 2,
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::JsonhSource;
+
+/// A `JsonhSource` that copies every character consumed from another source to a sink.
+/// 
+/// This is potentially useful for archiving the exact text of a payload while parsing it.
+/// 
+/// ```
+/// let mut archive: String = String::new();
+/// let source: JsonhTeeSource<_, _> = JsonhTeeSource::new(JsonhCharSource::new(payload.chars()), &mut archive);
+/// let element: Value = JsonhReader::parse_element_from_source(source, JsonhReaderOptions::new())?;
+/// // archive now contains the text read by the reader
+/// ```
+/// 
+/// Only consumed characters are copied, so characters after the end of the element are not copied unless the reader reads them.
+/// Errors returned by the sink are ignored.
+pub struct JsonhTeeSource<S: JsonhSource, W: fmt::Write> {
+    /// The source to read characters from.
+    pub source: S,
+    /// The sink to copy consumed characters to.
+    pub sink: W,
+}
+
+impl<S: JsonhSource, W: fmt::Write> JsonhTeeSource<S, W> {
+    /// Constructs a source that copies every character consumed from `source` to `sink`.
+    pub fn new(source: S, sink: W) -> Self {
+        return Self { source: source, sink: sink };
+    }
+}
+
+impl<S: JsonhSource, W: fmt::Write> JsonhSource for JsonhTeeSource<S, W> {
+    fn peek(&mut self) -> Option<char> {
+        return self.source.peek();
+    }
+    fn next(&mut self) -> Option<char> {
+        let next: Option<char> = self.source.next();
+        if let Some(next) = next {
+            let _ = self.sink.write_char(next);
+        }
+        return next;
+    }
+    fn position(&self) -> u64 {
+        return self.source.position();
+    }
+    fn size_hint(&self) -> Option<usize> {
+        return self.source.size_hint();
+    }
+    fn error(&self) -> Option<&'static str> {
+        return self.source.error();
+    }
+    fn cause(&self) -> Option<Arc<dyn Error + Send + Sync>> {
+        return self.source.cause();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,68 @@
+/// A replacement of a range of characters in a JSONH document.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonhTextEdit {
+    /// The number of characters in the source before the start of the range.
+    pub start: u64,
+    /// The number of characters in the source before the end of the range.
+    pub end: u64,
+    /// The text to replace the range with.
+    pub replacement: String,
+}
+
+impl JsonhTextEdit {
+    /// Constructs an edit replacing a range of characters.
+    pub fn new(start: u64, end: u64, replacement: String) -> Self {
+        return Self { start: start, end: end, replacement: replacement };
+    }
+    /// Constructs an edit deleting a range of characters.
+    pub fn delete(start: u64, end: u64) -> Self {
+        return Self::new(start, end, String::new());
+    }
+    /// Constructs an edit inserting text before a character.
+    pub fn insert(position: u64, text: String) -> Self {
+        return Self::new(position, position, text);
+    }
+    /// Returns whether the ranges of the two edits overlap.
+    /// 
+    /// Two insertions at the same position are considered overlapping since their order is ambiguous.
+    pub fn overlaps(&self, other: &JsonhTextEdit) -> bool {
+        if self.start == other.start {
+            return true;
+        }
+        return self.start < other.end && other.start < self.end;
+    }
+
+    /// Applies the edits to the source text.
+    /// 
+    /// Edits overlapping an earlier edit (by start position) are skipped.
+    pub fn apply_all(source: &str, edits: &[JsonhTextEdit]) -> String {
+        let chars: Vec<char> = source.chars().collect();
+
+        let mut sorted_edits: Vec<&JsonhTextEdit> = edits.iter().collect();
+        sorted_edits.sort_by_key(|edit| (edit.start, edit.end));
+
+        let mut result: String = String::with_capacity(source.len());
+        let mut position: usize = 0;
+        let mut previous_edit: Option<&JsonhTextEdit> = None;
+        for edit in sorted_edits {
+            // Skip overlapping edit
+            if previous_edit.is_some_and(|previous_edit| previous_edit.overlaps(edit)) {
+                continue;
+            }
+
+            let start: usize = (edit.start as usize).clamp(position, chars.len());
+            let end: usize = (edit.end as usize).clamp(start, chars.len());
+
+            // Copy characters before edit
+            result.extend(&chars[position..start]);
+            // Replace range
+            result += &edit.replacement;
+
+            position = end;
+            previous_edit = Some(edit);
+        }
+        // Copy characters after last edit
+        result.extend(&chars[position..]);
+        return result;
+    }
+}
\ No newline at end of file
@@ -1,4 +1,11 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+
 use crate::JsonTokenType;
+use crate::JsonhReader;
+use crate::JsonhFormatter;
+use crate::JsonhCommentStyle;
 
 /// A single JSONH token with a `JsonTokenType`.
 #[derive(Clone, PartialEq, Debug)]
@@ -7,21 +14,116 @@ pub struct JsonhToken {
     pub json_type: JsonTokenType,
     /// The value of the token, or an empty string.
     pub value: String,
+    /// The number of characters read before the start of the token.
+    pub start: u64,
+    /// The number of characters read before the end of the token.
+    /// 
+    /// Tokens that don't appear in the source (e.g. the braces of a braceless object) have the same start and end.
+    pub end: u64,
+    /// Whether the value contains escape sequences that have not been decoded yet (see `JsonhReaderOptions::lazy_unescaping` and `JsonhReaderOptions::retain_escapes`).
+    /// 
+    /// Use `unescaped_value` to get the decoded value.
+    pub is_escaped: bool,
 }
 
 impl JsonhToken {
     /// Constructs a single JSONH token.
     pub fn new(json_type: JsonTokenType, value: String) -> Self {
-        return Self { json_type: json_type, value: value };
+        return Self { json_type: json_type, value: value, start: 0, end: 0, is_escaped: false };
     }
     /// Constructs a single JSONH token with an empty value.
     pub fn new_empty(json_type: JsonTokenType) -> Self {
         return Self::new(json_type, String::new());
     }
+    /// Sets the range of characters in the source that the JSONH token was read from.
+    pub fn with_span(mut self, start: u64, end: u64) -> Self {
+        self.start = start;
+        self.end = end;
+        return self;
+    }
+    /// Sets whether the value contains escape sequences that have not been decoded yet.
+    pub fn with_escaped(mut self, is_escaped: bool) -> Self {
+        self.is_escaped = is_escaped;
+        return self;
+    }
+    /// Returns the value of the token, decoding any escape sequences that have not been decoded yet.
+    /// 
+    /// The value is only copied if it contains escape sequences.
+    pub fn unescaped_value(&self) -> Result<Cow<'_, str>, &'static str> {
+        if !self.is_escaped {
+            return Ok(Cow::Borrowed(&self.value));
+        }
+        return Ok(Cow::Owned(JsonhReader::unescape(&self.value)?));
+    }
+    /// Returns the line and column numbers (starting from 1) of the start of the token in the source it was read from.
+    /// 
+    /// ```
+    /// let (line, column): (u64, u64) = token.location(source);
+    /// ```
+    pub fn location(&self, source: &str) -> (u64, u64) {
+        return crate::spanned::char_location(source, self.start);
+    }
+    /// Returns the line and column numbers (starting from 1) of the end of the token in the source it was read from.
+    pub fn end_location(&self, source: &str) -> (u64, u64) {
+        return crate::spanned::char_location(source, self.end);
+    }
+    /// Returns the range of bytes in the source that the token was read from, for slicing the source.
+    /// 
+    /// ```
+    /// let raw: &str = &source[token.byte_span(source)]; // "0x10"
+    /// ```
+    pub fn byte_span(&self, source: &str) -> Range<usize> {
+        return crate::spanned::char_offset(source, self.start)..crate::spanned::char_offset(source, self.end);
+    }
     /// Returns whether the JSONH token is a teapot.
     /// 
     /// Since JSONH tokens cannot currently be teapots, this always returns `false`.
     pub fn is_a_teapot(&self) -> bool {
         return false;
     }
+}
+
+impl fmt::Display for JsonhToken {
+    /// Formats the token as JSONH text, quoting and escaping strings and property names with double quotes.
+    /// 
+    /// ```
+    /// "a": // Property name
+    /// /* b */ // Comment
+    /// "c\n" // String
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.json_type {
+            JsonTokenType::None => return Ok(()),
+            JsonTokenType::StartObject => return f.write_str("{"),
+            JsonTokenType::EndObject => return f.write_str("}"),
+            JsonTokenType::StartArray => return f.write_str("["),
+            JsonTokenType::EndArray => return f.write_str("]"),
+            JsonTokenType::PropertyName => {
+                let value: Cow<'_, str> = self.unescaped_value().unwrap_or(Cow::Borrowed(&self.value));
+                return write!(f, "{}:", JsonhFormatter::quote(&value, '"'));
+            },
+            JsonTokenType::String => {
+                let value: Cow<'_, str> = self.unescaped_value().unwrap_or(Cow::Borrowed(&self.value));
+                return f.write_str(&JsonhFormatter::quote(&value, '"'));
+            },
+            JsonTokenType::Number => return f.write_str(&self.value),
+            JsonTokenType::True => return f.write_str("true"),
+            JsonTokenType::False => return f.write_str("false"),
+            JsonTokenType::Null => return f.write_str("null"),
+            JsonTokenType::Comment => {
+                // Block comment
+                if let Some(comment) = JsonhFormatter::convert_comment(&self.value, JsonhCommentStyle::Block, false) {
+                    return f.write_str(&comment);
+                }
+                // Nestable block comment (with more `=` than any `*=...=/` in the comment)
+                let mut nest_counter: usize = 1;
+                for (index, _) in self.value.match_indices('*') {
+                    let equals_counter: usize = self.value[(index + 1)..].chars().take_while(|next| *next == '=').count();
+                    nest_counter = nest_counter.max(equals_counter + 1);
+                }
+                let nest: String = "=".repeat(nest_counter);
+                return write!(f, "/{nest}*{}*{nest}/", self.value);
+            },
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+use crate::JsonhToken;
+use crate::JsonhError;
+
+/// An iterator over the tokens read by a `JsonhReader` (see `JsonhReader::read_element`).
+/// 
+/// The iterator borrows the reader, so it can be stored alongside other state (e.g. with `Peekable`) while tokens are read.
+/// 
+/// ```
+/// let mut tokens: Peekable<JsonhTokenIter<'_>> = reader.read_element().peekable();
+/// while let Some(token) = tokens.next() {
+///     // ...
+/// }
+/// ```
+/// 
+/// Errors include the position, line and column the reader stopped at (see `JsonhReader::locate_error`).
+/// If the iterator is dropped before the end, the rest of the tokens are abandoned.
+pub struct JsonhTokenIter<'r> {
+    /// The reader to read tokens from.
+    reader: &'r mut dyn TokenReader,
+}
+
+impl<'r> JsonhTokenIter<'r> {
+    pub(crate) fn new(reader: &'r mut dyn TokenReader) -> Self {
+        return Self { reader: reader };
+    }
+    /// Returns an iterator over the rest of the tokens with error messages that are not located.
+    pub(crate) fn raw(self) -> RawTokenIter<'r> {
+        return RawTokenIter { tokens: self };
+    }
+}
+
+impl Iterator for JsonhTokenIter<'_> {
+    type Item = Result<JsonhToken, JsonhError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token_result: Result<JsonhToken, &'static str> = self.reader.next_token()?;
+        return Some(token_result.map_err(|error| self.reader.locate_error(error)));
+    }
+}
+
+impl Drop for JsonhTokenIter<'_> {
+    fn drop(&mut self) {
+        self.reader.cancel_tokens();
+    }
+}
+
+/// An iterator over the tokens read by a `JsonhReader` with error messages that are not located (see `JsonhTokenIter::raw`).
+pub(crate) struct RawTokenIter<'r> {
+    tokens: JsonhTokenIter<'r>,
+}
+
+impl Iterator for RawTokenIter<'_> {
+    type Item = Result<JsonhToken, &'static str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        return self.tokens.reader.next_token();
+    }
+}
+
+/// A reader that reads tokens one at a time, regardless of its source.
+pub(crate) trait TokenReader {
+    /// Reads the next token, or returns `None` at the end of the tokens.
+    fn next_token(&mut self) -> Option<Result<JsonhToken, &'static str>>;
+    /// Converts an error message to an error with the position the reader stopped at.
+    fn locate_error(&self, message: &'static str) -> JsonhError;
+    /// Abandons the rest of the tokens.
+    fn cancel_tokens(&mut self);
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+/// The type an element is coerced to when parsed (see `JsonhReader::with_type_hint`).
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum JsonhTypeHint {
+    /// A string. Numbers and named literals are kept as written.
+    /// 
+    /// Example: `1.20` -> `"1.20"`
+    String = 0,
+    /// A number. Strings are parsed as numbers.
+    /// 
+    /// Example: `"0x1F90"` -> `8080`
+    Number = 1,
+    /// A boolean. The strings `true` and `false` are parsed as booleans.
+    /// 
+    /// Example: `"true"` -> `true`
+    Bool = 2,
+}
\ No newline at end of file
@@ -1,6 +1,9 @@
+use std::fmt;
+use std::str::FromStr;
+
 /// The major versions of the JSONH specification.
 #[repr(u32)]
-#[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Debug)]
 pub enum JsonhVersion {
     /// Indicates that the latest version should be used (currently `V2`).
     Latest = 0,
@@ -8,4 +11,80 @@ pub enum JsonhVersion {
     V1 = 1,
     /// Version 2 of the specification, released 2025/11/19.
     V2 = 2,
+    /// A version of the specification not known to this crate, such as a version released after it.
+    /// 
+    /// Unknown versions are rejected unless `JsonhReaderOptions::unknown_versions` is enabled, in which case the latest known version is used.
+    Other(u32) = 3,
+}
+
+impl JsonhVersion {
+    /// The latest version of the specification known to this crate.
+    pub const LATEST_KNOWN: JsonhVersion = JsonhVersion::V2;
+
+    /// Returns the version with the given major version number (e.g. `V2` for `2`).
+    pub fn from_number(number: u32) -> Self {
+        return match number {
+            1 => JsonhVersion::V1,
+            2 => JsonhVersion::V2,
+            _ => JsonhVersion::Other(number),
+        };
+    }
+    /// Returns the major version number (e.g. `2` for `V2`).
+    /// 
+    /// `Latest` returns the number of the latest known version.
+    pub fn number(self) -> u32 {
+        return match self {
+            JsonhVersion::Latest => JsonhVersion::LATEST_KNOWN.number(),
+            JsonhVersion::V1 => 1,
+            JsonhVersion::V2 => 2,
+            JsonhVersion::Other(number) => number,
+        };
+    }
+    /// Returns the concrete version for `Latest` (the latest known version), or the version itself otherwise.
+    /// 
+    /// Versions should be resolved before comparing them, since `Latest` is ordered before every other version.
+    pub fn resolved(self) -> Self {
+        if self == JsonhVersion::Latest {
+            return JsonhVersion::LATEST_KNOWN;
+        }
+        return self;
+    }
+    /// Returns whether the version is known to this crate.
+    pub fn is_known(self) -> bool {
+        return !matches!(self, JsonhVersion::Other(_));
+    }
+}
+
+impl fmt::Display for JsonhVersion {
+    /// Writes the version as `latest` or `v` followed by its number (e.g. `v2`).
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == JsonhVersion::Latest {
+            return formatter.write_str("latest");
+        }
+        return write!(formatter, "v{}", self.number());
+    }
+}
+
+impl FromStr for JsonhVersion {
+    type Err = &'static str;
+
+    /// Parses a version from `latest`, a number (e.g. `2`) or a number preceded by `v` (e.g. `v2`), ignoring case.
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let source: String = source.trim().to_ascii_lowercase();
+
+        // Latest
+        if source == "latest" {
+            return Ok(JsonhVersion::Latest);
+        }
+
+        // Number
+        let digits: &str = source.strip_prefix('v').unwrap_or(&source);
+        if digits.is_empty() || !digits.chars().all(|next| next.is_ascii_digit()) {
+            return Err("Invalid JSONH version");
+        }
+        return match digits.parse::<u32>() {
+            Ok(number) => Ok(JsonhVersion::from_number(number)),
+            Err(_) => Err("Invalid JSONH version"),
+        };
+    }
 }
\ No newline at end of file
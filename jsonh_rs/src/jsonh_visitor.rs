@@ -0,0 +1,64 @@
+use std::ops::ControlFlow;
+
+use crate::JsonhToken;
+
+/// A handler for the tokens of a JSONH element, called by `JsonhReader::drive` as each token is read.
+/// 
+/// Every method does nothing by default, so only the tokens of interest need handling.
+/// Returning `ControlFlow::Break` stops reading, leaving the reader after the token.
+/// 
+/// For example, to sum the numbers in an element:
+/// ```
+/// struct SumVisitor {
+///     sum: f64,
+/// }
+/// 
+/// impl JsonhVisitor for SumVisitor {
+///     fn on_number(&mut self, value: &str, _token: &JsonhToken) -> ControlFlow<()> {
+///         self.sum += JsonhNumberParser::parse(value.to_string()).unwrap_or(0.0);
+///         return ControlFlow::Continue(());
+///     }
+/// }
+/// ```
+pub trait JsonhVisitor {
+    /// Called at the start of an object (including braceless objects).
+    fn on_start_object(&mut self, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called at the end of an object.
+    fn on_end_object(&mut self, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called at the start of an array.
+    fn on_start_array(&mut self, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called at the end of an array.
+    fn on_end_array(&mut self, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called with the name of a property in an object, before its value.
+    fn on_property_name(&mut self, _name: &str, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called with the value of a string.
+    fn on_string(&mut self, _value: &str, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called with a number as written (e.g. `0x1F`), which can be parsed with `JsonhNumberParser`.
+    fn on_number(&mut self, _value: &str, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called with the value of a boolean.
+    fn on_bool(&mut self, _value: bool, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called for a null.
+    fn on_null(&mut self, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+    /// Called with the text of a comment.
+    fn on_comment(&mut self, _value: &str, _token: &JsonhToken) -> ControlFlow<()> {
+        return ControlFlow::Continue(());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,546 @@
+use std::cmp::Ordering;
+use std::io;
+use std::io::Write;
+
+use serde_json::Number;
+use serde_json::Value;
+
+use crate::JsonhFormatter;
+use crate::JsonhCommentStyle;
+use crate::JsonhEscapeStyle;
+use crate::JsonhWriterOptions;
+use crate::JsonhPath;
+use crate::JsonhPathSegment;
+use crate::radix::RadixInteger;
+
+/// Writes JSONH elements as UTF-8 text to a byte writer, such as a file or a `Vec<u8>`.
+/// 
+/// ```
+/// let jsonh: String = JsonhWriter::write_to_string(&serde_json::json!({ "a": [1, 2] }), JsonhWriterOptions::new());
+/// ```
+/// ```
+/// {
+///     "a": [
+///         1,
+///         2
+///     ]
+/// }
+/// ```
+/// 
+/// Elements can also be written token by token, without building a `Value` first:
+/// ```
+/// let mut writer: JsonhWriter<File> = JsonhWriter::new(File::create("large.jsonh")?, JsonhWriterOptions::new());
+/// writer.write_start_array()?;
+/// for row in rows {
+///     writer.write_number(&Number::from(row))?;
+/// }
+/// writer.write_end_array()?;
+/// ```
+pub struct JsonhWriter<W: Write> {
+    /// The byte writer to write UTF-8 text to.
+    pub writer: W,
+    /// The options to use when writing JSONH.
+    pub options: JsonhWriterOptions,
+    /// The path from the root element to the element currently being written.
+    pub path: JsonhPath,
+    /// The radixes that integers at each path are written in (see `with_radix_hint`).
+    pub radix_hints: Vec<(JsonhPath, u32)>,
+    /// The error returned by `writer`, if writing failed.
+    pub error: Option<io::Error>,
+    /// The objects and arrays currently being written.
+    structures: Vec<WriterStructure>,
+    /// Whether a property name has been written without its value.
+    is_after_property_name: bool,
+    /// Whether the root element has been started.
+    has_root: bool,
+    /// Whether any text has been written.
+    has_output: bool,
+}
+
+impl<W: Write> JsonhWriter<W> {
+    /// Constructs a writer writing UTF-8 text to a byte writer.
+    pub fn new(writer: W, options: JsonhWriterOptions) -> Self {
+        return Self { writer: writer, options: options, path: JsonhPath::new(), radix_hints: Vec::new(), error: None, structures: Vec::new(), is_after_property_name: false, has_root: false, has_output: false };
+    }
+    /// Registers a radix (2, 8, 10 or 16) that integers at the given path (formatted like `a.b[2].c`) are written in.
+    /// 
+    /// For example, to write `address` in hexadecimal and `flags` in binary:
+    /// ```
+    /// writer.with_radix_hint("address", 16).with_radix_hint("flags", 2)
+    /// ```
+    /// ```
+    /// {
+    ///     "address": 0x1F00,
+    ///     "flags": 0b1010
+    /// }
+    /// ```
+    /// 
+    /// Numbers with a fractional part are always written in decimal. Paths that cannot be parsed (see `JsonhPath::from_str`) are ignored.
+    pub fn with_radix_hint(mut self, path: &str, radix: u32) -> Self {
+        if let Ok(path) = path.parse() {
+            self.radix_hints.push((path, radix));
+        }
+        return self;
+    }
+
+    /// Writes a single element, as the root element, an item in an array, or the value of a property.
+    /// 
+    /// If `JsonhWriterOptions::sort_keys` is enabled, the properties of each object are sorted alphabetically by name.
+    /// 
+    /// Errors if the byte writer fails or the element is not expected.
+    pub fn write_value(&mut self, value: &Value) -> Result<(), &'static str> {
+        if self.options.sort_keys {
+            return self.write_element(value, &mut Some(&mut |name1: &str, name2: &str| name1.cmp(name2)));
+        }
+        return self.write_element(value, &mut None);
+    }
+    /// Writes a single element, sorting the properties of each object by name with the given comparator.
+    /// 
+    /// Properties that compare equal keep their order.
+    /// 
+    /// ```
+    /// // Sort by length
+    /// writer.write_value_sorted_by(&element, |name1, name2| name1.len().cmp(&name2.len()))?;
+    /// ```
+    pub fn write_value_sorted_by(&mut self, value: &Value, mut compare: impl FnMut(&str, &str) -> Ordering) -> Result<(), &'static str> {
+        return self.write_element(value, &mut Some(&mut compare));
+    }
+    /// Writes the start of an object (`{`).
+    /// 
+    /// If `JsonhWriterOptions::braceless_root` is enabled and the object is the root element, its braces are omitted unless it is empty.
+    pub fn write_start_object(&mut self) -> Result<(), &'static str> {
+        let is_braceless: bool = self.options.braceless_root && !self.has_root;
+        self.start_element()?;
+        self.structures.push(WriterStructure { is_array: false, is_braceless: is_braceless, item_count: 0, has_comments: false, has_separator: false });
+        if !is_braceless {
+            self.write_raw("{")?;
+        }
+        return Ok(());
+    }
+    /// Writes the end of an object (`}`).
+    pub fn write_end_object(&mut self) -> Result<(), &'static str> {
+        if self.is_after_property_name {
+            return Err("Expected property value before end of object");
+        }
+        return self.end_structure(false);
+    }
+    /// Writes the start of an array (`[`).
+    pub fn write_start_array(&mut self) -> Result<(), &'static str> {
+        self.start_element()?;
+        self.structures.push(WriterStructure { is_array: true, is_braceless: false, item_count: 0, has_comments: false, has_separator: false });
+        return self.write_raw("[");
+    }
+    /// Writes the end of an array (`]`).
+    pub fn write_end_array(&mut self) -> Result<(), &'static str> {
+        return self.end_structure(true);
+    }
+    /// Writes the name of a property in the current object, which must be followed by its value.
+    pub fn write_property_name(&mut self, property_name: &str) -> Result<(), &'static str> {
+        match self.structures.last() {
+            Some(WriterStructure { is_array: false, .. }) if !self.is_after_property_name => {},
+            Some(WriterStructure { is_array: false, .. }) => return Err("Expected property value, got property name"),
+            Some(WriterStructure { is_array: true, .. }) => return Err("Unexpected property name in array"),
+            None => return Err("Unexpected property name outside of object"),
+        }
+        self.start_item()?;
+        self.path.push(JsonhPathSegment::Property(property_name.to_string()));
+        self.write_string_token(property_name, true)?;
+        self.write_raw(if self.options.newline.is_empty() { ":" } else { ": " })?;
+        self.is_after_property_name = true;
+        return Ok(());
+    }
+    /// Writes a string.
+    pub fn write_string(&mut self, string: &str) -> Result<(), &'static str> {
+        self.start_element()?;
+        self.write_string_token(string, false)?;
+        self.end_element();
+        return Ok(());
+    }
+    /// Writes a number, in the radix registered for the current path (see `with_radix_hint`) or in decimal.
+    /// 
+    /// Use `Number::from` for integers and `Number::from_f64` for floats.
+    pub fn write_number(&mut self, number: &Number) -> Result<(), &'static str> {
+        let radix: u32 = self.radix_hint();
+        return self.write_number_in_radix(number, radix);
+    }
+    /// Writes a number in the given radix (2, 8, 10 or 16), such as `0x1F00`.
+    /// 
+    /// Numbers with a fractional part are always written in decimal.
+    pub fn write_number_in_radix(&mut self, number: &Number, radix: u32) -> Result<(), &'static str> {
+        if !matches!(radix, 2 | 8 | 10 | 16) {
+            return Err("Radix must be 2, 8, 10 or 16");
+        }
+        self.start_element()?;
+        return self.write_formatted_number(Self::format_number(number, radix), radix);
+    }
+    /// Writes an integer of any size (such as `u128::MAX`), in the radix registered for the current path (see `with_radix_hint`) or in decimal.
+    pub fn write_integer<T: RadixInteger>(&mut self, integer: T) -> Result<(), &'static str> {
+        let radix: u32 = self.radix_hint();
+        return self.write_integer_in_radix(integer, radix);
+    }
+    /// Writes an integer of any size in the given radix (2, 8, 10 or 16), such as `0x1F00`.
+    pub fn write_integer_in_radix<T: RadixInteger>(&mut self, integer: T, radix: u32) -> Result<(), &'static str> {
+        if !matches!(radix, 2 | 8 | 10 | 16) {
+            return Err("Radix must be 2, 8, 10 or 16");
+        }
+        self.start_element()?;
+        return self.write_formatted_number(integer.to_radix_string(radix), radix);
+    }
+    /// Writes a boolean literal (`true` or `false`).
+    pub fn write_bool(&mut self, boolean: bool) -> Result<(), &'static str> {
+        self.start_element()?;
+        self.write_raw(if boolean { "true" } else { "false" })?;
+        self.end_element();
+        return Ok(());
+    }
+    /// Writes a null literal (`null`).
+    pub fn write_null(&mut self) -> Result<(), &'static str> {
+        self.start_element()?;
+        self.write_raw("null")?;
+        self.end_element();
+        return Ok(());
+    }
+    /// Writes a comment on its own line, or between a property name and its value (block comments only).
+    /// 
+    /// Line comments containing newlines are written as one comment per line.
+    /// 
+    /// ```
+    /// // Comment style: Line
+    /// {
+    ///     // The port to listen on.
+    ///     "port": 80
+    /// }
+    /// ```
+    pub fn write_comment(&mut self, comment: &str, style: JsonhCommentStyle) -> Result<(), &'static str> {
+        // Inline comment
+        if self.is_after_property_name {
+            if style != JsonhCommentStyle::Block {
+                return Err("Expected block comment between property name and value");
+            }
+            let block_comment: String = JsonhFormatter::convert_comment(&format!(" {comment}"), style, false).ok_or("Block comment cannot contain `*/`")?;
+            self.write_raw(&block_comment)?;
+            return self.write_raw(" ");
+        }
+
+        // Convert comment lines
+        let lines: Vec<&str> = if style == JsonhCommentStyle::Block { vec![comment] } else { comment.split('\n').map(|line| line.strip_suffix('\r').unwrap_or(line)).collect() };
+        let mut converted_lines: Vec<String> = Vec::new();
+        for line in lines {
+            converted_lines.push(JsonhFormatter::convert_comment(&format!(" {line}"), style, true).ok_or(match style {
+                JsonhCommentStyle::Block => "Block comment cannot contain `*/`",
+                _ => "Line comment cannot contain newlines",
+            })?);
+        }
+
+        // Separate from previous item
+        if let Some(structure) = self.structures.last_mut() {
+            let needs_separator: bool = structure.item_count > 0 && !structure.has_separator;
+            structure.has_separator |= needs_separator;
+            structure.has_comments = true;
+            if needs_separator {
+                self.write_separator()?;
+            }
+        }
+
+        // Write comment lines
+        for converted_line in converted_lines {
+            if self.has_output {
+                let depth: usize = self.depth();
+                self.write_newline(depth)?;
+            }
+            self.write_raw(&converted_line)?;
+        }
+        return Ok(());
+    }
+    /// Returns whether a complete root element has been written.
+    pub fn is_complete(&self) -> bool {
+        return self.has_root && self.structures.is_empty();
+    }
+
+    fn write_element(&mut self, value: &Value, compare: &mut Option<&mut KeyComparator<'_>>) -> Result<(), &'static str> {
+        match value {
+            // Primitives
+            Value::Null => return self.write_null(),
+            Value::Bool(boolean) => return self.write_bool(*boolean),
+            Value::Number(number) => return self.write_number(number),
+            Value::String(string) => return self.write_string(string),
+            // Array
+            Value::Array(items) => {
+                self.write_start_array()?;
+                for item in items {
+                    self.write_element(item, compare)?;
+                }
+                return self.write_end_array();
+            },
+            // Object
+            Value::Object(properties) => {
+                let mut properties: Vec<(&String, &Value)> = properties.iter().collect();
+                if let Some(compare) = compare {
+                    properties.sort_by(|(name1, _), (name2, _)| compare(name1, name2));
+                }
+
+                self.write_start_object()?;
+                for (property_name, property_value) in properties {
+                    self.write_property_name(property_name)?;
+                    self.write_element(property_value, compare)?;
+                }
+                return self.write_end_object();
+            },
+        }
+    }
+    fn start_element(&mut self) -> Result<(), &'static str> {
+        // Property value
+        if self.is_after_property_name {
+            self.is_after_property_name = false;
+            return Ok(());
+        }
+        match self.structures.last() {
+            // Root element
+            None => {
+                if self.has_root {
+                    return Err("Expected single element");
+                }
+                self.has_root = true;
+                // Leading comments
+                if self.has_output {
+                    self.write_newline(0)?;
+                }
+                return Ok(());
+            },
+            // Item
+            Some(WriterStructure { is_array: true, item_count, .. }) => {
+                let item_index: usize = *item_count;
+                self.start_item()?;
+                self.path.push(JsonhPathSegment::Index(item_index));
+                return Ok(());
+            },
+            // Property
+            Some(WriterStructure { is_array: false, .. }) => return Err("Expected property name in object"),
+        }
+    }
+    fn start_item(&mut self) -> Result<(), &'static str> {
+        let depth: usize = self.depth();
+        let Some(structure) = self.structures.last_mut() else {
+            return Ok(());
+        };
+        let is_first: bool = structure.item_count == 0;
+        let needs_separator: bool = !is_first && !structure.has_separator;
+        let needs_newline: bool = !is_first || !structure.is_braceless || structure.has_comments;
+        structure.item_count += 1;
+        structure.has_separator = false;
+
+        if needs_separator {
+            self.write_separator()?;
+        }
+        if needs_newline {
+            self.write_newline(depth)?;
+        }
+        return Ok(());
+    }
+    fn end_structure(&mut self, is_array: bool) -> Result<(), &'static str> {
+        let Some(structure) = self.structures.last().copied() else {
+            return Err(if is_array { "Unexpected end of array" } else { "Unexpected end of object" });
+        };
+        if structure.is_array != is_array {
+            return Err(if is_array { "Expected `}` to end object" } else { "Expected `]` to end array" });
+        }
+        self.structures.pop();
+
+        // Braceless object
+        if structure.is_braceless {
+            if structure.item_count == 0 {
+                if structure.has_comments {
+                    self.write_newline(0)?;
+                }
+                return self.write_raw("{}");
+            }
+            return Ok(());
+        }
+
+        if structure.item_count > 0 || structure.has_comments {
+            let depth: usize = self.depth();
+            self.write_newline(depth)?;
+        }
+        self.write_raw(if is_array { "]" } else { "}" })?;
+        self.end_element();
+        return Ok(());
+    }
+    fn end_element(&mut self) {
+        if !self.structures.is_empty() {
+            self.path.pop();
+        }
+    }
+    fn element_path(&self) -> Option<JsonhPath> {
+        // Path of property value or item
+        if self.is_after_property_name {
+            return Some(self.path.clone());
+        }
+        let Some(structure) = self.structures.last() else {
+            return Some(self.path.clone());
+        };
+        if !structure.is_array {
+            return None;
+        }
+        let mut path: JsonhPath = self.path.clone();
+        path.push(JsonhPathSegment::Index(structure.item_count));
+        return Some(path);
+    }
+    fn radix_hint(&self) -> u32 {
+        if !self.radix_hints.is_empty() && let Some(number_path) = self.element_path() {
+            if let Some((_, radix_hint)) = self.radix_hints.iter().find(|(path, _)| *path == number_path) {
+                return *radix_hint;
+            }
+        }
+        return 10;
+    }
+    fn write_formatted_number(&mut self, mut formatted: String, radix: u32) -> Result<(), &'static str> {
+        if self.options.digit_grouping {
+            formatted = Self::group_digits(&formatted, radix);
+        }
+        self.write_raw(&formatted)?;
+        self.end_element();
+        return Ok(());
+    }
+    fn format_number(number: &Number, radix: u32) -> String {
+        if radix != 10 {
+            // Integer
+            if let Some(integer) = number.as_i64() {
+                return integer.to_radix_string(radix);
+            }
+            if let Some(integer) = number.as_u64() {
+                return integer.to_radix_string(radix);
+            }
+            // Float without fractional part (in the range of exact integers)
+            if let Some(float) = number.as_f64() && float.fract() == 0.0 && float.abs() <= 9007199254740992.0 {
+                return (float as i64).to_radix_string(radix);
+            }
+        }
+        return number.to_string();
+    }
+    fn group_digits(formatted: &str, radix: u32) -> String {
+        let group_size: usize = if matches!(radix, 2 | 16) { 4 } else { 3 };
+
+        // Sign and prefix
+        let prefix_length: usize = formatted.find(|next: char| next.is_ascii_digit()).unwrap_or(0) + if radix == 10 { 0 } else { 2 };
+        let (prefix, rest) = formatted.split_at(prefix_length.min(formatted.len()));
+
+        // Integer digits (hexadecimal numbers have no fraction or exponent)
+        let digits_length: usize = if radix == 10 { rest.find(['.', 'e', 'E']).unwrap_or(rest.len()) } else { rest.len() };
+        let (digits, suffix) = rest.split_at(digits_length);
+
+        let mut grouped: String = prefix.to_string();
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index) % group_size == 0 {
+                grouped.push('_');
+            }
+            grouped.push(digit);
+        }
+        grouped += suffix;
+        return grouped;
+    }
+    fn depth(&self) -> usize {
+        return self.structures.iter().filter(|structure| !structure.is_braceless).count();
+    }
+    fn write_string_token(&mut self, string: &str, is_property_name: bool) -> Result<(), &'static str> {
+        let is_ascii_only: bool = self.options.escape_style == JsonhEscapeStyle::Ascii;
+
+        // Quoteless string
+        if self.options.quoteless_strings && (string.is_ascii() || !is_ascii_only) && JsonhFormatter::is_quoteless_safe(string, is_property_name, self.options.version) {
+            return self.write_raw(string);
+        }
+        // Multi-quoted string
+        if !is_property_name
+            && let Some(min_length) = self.options.multi_quoted_strings
+            && self.options.newline == "\n"
+            && string.contains('\n')
+            && string.chars().count() >= min_length
+        {
+            let indent: String = self.options.indent_char.to_string().repeat((self.depth() + 1) * self.options.indent_width);
+            if let Some(multi_quoted) = JsonhFormatter::multi_quote(string, self.options.quote_style.quote(), &indent) {
+                return self.write_raw(&if is_ascii_only { Self::escape_non_ascii(&multi_quoted) } else { multi_quoted });
+            }
+        }
+        // Quoted string
+        let quote: char = self.options.quote_style.quote();
+        let quoted: String = match self.options.escape_style {
+            JsonhEscapeStyle::Standard => JsonhFormatter::quote(string, quote),
+            JsonhEscapeStyle::Ascii => Self::escape_non_ascii(&JsonhFormatter::quote(string, quote)),
+            JsonhEscapeStyle::Minimal => {
+                let mut quoted: String = quote.to_string();
+                for next in string.chars() {
+                    if next == '\\' || next == quote {
+                        quoted.push('\\');
+                    }
+                    quoted.push(next);
+                }
+                quoted.push(quote);
+                quoted
+            },
+        };
+        return self.write_raw(&quoted);
+    }
+    fn escape_non_ascii(text: &str) -> String {
+        let mut escaped: String = String::new();
+        for next in text.chars() {
+            // ASCII character
+            if next.is_ascii() {
+                escaped.push(next);
+            }
+            // Basic Multilingual Plane
+            else if (next as u32) <= 0xFFFF {
+                escaped += &format!("\\u{:04x}", next as u32);
+            }
+            // Supplementary planes
+            else {
+                escaped += &format!("\\U{:08x}", next as u32);
+            }
+        }
+        return escaped;
+    }
+    fn write_separator(&mut self) -> Result<(), &'static str> {
+        // Commas are required on a single line
+        if self.options.commas || self.options.newline.is_empty() {
+            return self.write_raw(",");
+        }
+        return Ok(());
+    }
+    fn write_newline(&mut self, depth: usize) -> Result<(), &'static str> {
+        // Single line
+        if self.options.newline.is_empty() {
+            return Ok(());
+        }
+        self.write_raw(self.options.newline)?;
+        let indent: String = self.options.indent_char.to_string().repeat(depth * self.options.indent_width);
+        return self.write_raw(&indent);
+    }
+    fn write_raw(&mut self, text: &str) -> Result<(), &'static str> {
+        self.has_output |= !text.is_empty();
+        return self.writer.write_all(text.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            "Failed to write"
+        });
+    }
+}
+
+impl JsonhWriter<Vec<u8>> {
+    /// Writes a single element to a string.
+    pub fn write_to_string(value: &Value, options: JsonhWriterOptions) -> String {
+        let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), options);
+        // Writing a single element to a Vec<u8> cannot fail
+        let _ = writer.write_value(value);
+        return String::from_utf8(writer.writer).unwrap_or_default();
+    }
+}
+
+/// Compares property names when sorting the properties of an object.
+type KeyComparator<'c> = dyn FnMut(&str, &str) -> Ordering + 'c;
+
+/// An object or array being written by a `JsonhWriter`.
+#[derive(Clone, Copy)]
+struct WriterStructure {
+    is_array: bool,
+    is_braceless: bool,
+    item_count: usize,
+    has_comments: bool,
+    has_separator: bool,
+}
\ No newline at end of file
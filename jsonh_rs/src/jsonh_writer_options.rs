@@ -0,0 +1,260 @@
+use crate::JsonhVersion;
+use crate::JsonhQuoteStyle;
+use crate::JsonhEscapeStyle;
+
+/// Options for a `JsonhWriter`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[non_exhaustive]
+pub struct JsonhWriterOptions {
+    /// Specifies the major version of the JSONH specification that the output must be readable by.
+    pub version: JsonhVersion,
+    /// Sets the number of indent characters per level of nesting.
+    /// 
+    /// ```
+    /// // Indent width: 2
+    /// {
+    ///   "a": "b"
+    /// }
+    /// ```
+    pub indent_width: usize,
+    /// Sets the character used for indentation, usually a space or a tab.
+    pub indent_char: char,
+    /// Sets the newline sequence used between lines, usually `"\n"` or `"\r\n"`.
+    /// 
+    /// If empty, elements are written on a single line without spaces (e.g. `{"a":[1,2]}`), so commas are written even if disabled.
+    pub newline: &'static str,
+    /// Sets the quotes used to write strings and property names.
+    /// 
+    /// ```
+    /// // Quote style: Single
+    /// 'a': 'b'
+    /// ```
+    pub quote_style: JsonhQuoteStyle,
+    /// Enables/disables omitting the braces of the root object.
+    /// 
+    /// ```
+    /// "a": 1,
+    /// "b": 2
+    /// ```
+    /// 
+    /// Empty root objects are always written with braces.
+    pub braceless_root: bool,
+    /// Enables/disables commas between properties and items.
+    /// 
+    /// Commas are always written if the newline sequence is empty, since nothing else would separate the elements.
+    /// 
+    /// ```
+    /// {
+    ///   "a": "b", // With commas
+    ///   "c": "d"  // Without commas
+    /// }
+    /// ```
+    pub commas: bool,
+    /// Enables/disables writing strings and property names without quotes wherever they would be read back unchanged under the configured version.
+    /// 
+    /// Strings with reserved characters, newlines, or leading or trailing whitespace, and strings that would be read as numbers or named literals, are still quoted.
+    /// 
+    /// ```
+    /// // Quoteless strings: true
+    /// a: b c,
+    /// d: "true"
+    /// ```
+    pub quoteless_strings: bool,
+    /// Sets the minimum length of strings containing newlines to write as multi-quoted strings, or `None` to never write them.
+    /// 
+    /// Strings are only written as multi-quoted strings if the newline sequence is `"\n"`, since the newlines in the string are written as-is.
+    /// Property names are always written on a single line.
+    /// 
+    /// ```
+    /// // Multi-quoted strings: Some(0)
+    /// {
+    ///     "a": """
+    ///         line 1
+    ///         line 2
+    ///         """
+    /// }
+    /// ```
+    pub multi_quoted_strings: Option<usize>,
+    /// Enables/disables separating groups of digits in the integer part of numbers with underscores.
+    /// 
+    /// Decimal and octal digits are grouped in threes, and hexadecimal and binary digits are grouped in fours.
+    /// 
+    /// ```
+    /// // Digit grouping: true
+    /// 1_000_000.25
+    /// 0x1F_0000
+    /// ```
+    pub digit_grouping: bool,
+    /// Enables/disables sorting the properties of each object alphabetically by name when writing values.
+    /// 
+    /// This makes the output stable regardless of the order properties were inserted in.
+    /// To sort with a custom comparator, use `JsonhWriter::write_value_sorted_by`.
+    /// 
+    /// ```
+    /// // Sort keys: true
+    /// {
+    ///     "a": 1,
+    ///     "b": 2
+    /// }
+    /// ```
+    pub sort_keys: bool,
+    /// Sets the characters escaped in strings and property names.
+    /// 
+    /// ```
+    /// // Escape style: Ascii
+    /// "caf\u00e9"
+    /// ```
+    /// 
+    /// Comments are never escaped. If the escape style is `Ascii`, strings containing non-ASCII characters are always quoted.
+    pub escape_style: JsonhEscapeStyle,
+}
+
+impl JsonhWriterOptions {
+    /// Constructs a `JsonhWriterOptions` with some default values.
+    pub fn new() -> Self {
+        return Self { version: JsonhVersion::Latest, indent_width: 4, indent_char: ' ', newline: "\n", quote_style: JsonhQuoteStyle::Double, braceless_root: false, commas: true, quoteless_strings: false, multi_quoted_strings: None, digit_grouping: false, sort_keys: false, escape_style: JsonhEscapeStyle::Standard };
+    }
+    /// Specifies the major version of the JSONH specification that the output must be readable by.
+    pub fn with_version(mut self, value: JsonhVersion) -> Self {
+        self.version = value;
+        return self;
+    }
+    /// Sets the number of indent characters per level of nesting.
+    /// 
+    /// ```
+    /// // Indent width: 2
+    /// {
+    ///   "a": "b"
+    /// }
+    /// ```
+    pub fn with_indent_width(mut self, value: usize) -> Self {
+        self.indent_width = value;
+        return self;
+    }
+    /// Sets the character used for indentation, usually a space or a tab.
+    pub fn with_indent_char(mut self, value: char) -> Self {
+        self.indent_char = value;
+        return self;
+    }
+    /// Sets the newline sequence used between lines, usually `"\n"` or `"\r\n"`.
+    /// 
+    /// If empty, elements are written on a single line without spaces (e.g. `{"a":[1,2]}`), so commas are written even if disabled.
+    pub fn with_newline(mut self, value: &'static str) -> Self {
+        self.newline = value;
+        return self;
+    }
+    /// Sets the quotes used to write strings and property names.
+    /// 
+    /// ```
+    /// // Quote style: Single
+    /// 'a': 'b'
+    /// ```
+    pub fn with_quote_style(mut self, value: JsonhQuoteStyle) -> Self {
+        self.quote_style = value;
+        return self;
+    }
+    /// Enables/disables omitting the braces of the root object.
+    /// 
+    /// ```
+    /// "a": 1,
+    /// "b": 2
+    /// ```
+    /// 
+    /// Empty root objects are always written with braces.
+    pub fn with_braceless_root(mut self, value: bool) -> Self {
+        self.braceless_root = value;
+        return self;
+    }
+    /// Enables/disables commas between properties and items.
+    /// 
+    /// Commas are always written if the newline sequence is empty, since nothing else would separate the elements.
+    /// 
+    /// ```
+    /// {
+    ///   "a": "b", // With commas
+    ///   "c": "d"  // Without commas
+    /// }
+    /// ```
+    pub fn with_commas(mut self, value: bool) -> Self {
+        self.commas = value;
+        return self;
+    }
+    /// Enables/disables writing strings and property names without quotes wherever they would be read back unchanged under the configured version.
+    /// 
+    /// Strings with reserved characters, newlines, or leading or trailing whitespace, and strings that would be read as numbers or named literals, are still quoted.
+    /// 
+    /// ```
+    /// // Quoteless strings: true
+    /// a: b c,
+    /// d: "true"
+    /// ```
+    pub fn with_quoteless_strings(mut self, value: bool) -> Self {
+        self.quoteless_strings = value;
+        return self;
+    }
+    /// Sets the minimum length of strings containing newlines to write as multi-quoted strings, or `None` to never write them.
+    /// 
+    /// Strings are only written as multi-quoted strings if the newline sequence is `"\n"`, since the newlines in the string are written as-is.
+    /// Property names are always written on a single line.
+    /// 
+    /// ```
+    /// // Multi-quoted strings: Some(0)
+    /// {
+    ///     "a": """
+    ///         line 1
+    ///         line 2
+    ///         """
+    /// }
+    /// ```
+    pub fn with_multi_quoted_strings(mut self, value: Option<usize>) -> Self {
+        self.multi_quoted_strings = value;
+        return self;
+    }
+    /// Enables/disables separating groups of digits in the integer part of numbers with underscores.
+    /// 
+    /// Decimal and octal digits are grouped in threes, and hexadecimal and binary digits are grouped in fours.
+    /// 
+    /// ```
+    /// // Digit grouping: true
+    /// 1_000_000.25
+    /// 0x1F_0000
+    /// ```
+    pub fn with_digit_grouping(mut self, value: bool) -> Self {
+        self.digit_grouping = value;
+        return self;
+    }
+    /// Enables/disables sorting the properties of each object alphabetically by name when writing values.
+    /// 
+    /// This makes the output stable regardless of the order properties were inserted in.
+    /// To sort with a custom comparator, use `JsonhWriter::write_value_sorted_by`.
+    /// 
+    /// ```
+    /// // Sort keys: true
+    /// {
+    ///     "a": 1,
+    ///     "b": 2
+    /// }
+    /// ```
+    pub fn with_sort_keys(mut self, value: bool) -> Self {
+        self.sort_keys = value;
+        return self;
+    }
+    /// Sets the characters escaped in strings and property names.
+    /// 
+    /// ```
+    /// // Escape style: Ascii
+    /// "caf\u00e9"
+    /// ```
+    /// 
+    /// Comments are never escaped. If the escape style is `Ascii`, strings containing non-ASCII characters are always quoted.
+    pub fn with_escape_style(mut self, value: JsonhEscapeStyle) -> Self {
+        self.escape_style = value;
+        return self;
+    }
+}
+
+impl Default for JsonhWriterOptions {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
\ No newline at end of file
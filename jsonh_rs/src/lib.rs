@@ -1,15 +1,183 @@
 pub mod jsonh_reader;
 pub mod jsonh_token;
+pub mod jsonh_token_iter;
 pub mod json_token_type;
 pub mod jsonh_reader_options;
 pub mod jsonh_version;
 pub mod jsonh_number_parser;
+pub mod jsonh_path;
+pub mod jsonh_path_segment;
+pub mod jsonh_parse_stats;
+pub mod jsonh_formatter;
+pub mod jsonh_formatter_options;
+pub mod jsonh_severity;
+pub mod jsonh_diagnostic;
+pub mod jsonh_linter;
+pub mod jsonh_lint_rule;
+pub mod jsonh_lint_rules;
+pub mod jsonh_lint_context;
+pub mod jsonh_text_edit;
+pub mod jsonh_quote_style;
+pub mod jsonh_comment_style;
+pub mod jsonh_error;
+pub mod jsonh_deserializer;
+pub mod commented;
+pub mod spanned;
+pub mod jsonh_raw_value;
+pub mod radix;
+pub mod jsonh_source_map;
+pub mod jsonh_source_mapping;
+pub mod jsonh_comment;
+pub mod jsonh_embedded_element;
+pub mod jsonh_bytes_encoding;
+pub mod bytes;
+pub mod value_parser;
+pub mod jsonh_source;
+pub mod jsonh_char_source;
+pub mod jsonh_partial_parser;
+pub mod jsonh_push_parser;
+pub mod jsonh_read_state;
+pub mod jsonh_string_style;
+pub mod escaping;
+pub mod jsonh_divergence;
+pub mod round_trip;
+pub mod jsonh_semantic_options;
+pub mod semantic;
+pub mod jsonh_type_hint;
+pub mod jsonh_tee_source;
+pub mod jsonh_read_source;
+pub mod jsonh_bytes_source;
+pub mod jsonh_format_guess;
+pub mod format_detection;
+pub mod path_lookup;
+pub mod token_dump;
+pub mod quoting;
+pub mod jsonh_writer;
+pub mod jsonh_writer_options;
+pub mod jsonh_escape_style;
+pub mod deserialize;
+pub mod jsonh_stream_deserializer;
+pub mod jsonh_serializer;
+pub mod serialize;
+pub mod transcode;
+pub mod error_snippet;
+pub mod recovery;
+pub mod suggestions;
+pub mod jsonh_line_index;
+pub mod jsonh_visitor;
+pub mod jsonh_patch_op;
+pub mod jsonh_patch_operation;
+pub mod patch;
+#[cfg(feature = "ropey")]
+pub mod jsonh_rope_source;
+#[cfg(feature = "arena")]
+pub mod jsonh_arena_value;
+#[cfg(feature = "miette")]
+pub mod jsonh_error_report;
+#[cfg(feature = "async")]
+pub mod jsonh_async_reader;
+#[cfg(feature = "async")]
+pub mod jsonh_partial_stream;
 
 pub use self::jsonh_reader::JsonhReader;
 pub use self::jsonh_token::JsonhToken;
+pub use self::jsonh_token_iter::JsonhTokenIter;
 pub use self::json_token_type::JsonTokenType;
 pub use self::jsonh_reader_options::JsonhReaderOptions;
 pub use self::jsonh_version::JsonhVersion;
 pub use self::jsonh_number_parser::JsonhNumberParser;
+pub use self::jsonh_path::JsonhPath;
+pub use self::jsonh_path_segment::JsonhPathSegment;
+pub use self::jsonh_parse_stats::JsonhParseStats;
+pub use self::jsonh_formatter::JsonhFormatter;
+pub use self::jsonh_formatter_options::JsonhFormatterOptions;
+pub use self::jsonh_severity::JsonhSeverity;
+pub use self::jsonh_diagnostic::JsonhDiagnostic;
+pub use self::jsonh_linter::JsonhLinter;
+pub use self::jsonh_lint_rule::JsonhLintRule;
+pub use self::jsonh_lint_context::JsonhLintContext;
+pub use self::jsonh_text_edit::JsonhTextEdit;
+pub use self::jsonh_quote_style::JsonhQuoteStyle;
+pub use self::jsonh_comment_style::JsonhCommentStyle;
+pub use self::jsonh_error::JsonhError;
+pub use self::jsonh_deserializer::JsonhDeserializer;
+pub use self::commented::Commented;
+pub use self::spanned::Spanned;
+pub use self::jsonh_raw_value::JsonhRawValue;
+pub use self::jsonh_source_map::JsonhSourceMap;
+pub use self::jsonh_source_mapping::JsonhSourceMapping;
+pub use self::jsonh_comment::JsonhComment;
+pub use self::jsonh_embedded_element::JsonhEmbeddedElement;
+pub use self::jsonh_bytes_encoding::JsonhBytesEncoding;
+pub use self::jsonh_source::JsonhSource;
+pub use self::jsonh_char_source::JsonhCharSource;
+pub use self::jsonh_partial_parser::JsonhPartialParser;
+pub use self::jsonh_push_parser::JsonhPushParser;
+pub use self::jsonh_read_state::JsonhReadState;
+pub use self::jsonh_string_style::JsonhStringStyle;
+pub use self::escaping::escape;
+pub use self::escaping::escape_for_reader;
+pub use self::escaping::unescape;
+pub use self::jsonh_divergence::JsonhDivergence;
+pub use self::round_trip::check_round_trip;
+pub use self::round_trip::assert_round_trip;
+pub use self::jsonh_semantic_options::JsonhSemanticOptions;
+pub use self::semantic::semantic_hash;
+pub use self::semantic::semantic_hash_str;
+pub use self::semantic::semantic_eq;
+pub use self::semantic::semantic_eq_str;
+pub use self::jsonh_type_hint::JsonhTypeHint;
+pub use self::jsonh_tee_source::JsonhTeeSource;
+pub use self::jsonh_read_source::JsonhReadSource;
+pub use self::jsonh_bytes_source::JsonhBytesSource;
+pub use self::jsonh_format_guess::JsonhFormatGuess;
+pub use self::format_detection::detect_format;
+pub use self::path_lookup::get;
+pub use self::path_lookup::get_path;
+pub use self::token_dump::dump_tokens;
+pub use self::token_dump::dump_tokens_from_str;
+pub use self::token_dump::parse_token_dump;
+pub use self::quoting::is_valid_quoteless_string;
+pub use self::quoting::key_needs_quotes;
+pub use self::quoting::string_requires_multiquote;
+pub use self::jsonh_writer::JsonhWriter;
+pub use self::jsonh_writer_options::JsonhWriterOptions;
+pub use self::jsonh_escape_style::JsonhEscapeStyle;
+pub use self::deserialize::from_str;
+pub use self::deserialize::from_str_with_options;
+pub use self::deserialize::from_reader;
+pub use self::jsonh_stream_deserializer::JsonhStreamDeserializer;
+pub use self::jsonh_serializer::JsonhSerializer;
+pub use self::serialize::to_string;
+pub use self::serialize::to_string_pretty;
+pub use self::serialize::to_string_with_options;
+pub use self::serialize::to_writer;
+pub use self::transcode::transcode;
+pub use self::transcode::transcode_to_json;
+pub use self::error_snippet::render_error;
+pub use self::recovery::parse_with_recovery;
+pub use self::suggestions::suggest_literal;
+pub use self::suggestions::suggest_literal_from;
+pub use self::jsonh_line_index::JsonhLineIndex;
+pub use self::jsonh_visitor::JsonhVisitor;
+pub use self::jsonh_patch_op::JsonhPatchOp;
+pub use self::jsonh_patch_operation::JsonhPatchOperation;
+pub use self::patch::diff;
+pub use self::patch::diff_str;
+pub use self::patch::apply_patch;
+pub use self::patch::parse_patch;
+pub use self::patch::patch_to_value;
+#[cfg(feature = "ropey")]
+pub use self::jsonh_rope_source::JsonhRopeSource;
+#[cfg(feature = "arena")]
+pub use self::jsonh_arena_value::JsonhArenaValue;
+#[cfg(feature = "arena")]
+pub use bumpalo;
+#[cfg(feature = "miette")]
+pub use self::jsonh_error_report::JsonhErrorReport;
+#[cfg(feature = "async")]
+pub use self::jsonh_async_reader::JsonhAsyncReader;
+#[cfg(feature = "async")]
+pub use self::jsonh_partial_stream::JsonhPartialStream;
 pub use serde_json::Value;
 pub use serde_json;
\ No newline at end of file
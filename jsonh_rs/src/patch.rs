@@ -0,0 +1,217 @@
+//! Functions for finding the differences between JSONH elements and applying them as JSON Patch (RFC 6902) operations.
+//! 
+//! ```
+//! let operations: Vec<JsonhPatchOperation> = jsonh_rs::diff_str("{ a: 1, b: [1] }", "{ a: 2, b: [1, 2] }", JsonhReaderOptions::new())?;
+//! // replace a: 2
+//! // add b[1]: 2
+//! let mut value: Value = JsonhReader::parse_element_from_str("{ a: 1, b: [1] }", JsonhReaderOptions::new())?;
+//! jsonh_rs::apply_patch(&mut value, &operations)?;
+//! ```
+
+use serde_json::Value;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhPath;
+use crate::JsonhPathSegment;
+use crate::JsonhPatchOp;
+use crate::JsonhPatchOperation;
+use crate::JsonhSemanticOptions;
+use crate::JsonhError;
+use crate::semantic_eq;
+
+/// Returns the operations that change the first element into the second element.
+/// 
+/// Properties are compared by name regardless of their order, and numbers are compared by their value (so `16` and `16.0` are equal).
+/// Array items are compared by index, with items added to or removed from the end.
+pub fn diff(a: &Value, b: &Value) -> Vec<JsonhPatchOperation> {
+    let mut operations: Vec<JsonhPatchOperation> = Vec::new();
+    let mut path: JsonhPath = JsonhPath::new();
+    diff_at(a, b, &mut path, &mut operations);
+    return operations;
+}
+/// Parses a single element from each source and returns the operations that change the first element into the second element (see `diff`).
+/// 
+/// Comments, whitespace and the spelling of strings and numbers are ignored.
+pub fn diff_str(a: &str, b: &str, options: JsonhReaderOptions) -> Result<Vec<JsonhPatchOperation>, JsonhError> {
+    let a_value: Value = JsonhReader::parse_element_from_str(a, options)?;
+    let b_value: Value = JsonhReader::parse_element_from_str(b, options)?;
+    return Ok(diff(&a_value, &b_value));
+}
+
+/// Applies the operations to the element in order.
+/// 
+/// If any operation fails (e.g. a path is not found or a `test` operation does not match), the element is left unchanged.
+pub fn apply_patch(value: &mut Value, operations: &[JsonhPatchOperation]) -> Result<(), JsonhError> {
+    let mut patched: Value = value.clone();
+    for operation in operations {
+        apply_operation(&mut patched, operation)?;
+    }
+    *value = patched;
+    return Ok(());
+}
+/// Parses the operations in a JSON Patch (RFC 6902) document, which can also be written in JSONH.
+/// 
+/// ```
+/// [
+///     { op: replace, path: "/a", value: 2 }
+///     { op: add, path: "/b/-", value: 2 }
+/// ]
+/// ```
+pub fn parse_patch(source: &str, options: JsonhReaderOptions) -> Result<Vec<JsonhPatchOperation>, JsonhError> {
+    let Value::Array(items) = JsonhReader::parse_element_from_str(source, options)? else {
+        return Err(JsonhError::from("Expected array of patch operations"));
+    };
+    let mut operations: Vec<JsonhPatchOperation> = Vec::with_capacity(items.len());
+    for (index, item) in items.iter().enumerate() {
+        match JsonhPatchOperation::from_value(item) {
+            Ok(operation) => operations.push(operation),
+            Err(error) => return Err(JsonhError::new(format!("{error} (operation {index})"))),
+        }
+    }
+    return Ok(operations);
+}
+/// Converts the operations to a JSON Patch (RFC 6902) document.
+pub fn patch_to_value(operations: &[JsonhPatchOperation]) -> Value {
+    return Value::Array(operations.iter().map(JsonhPatchOperation::to_value).collect());
+}
+
+fn diff_at(a: &Value, b: &Value, path: &mut JsonhPath, operations: &mut Vec<JsonhPatchOperation>) {
+    match (a, b) {
+        // Object
+        (Value::Object(a_properties), Value::Object(b_properties)) => {
+            for (name, a_value) in a_properties {
+                path.push(JsonhPathSegment::Property(name.clone()));
+                match b_properties.get(name) {
+                    Some(b_value) => diff_at(a_value, b_value, path, operations),
+                    None => operations.push(JsonhPatchOperation::remove(path.clone())),
+                }
+                path.pop();
+            }
+            for (name, b_value) in b_properties {
+                if !a_properties.contains_key(name) {
+                    path.push(JsonhPathSegment::Property(name.clone()));
+                    operations.push(JsonhPatchOperation::add(path.clone(), b_value.clone()));
+                    path.pop();
+                }
+            }
+        },
+        // Array
+        (Value::Array(a_items), Value::Array(b_items)) => {
+            for (index, (a_item, b_item)) in a_items.iter().zip(b_items).enumerate() {
+                path.push(JsonhPathSegment::Index(index));
+                diff_at(a_item, b_item, path, operations);
+                path.pop();
+            }
+            // Added items
+            for (index, b_item) in b_items.iter().enumerate().skip(a_items.len()) {
+                path.push(JsonhPathSegment::Index(index));
+                operations.push(JsonhPatchOperation::add(path.clone(), b_item.clone()));
+                path.pop();
+            }
+            // Removed items (from the end so indexes stay valid)
+            for index in (b_items.len()..a_items.len()).rev() {
+                path.push(JsonhPathSegment::Index(index));
+                operations.push(JsonhPatchOperation::remove(path.clone()));
+                path.pop();
+            }
+        },
+        // Primitive or different types
+        _ => {
+            if !semantic_eq(a, b, JsonhSemanticOptions::new().with_ignore_key_order(true)) {
+                operations.push(JsonhPatchOperation::replace(path.clone(), b.clone()));
+            }
+        },
+    }
+}
+fn apply_operation(value: &mut Value, operation: &JsonhPatchOperation) -> Result<(), JsonhError> {
+    match operation.op {
+        JsonhPatchOp::Add => {
+            let item: Value = operation.value.clone().ok_or("Expected `value` in patch operation")?;
+            return add_at(value, &operation.path, item);
+        },
+        JsonhPatchOp::Remove => {
+            remove_at(value, &operation.path)?;
+            return Ok(());
+        },
+        JsonhPatchOp::Replace => {
+            let item: Value = operation.value.clone().ok_or("Expected `value` in patch operation")?;
+            *get_at_mut(value, &operation.path.segments).ok_or("Path not found for `replace` operation")? = item;
+            return Ok(());
+        },
+        JsonhPatchOp::Move => {
+            let from: &JsonhPath = operation.from.as_ref().ok_or("Expected `from` in patch operation")?;
+            if from.segments.len() < operation.path.segments.len() && is_prefix(&from.segments, &operation.path.segments) {
+                return Err(JsonhError::from("Cannot move element into one of its children"));
+            }
+            let item: Value = remove_at(value, from)?;
+            return add_at(value, &operation.path, item);
+        },
+        JsonhPatchOp::Copy => {
+            let from: &JsonhPath = operation.from.as_ref().ok_or("Expected `from` in patch operation")?;
+            let item: Value = get_at_mut(value, &from.segments).ok_or("Path not found for `copy` operation")?.clone();
+            return add_at(value, &operation.path, item);
+        },
+        JsonhPatchOp::Test => {
+            let expected: &Value = operation.value.as_ref().ok_or("Expected `value` in patch operation")?;
+            let actual: &Value = get_at_mut(value, &operation.path.segments).ok_or("Path not found for `test` operation")?;
+            if !semantic_eq(expected, actual, JsonhSemanticOptions::new().with_ignore_key_order(true)) {
+                return Err(JsonhError::new(format!("Test failed at {}: expected {expected}, got {actual}", operation.path)));
+            }
+            return Ok(());
+        },
+    }
+}
+fn add_at(value: &mut Value, path: &JsonhPath, item: Value) -> Result<(), JsonhError> {
+    let Some((last, parent_segments)) = path.segments.split_last() else {
+        *value = item;
+        return Ok(());
+    };
+    match get_at_mut(value, parent_segments).ok_or("Path not found for `add` operation")? {
+        Value::Object(properties) => {
+            properties.insert(segment_name(last), item);
+        },
+        Value::Array(items) => match last {
+            JsonhPathSegment::Index(index) if *index <= items.len() => items.insert(*index, item),
+            JsonhPathSegment::Property(name) if name == "-" => items.push(item),
+            _ => return Err(JsonhError::from("Index out of range for `add` operation")),
+        },
+        _ => return Err(JsonhError::from("Expected object or array for `add` operation")),
+    }
+    return Ok(());
+}
+fn remove_at(value: &mut Value, path: &JsonhPath) -> Result<Value, JsonhError> {
+    let Some((last, parent_segments)) = path.segments.split_last() else {
+        return Err(JsonhError::from("Cannot remove root element"));
+    };
+    let removed: Option<Value> = match get_at_mut(value, parent_segments) {
+        Some(Value::Object(properties)) => properties.remove(&segment_name(last)),
+        Some(Value::Array(items)) => match last {
+            JsonhPathSegment::Index(index) if *index < items.len() => Some(items.remove(*index)),
+            _ => None,
+        },
+        _ => None,
+    };
+    return removed.ok_or(JsonhError::from("Path not found for `remove` operation"));
+}
+fn get_at_mut<'a>(value: &'a mut Value, segments: &[JsonhPathSegment]) -> Option<&'a mut Value> {
+    let mut current: &mut Value = value;
+    for segment in segments {
+        current = match (current, segment) {
+            (Value::Object(properties), segment) => properties.get_mut(&segment_name(segment))?,
+            (Value::Array(items), JsonhPathSegment::Index(index)) => items.get_mut(*index)?,
+            _ => return None,
+        };
+    }
+    return Some(current);
+}
+fn segment_name(segment: &JsonhPathSegment) -> String {
+    // Pointers do not distinguish indexes from property names
+    return match segment {
+        JsonhPathSegment::Property(name) => name.clone(),
+        JsonhPathSegment::Index(index) => index.to_string(),
+    };
+}
+fn is_prefix(prefix: &[JsonhPathSegment], segments: &[JsonhPathSegment]) -> bool {
+    return prefix.iter().zip(segments).all(|(a, b)| segment_name(a) == segment_name(b));
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+//! Functions for looking up a single value by path without parsing the whole document.
+//!
+//! ```
+//! let port: Option<Value> = jsonh_rs::get("server: { hosts: [{ port: 80 }] }", "server.hosts[0].port"); // Some(80.0)
+//! ```
+
+use serde_json::Value;
+
+use crate::JsonhPath;
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+
+/// Returns the value at the path (formatted like `a.b[2].c`, see `JsonhPath::from_str`), or `None` if it is not found.
+/// 
+/// Tokens are read only until the value ends, and only the value itself is parsed, so lookups near the start of a large document are fast.
+/// Values outside the path are skipped without being kept (see `JsonhReader::find_path_value`), and reading stops early once the structure containing the path ends.
+/// If a property name is repeated, the first value is returned.
+pub fn get(source: &str, path: &str) -> Option<Value> {
+    let path: JsonhPath = path.parse().ok()?;
+    return get_path(source, &path);
+}
+/// Returns the value at the path, or `None` if it is not found (see `get`).
+pub fn get_path(source: &str, path: &JsonhPath) -> Option<Value> {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(source, JsonhReaderOptions::new());
+    if !reader.find_path_value(path) {
+        return None;
+    }
+    return reader.parse_element().ok();
+}
\ No newline at end of file
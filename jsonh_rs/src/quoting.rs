@@ -0,0 +1,42 @@
+//! Functions for deciding how a string must be quoted, using the same rules as the reader.
+//!
+//! ```
+//! let key: String = if jsonh_rs::key_needs_quotes(name) { format!("\"{}\"", jsonh_rs::escape(name, JsonhStringStyle::Double)) } else { name.to_string() };
+//! ```
+
+use crate::JsonhFormatter;
+use crate::JsonhVersion;
+
+/// Returns whether the string can be written as a quoteless string and read back unchanged in the given version.
+/// 
+/// Strings with leading or trailing whitespace, newlines or reserved characters, and strings that would be read as numbers or named literals, are not valid.
+/// 
+/// ```
+/// hello world -> true
+/// a, b -> false
+/// 10 -> false
+/// ```
+pub fn is_valid_quoteless_string(value: &str, version: JsonhVersion) -> bool {
+    return JsonhFormatter::is_quoteless_safe(value, false, version);
+}
+/// Returns whether the property name must be quoted to be read back unchanged with the latest version.
+/// 
+/// Unlike quoteless strings, quoteless property names can look like numbers or named literals.
+/// 
+/// ```
+/// key -> false
+/// 10 -> false
+/// a:b -> true
+/// ```
+pub fn key_needs_quotes(key: &str) -> bool {
+    return !JsonhFormatter::is_quoteless_safe(key, true, JsonhVersion::Latest);
+}
+/// Returns whether the string contains newlines, so it must be multi-quoted to be written without escape sequences.
+/// 
+/// ```
+/// line 1\nline 2 -> true
+/// line -> false
+/// ```
+pub fn string_requires_multiquote(value: &str) -> bool {
+    return value.contains(['\n', '\r', '\u{2028}', '\u{2029}']);
+}
\ No newline at end of file
@@ -0,0 +1,184 @@
+//! Serde adapters for integers written in hexadecimal, binary or octal.
+//! 
+//! ```
+//! struct Register {
+//!     #[serde(with = "jsonh_rs::radix::hex")]
+//!     address: u32, // 0x1F00
+//!     #[serde(with = "jsonh_rs::radix::binary")]
+//!     flags: u8, // 0b1010
+//! }
+//! ```
+//! 
+//! Integers can be deserialized from numbers in any radix, or from strings containing numbers in any radix.
+//! Integers are serialized as strings in the adapter's radix (e.g. `"0x1F00"`), since most serializers cannot write numbers in other radixes.
+//! `JsonhSerializer` writes them as numbers instead (e.g. `0x1F00`).
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::Deserializer;
+use serde::Serializer;
+use serde::de::Visitor;
+
+pub(crate) const RADIX_NAME: &str = "$__jsonh_private_Radix";
+
+/// An integer that can be written in any radix.
+pub trait RadixInteger: Copy + Sized {
+    /// Writes the integer in the given radix (2, 8, 10 or 16) with its prefix (e.g. `-0x1F`).
+    fn to_radix_string(self, radix: u32) -> String;
+    /// Parses the integer from digits in the given radix (without a prefix), optionally preceded by a sign.
+    fn from_radix_str(digits: &str, radix: u32) -> Option<Self>;
+}
+
+macro_rules! impl_radix_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl RadixInteger for $integer {
+                fn to_radix_string(self, radix: u32) -> String {
+                    let sign: &str = if self < 0 { "-" } else { "" };
+                    let magnitude = self.unsigned_abs();
+                    return match radix {
+                        16 => format!("{sign}0x{magnitude:X}"),
+                        8 => format!("{sign}0o{magnitude:o}"),
+                        2 => format!("{sign}0b{magnitude:b}"),
+                        _ => format!("{sign}{magnitude}"),
+                    };
+                }
+                fn from_radix_str(digits: &str, radix: u32) -> Option<Self> {
+                    return <$integer>::from_str_radix(digits, radix).ok();
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_integer!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_radix_integer_unsigned {
+    ($($integer:ty),*) => {
+        $(
+            impl RadixInteger for $integer {
+                fn to_radix_string(self, radix: u32) -> String {
+                    return match radix {
+                        16 => format!("0x{self:X}"),
+                        8 => format!("0o{self:o}"),
+                        2 => format!("0b{self:b}"),
+                        _ => format!("{self}"),
+                    };
+                }
+                fn from_radix_str(digits: &str, radix: u32) -> Option<Self> {
+                    return <$integer>::from_str_radix(digits, radix).ok();
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_integer_unsigned!(u8, u16, u32, u64, u128, usize);
+
+/// Serializes and deserializes integers in hexadecimal (e.g. `0x1F`).
+pub mod hex {
+    use super::*;
+
+    /// Serializes the integer as a hexadecimal string.
+    pub fn serialize<T: RadixInteger, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        return super::serialize(*value, 16, serializer);
+    }
+    /// Deserializes the integer from a number or string in any radix.
+    pub fn deserialize<'de, T: RadixInteger, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        return super::deserialize(deserializer);
+    }
+}
+
+/// Serializes and deserializes integers in binary (e.g. `0b1010`).
+pub mod binary {
+    use super::*;
+
+    /// Serializes the integer as a binary string.
+    pub fn serialize<T: RadixInteger, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        return super::serialize(*value, 2, serializer);
+    }
+    /// Deserializes the integer from a number or string in any radix.
+    pub fn deserialize<'de, T: RadixInteger, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        return super::deserialize(deserializer);
+    }
+}
+
+/// Serializes and deserializes integers in octal (e.g. `0o755`).
+pub mod octal {
+    use super::*;
+
+    /// Serializes the integer as an octal string.
+    pub fn serialize<T: RadixInteger, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        return super::serialize(*value, 8, serializer);
+    }
+    /// Deserializes the integer from a number or string in any radix.
+    pub fn deserialize<'de, T: RadixInteger, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        return super::deserialize(deserializer);
+    }
+}
+
+fn serialize<T: RadixInteger, S: Serializer>(value: T, radix: u32, serializer: S) -> Result<S::Ok, S::Error> {
+    // Wrap in newtype so JSONH serializers can write the number without quotes
+    return serializer.serialize_newtype_struct(RADIX_NAME, &value.to_radix_string(radix));
+}
+
+fn deserialize<'de, T: RadixInteger, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    return deserializer.deserialize_any(RadixVisitor { marker: PhantomData });
+}
+
+pub(crate) fn parse<T: RadixInteger>(number: &str) -> Option<T> {
+    let number: String = number.trim().replace('_', "");
+
+    // Sign
+    let (sign, unsigned_number): (&str, &str) = match number.strip_prefix('-') {
+        Some(unsigned_number) => ("-", unsigned_number),
+        None => ("", number.strip_prefix('+').unwrap_or(&number)),
+    };
+
+    // Radix
+    let (radix, digits): (u32, &str) = match unsigned_number.get(..2) {
+        Some("0x" | "0X") => (16, &unsigned_number[2..]),
+        Some("0b" | "0B") => (2, &unsigned_number[2..]),
+        Some("0o" | "0O") => (8, &unsigned_number[2..]),
+        _ => (10, unsigned_number),
+    };
+    if digits.starts_with(['-', '+']) {
+        return None;
+    }
+
+    return T::from_radix_str(&(sign.to_string() + digits), radix);
+}
+
+struct RadixVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: RadixInteger> Visitor<'de> for RadixVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return formatter.write_str("an integer in any radix");
+    }
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        return parse(&value.to_string()).ok_or_else(|| E::custom("Integer out of range"));
+    }
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        return parse(&value.to_string()).ok_or_else(|| E::custom("Integer out of range"));
+    }
+    fn visit_i128<E: serde::de::Error>(self, value: i128) -> Result<Self::Value, E> {
+        return parse(&value.to_string()).ok_or_else(|| E::custom("Integer out of range"));
+    }
+    fn visit_u128<E: serde::de::Error>(self, value: u128) -> Result<Self::Value, E> {
+        return parse(&value.to_string()).ok_or_else(|| E::custom("Integer out of range"));
+    }
+    fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        if value.fract() != 0.0 || !value.is_finite() {
+            return Err(E::custom("Expected integer"));
+        }
+        return parse(&format!("{value:.0}")).ok_or_else(|| E::custom("Integer out of range"));
+    }
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        return parse(value).ok_or_else(|| E::custom("Invalid integer"));
+    }
+}
\ No newline at end of file
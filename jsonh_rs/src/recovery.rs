@@ -0,0 +1,141 @@
+//! Functions for parsing malformed JSONH by skipping past each error, for editors that show every problem at once.
+//!
+//! ```
+//! let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("{ a: 1, b: , c: 3 }", JsonhReaderOptions::new());
+//! // element: {"a": 1, "c": 3}
+//! // diagnostics: [syntax error at 7..12]
+//! ```
+
+use serde_json::Value;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhDiagnostic;
+use crate::JsonhSeverity;
+use crate::JsonhError;
+use crate::JsonTokenType;
+
+/// The maximum number of errors skipped before giving up.
+const MAX_RECOVERIES: usize = 100;
+
+/// Parses a single element, skipping past each syntax error to the next `,` or newline and parsing again.
+/// 
+/// Closing brackets of open structures are never skipped, and a mismatched closing bracket of an outer structure is replaced with the expected one (e.g. `[1, 2}` is read as `[1, 2]`).
+/// Errors with overlapping ranges are reported as a single diagnostic.
+/// 
+/// Returns the best-effort element along with a diagnostic (with the code `syntax`) for each error skipped.
+/// If an error cannot be skipped (e.g. the input ends early), the element read before the error is returned.
+pub fn parse_with_recovery(source: &str, options: JsonhReaderOptions) -> (Value, Vec<JsonhDiagnostic>) {
+    let mut chars: Vec<char> = source.chars().collect();
+    let mut diagnostics: Vec<JsonhDiagnostic> = Vec::new();
+
+    for _ in 0..MAX_RECOVERIES {
+        // Parse element
+        let text: String = chars.iter().collect();
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(&text, options);
+        let error: JsonhError = match reader.parse_element() {
+            Ok(element) => return (element, diagnostics),
+            Err(error) => error,
+        };
+        let position: usize = (reader.char_counter as usize).min(chars.len());
+
+        // Replace mismatched closing bracket (e.g. `[1, 2}`) with the expected one if it closes an outer structure
+        let structures: Vec<Option<char>> = open_structures(&text, options);
+        if let Some(&closer) = chars.get(position) && matches!(closer, '}' | ']') {
+            if let Some(Some(expected)) = structures.last() && *expected != closer && structures.contains(&Some(closer)) {
+                push_diagnostic(&mut diagnostics, error.message, position, position + 1);
+                chars[position] = *expected;
+                continue;
+            }
+        }
+
+        // Skip closing bracket that closes no structure, malformed item (or property), or extra `,`
+        let is_stray_closer: bool = matches!(chars.get(position), Some(&closer) if matches!(closer, '}' | ']') && !structures.contains(&Some(closer)));
+        let skipped: Option<(usize, usize)> = is_stray_closer.then_some((position, position + 1))
+            .or_else(|| find_malformed_item(&chars, position, false))
+            .or_else(|| find_malformed_item(&chars, position, true))
+            .or_else(|| (chars.get(position) == Some(&',')).then_some((position, position + 1)));
+        let Some((start, end)) = skipped.filter(|_| position < chars.len()) else {
+            push_diagnostic(&mut diagnostics, error.message, position, position);
+            return (parse_partial(&chars[..position], options), diagnostics);
+        };
+        push_diagnostic(&mut diagnostics, error.message, start, end);
+
+        // Replace with whitespace to keep positions
+        for next in &mut chars[start..end] {
+            if !matches!(next, '\n' | '\r') {
+                *next = ' ';
+            }
+        }
+    }
+
+    return (parse_partial(&chars, options), diagnostics);
+}
+
+/// Returns the range of the item containing the position, or `None` if it only contains whitespace.
+/// 
+/// An item is delimited by `,`, newlines and the start and end of a structure, and also by `:` unless the whole property is skipped.
+/// When the whole property is skipped, the `,` after it is also skipped.
+/// Closing brackets of structures opened before the item are never skipped.
+fn find_malformed_item(chars: &[char], position: usize, is_whole_property: bool) -> Option<(usize, usize)> {
+    let is_start_delimiter = |next: char| -> bool { matches!(next, ',' | '\n' | '\r' | '[' | '{' | ']' | '}') || (next == ':' && !is_whole_property) };
+
+    // Find start
+    let mut start: usize = position;
+    while start > 0 && !is_start_delimiter(chars[start - 1]) {
+        start -= 1;
+    }
+    // Find end (stopping at closing bracket of outer structure)
+    let mut end: usize = position;
+    let mut depth: usize = 0;
+    while end < chars.len() && !matches!(chars[end], ',' | '\n' | '\r') {
+        match chars[end] {
+            '[' | '{' => depth += 1,
+            ']' | '}' if depth == 0 => break,
+            ']' | '}' => depth -= 1,
+            _ => {},
+        }
+        end += 1;
+    }
+    if is_whole_property && chars.get(end) == Some(&',') {
+        end += 1;
+    }
+
+    if chars[start..end].iter().all(|next| next.is_whitespace()) {
+        return None;
+    }
+    return Some((start, end));
+}
+/// Returns the closing bracket of each structure open where the reader stops, or `None` for a braceless root object.
+fn open_structures(text: &str, options: JsonhReaderOptions) -> Vec<Option<char>> {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(text, options);
+    let mut structures: Vec<Option<char>> = Vec::new();
+    for token_result in reader.read_element() {
+        let Ok(token) = token_result else {
+            break;
+        };
+        match token.json_type {
+            JsonTokenType::StartObject => structures.push(if token.start == token.end { None } else { Some('}') }),
+            JsonTokenType::StartArray => structures.push(Some(']')),
+            JsonTokenType::EndObject | JsonTokenType::EndArray => {
+                structures.pop();
+            },
+            _ => {},
+        }
+    }
+    return structures;
+}
+/// Adds a diagnostic for the error, or extends the previous diagnostic if their ranges overlap (so one mistake is only reported once).
+fn push_diagnostic(diagnostics: &mut Vec<JsonhDiagnostic>, message: String, start: usize, end: usize) {
+    if let Some(previous) = diagnostics.last_mut() && start as u64 <= previous.end && end as u64 >= previous.start {
+        previous.start = previous.start.min(start as u64);
+        previous.end = previous.end.max(end as u64);
+        return;
+    }
+    diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Error, "syntax", message).with_span(start as u64, end as u64));
+}
+/// Parses the incomplete element in the characters, or returns null.
+fn parse_partial(chars: &[char], options: JsonhReaderOptions) -> Value {
+    let text: String = chars.iter().collect();
+    return JsonhReader::parse_element_from_str(&text, options.incomplete_inputs(true)).unwrap_or(Value::Null);
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+//! Functions for checking that JSONH survives being parsed, written and parsed again.
+//!
+//! ```
+//! jsonh_rs::assert_round_trip(include_str!("config.jsonh"), JsonhFormatterOptions::new());
+//! let divergence: Option<JsonhDivergence> = jsonh_rs::check_round_trip("[0x10]", JsonhFormatterOptions::new())?; // None
+//! ```
+
+use serde_json::Value;
+
+use crate::JsonhDivergence;
+use crate::JsonhError;
+use crate::JsonhFormatter;
+use crate::JsonhFormatterOptions;
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+
+/// Parses the source, writes it back (as formatted JSONH and as JSON), parses each output again, and returns the first difference from the original element.
+/// 
+/// Errors if the source or an output cannot be parsed.
+pub fn check_round_trip(source: &str, options: JsonhFormatterOptions) -> Result<Option<JsonhDivergence>, JsonhError> {
+    let reader_options: JsonhReaderOptions = JsonhReaderOptions::new().with_version(options.version);
+    let original: Value = JsonhReader::parse_element_from_str(source, reader_options)?;
+
+    // Formatted JSONH
+    let formatted: String = JsonhFormatter::format(source, options)?;
+    let reparsed: Value = JsonhReader::parse_element_from_str(&formatted, reader_options).map_err(|_| JsonhError::from("Formatted JSONH could not be parsed"))?;
+    if let Some(divergence) = JsonhDivergence::find(&original, &reparsed) {
+        return Ok(Some(divergence));
+    }
+
+    // JSON
+    let json: String = original.to_string();
+    let reparsed: Value = JsonhReader::parse_element_from_str(&json, reader_options).map_err(|_| JsonhError::from("Written JSON could not be parsed"))?;
+    if let Some(divergence) = JsonhDivergence::find(&original, &reparsed) {
+        return Ok(Some(divergence));
+    }
+
+    return Ok(None);
+}
+/// Panics if the source does not survive a round trip (see `check_round_trip`).
+#[track_caller]
+pub fn assert_round_trip(source: &str, options: JsonhFormatterOptions) {
+    match check_round_trip(source, options) {
+        Ok(None) => {},
+        Ok(Some(divergence)) => panic!("JSONH round trip diverged at {divergence}"),
+        Err(error) => panic!("JSONH round trip failed: {error}"),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,136 @@
+//! Functions for comparing and hashing JSONH elements by meaning rather than by formatting.
+//! 
+//! ```
+//! let a: u64 = jsonh_rs::semantic_hash_str("{ a: 0x10 } // comment", JsonhSemanticOptions::new())?;
+//! let b: u64 = jsonh_rs::semantic_hash_str("{\"a\": 16}", JsonhSemanticOptions::new())?;
+//! assert_eq!(a, b);
+//! assert!(jsonh_rs::semantic_eq_str("[1, 2] # comment", "[1.0, 2e0]", JsonhSemanticOptions::new())?);
+//! ```
+
+use serde_json::Value;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhSemanticOptions;
+use crate::JsonhError;
+
+/// The initial state of a 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+/// The multiplier of a 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Computes a hash of the element that is stable across platforms and versions of this crate, for use as a cache key or to detect changes.
+/// 
+/// Numbers are hashed by their value (so `16` and `16.0` have the same hash).
+/// If `ignore_key_order` is enabled, properties are hashed in order of their names.
+pub fn semantic_hash(value: &Value, options: JsonhSemanticOptions) -> u64 {
+    let mut hasher: SemanticHasher = SemanticHasher { state: FNV_OFFSET_BASIS, options: options };
+    hasher.hash_value(value);
+    return hasher.state;
+}
+/// Parses a single element from the source and computes a hash of it (see `semantic_hash`).
+/// 
+/// Comments, whitespace and the spelling of strings and numbers do not affect the hash.
+pub fn semantic_hash_str(source: &str, options: JsonhSemanticOptions) -> Result<u64, JsonhError> {
+    let value: Value = JsonhReader::parse_element_from_str(source, JsonhReaderOptions::new().with_version(options.version))?;
+    return Ok(semantic_hash(&value, options));
+}
+
+/// Returns whether the elements are equal, comparing numbers by their value (so `16` and `16.0` are equal).
+/// 
+/// If `ignore_key_order` is disabled, properties must also be in the same order.
+pub fn semantic_eq(a: &Value, b: &Value, options: JsonhSemanticOptions) -> bool {
+    match (a, b) {
+        (Value::Number(a_number), Value::Number(b_number)) => {
+            return a_number.as_f64() == b_number.as_f64();
+        },
+        (Value::Array(a_items), Value::Array(b_items)) => {
+            return a_items.len() == b_items.len()
+                && a_items.iter().zip(b_items).all(|(a_item, b_item)| semantic_eq(a_item, b_item, options));
+        },
+        (Value::Object(a_properties), Value::Object(b_properties)) => {
+            if a_properties.len() != b_properties.len() {
+                return false;
+            }
+            if !options.ignore_key_order && !a_properties.keys().eq(b_properties.keys()) {
+                return false;
+            }
+            return a_properties.iter().all(|(name, a_value)| {
+                b_properties.get(name).is_some_and(|b_value| semantic_eq(a_value, b_value, options))
+            });
+        },
+        _ => {
+            return a == b;
+        },
+    }
+}
+/// Parses a single element from each source and returns whether they are equal (see `semantic_eq`).
+/// 
+/// Comments, whitespace and the spelling of strings and numbers are ignored.
+/// ```
+/// { a: 0x10, b: 'c' } == {"b": "c", "a": 16}
+/// ```
+pub fn semantic_eq_str(a: &str, b: &str, options: JsonhSemanticOptions) -> Result<bool, JsonhError> {
+    let reader_options: JsonhReaderOptions = JsonhReaderOptions::new().with_version(options.version);
+    let a_value: Value = JsonhReader::parse_element_from_str(a, reader_options)?;
+    let b_value: Value = JsonhReader::parse_element_from_str(b, reader_options)?;
+    return Ok(semantic_eq(&a_value, &b_value, options));
+}
+
+struct SemanticHasher {
+    state: u64,
+    options: JsonhSemanticOptions,
+}
+
+impl SemanticHasher {
+    fn hash_value(&mut self, value: &Value) {
+        match value {
+            Value::Null => {
+                self.write(&[0]);
+            },
+            Value::Bool(boolean) => {
+                self.write(&[1, *boolean as u8]);
+            },
+            Value::Number(number) => {
+                // Hash negative zero as zero since they are equal
+                let float: f64 = number.as_f64().unwrap_or_default() + 0.0;
+                self.write(&[2]);
+                self.write(&float.to_bits().to_le_bytes());
+            },
+            Value::String(string) => {
+                self.write(&[3]);
+                self.hash_str(string);
+            },
+            Value::Array(items) => {
+                self.write(&[4]);
+                self.write(&(items.len() as u64).to_le_bytes());
+                for item in items {
+                    self.hash_value(item);
+                }
+            },
+            Value::Object(properties) => {
+                let mut properties: Vec<(&String, &Value)> = properties.iter().collect();
+                if self.options.ignore_key_order {
+                    properties.sort_by(|a, b| a.0.cmp(b.0));
+                }
+
+                self.write(&[5]);
+                self.write(&(properties.len() as u64).to_le_bytes());
+                for (name, value) in properties {
+                    self.hash_str(name);
+                    self.hash_value(value);
+                }
+            },
+        }
+    }
+    fn hash_str(&mut self, string: &str) {
+        self.write(&(string.len() as u64).to_le_bytes());
+        self.write(string.as_bytes());
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
\ No newline at end of file
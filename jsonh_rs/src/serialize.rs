@@ -0,0 +1,46 @@
+//! Functions for serializing types implementing `Serialize` directly to JSONH, without building a `Value` first.
+//!
+//! ```
+//! let jsonh: String = jsonh_rs::to_string_pretty(&config)?;
+//! ```
+//! ```
+//! {
+//!     "host": "localhost",
+//!     "port": 8080
+//! }
+//! ```
+
+use std::io::Write;
+
+use serde::Serialize;
+
+use crate::JsonhWriter;
+use crate::JsonhWriterOptions;
+use crate::JsonhSerializer;
+use crate::JsonhError;
+
+/// Serializes the value as a single line of JSONH (e.g. `{"a":[1,2]}`).
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, JsonhError> {
+    return to_string_with_options(value, JsonhWriterOptions::new().with_newline(""));
+}
+/// Serializes the value as indented JSONH.
+pub fn to_string_pretty<T: Serialize + ?Sized>(value: &T) -> Result<String, JsonhError> {
+    return to_string_with_options(value, JsonhWriterOptions::new());
+}
+/// Serializes the value as JSONH with the given options.
+pub fn to_string_with_options<T: Serialize + ?Sized>(value: &T, options: JsonhWriterOptions) -> Result<String, JsonhError> {
+    let mut bytes: Vec<u8> = Vec::new();
+    to_writer(&mut bytes, value, options)?;
+    return String::from_utf8(bytes).map_err(|error| JsonhError::from("Invalid UTF-8 in output").with_cause(error));
+}
+/// Serializes the value as JSONH with the given options to a byte writer, such as a file.
+pub fn to_writer<W: Write, T: Serialize + ?Sized>(writer: W, value: &T, options: JsonhWriterOptions) -> Result<(), JsonhError> {
+    let mut writer: JsonhWriter<W> = JsonhWriter::new(writer, options);
+    let result: Result<(), JsonhError> = value.serialize(&mut JsonhSerializer::new(&mut writer));
+
+    // Keep I/O error
+    return result.map_err(|error| match writer.error.take() {
+        Some(io_error) => error.with_cause(io_error),
+        None => error,
+    });
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+
+use crate::JsonhLineIndex;
+
+/// A deserialized value along with the range of characters it was read from.
+/// 
+/// ```
+/// {
+///     port: 99999
+/// }
+/// ```
+/// ```
+/// struct Config {
+///     port: Spanned<u32>, // start: 12, end: 17, value: 99999
+/// }
+/// ```
+/// 
+/// Spans are only captured by `JsonhDeserializer`. Other deserializers will fail to deserialize a `Spanned`.
+/// Serializing a `Spanned` serializes the value only.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Spanned<T> {
+    /// The number of characters in the source before the start of the value.
+    pub start: u64,
+    /// The number of characters in the source before the end of the value.
+    pub end: u64,
+    /// The value itself.
+    pub value: T,
+}
+
+impl<T> Spanned<T> {
+    /// Constructs a value with the given range of characters.
+    pub fn new(start: u64, end: u64, value: T) -> Self {
+        return Self { start: start, end: end, value: value };
+    }
+    /// Returns the value, discarding the span.
+    pub fn into_inner(self) -> T {
+        return self.value;
+    }
+    /// Returns the line and column numbers (starting from 1) of the start of the value in the source it was read from.
+    /// 
+    /// ```
+    /// let (line, column): (u64, u64) = config.port.location(source);
+    /// eprintln!("invalid port at config.jsonh:{line}:{column}");
+    /// ```
+    pub fn location(&self, source: &str) -> (u64, u64) {
+        return char_location(source, self.start);
+    }
+}
+
+/// Returns the line and column numbers (starting from 1) of the character position in the source.
+pub(crate) fn char_location(source: &str, position: u64) -> (u64, u64) {
+    return JsonhLineIndex::new(source).location(position);
+}
+
+/// Returns the byte offset of the character position in the source.
+pub(crate) fn char_offset(source: &str, position: u64) -> usize {
+    return source.char_indices().nth(position as usize).map_or(source.len(), |(offset, _)| offset);
+}
+
+pub(crate) const SPANNED_NAME: &str = "$__jsonh_private_Spanned";
+pub(crate) const SPANNED_VALUE_FIELD: &str = "$__jsonh_private_value";
+pub(crate) const SPANNED_START_FIELD: &str = "$__jsonh_private_start";
+pub(crate) const SPANNED_END_FIELD: &str = "$__jsonh_private_end";
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Spanned<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return deserializer.deserialize_struct(SPANNED_NAME, &[SPANNED_VALUE_FIELD, SPANNED_START_FIELD, SPANNED_END_FIELD], SpannedVisitor { marker: PhantomData });
+    }
+}
+
+impl<T: Serialize> Serialize for Spanned<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return self.value.serialize(serializer);
+    }
+}
+
+struct SpannedVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SpannedVisitor<T> {
+    type Value = Spanned<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return formatter.write_str("a spanned value");
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        // Value
+        if map.next_key::<&str>()? != Some(SPANNED_VALUE_FIELD) {
+            return Err(serde::de::Error::custom("Expected spanned value"));
+        }
+        let value: T = map.next_value()?;
+
+        // Start
+        if map.next_key::<&str>()? != Some(SPANNED_START_FIELD) {
+            return Err(serde::de::Error::custom("Expected start of spanned value"));
+        }
+        let start: u64 = map.next_value()?;
+
+        // End
+        if map.next_key::<&str>()? != Some(SPANNED_END_FIELD) {
+            return Err(serde::de::Error::custom("Expected end of spanned value"));
+        }
+        let end: u64 = map.next_value()?;
+
+        return Ok(Spanned::new(start, end, value));
+    }
+}
\ No newline at end of file
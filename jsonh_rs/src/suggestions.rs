@@ -0,0 +1,51 @@
+//! Functions for suggesting corrections to likely typos.
+//!
+//! ```
+//! assert_eq!(jsonh_rs::suggest_literal("flase"), Some("false"));
+//! ```
+
+/// The literals that typos are checked against.
+const LITERALS: &[&str] = &["true", "false", "null"];
+
+/// Returns the literal (`true`, `false` or `null`) that the quoteless string is likely a misspelling of.
+/// 
+/// A string is a likely misspelling if it differs only in case or by a single inserted, deleted, replaced or swapped character (e.g. `ture`, `Null`, `fals`).
+pub fn suggest_literal(value: &str) -> Option<&'static str> {
+    return suggest_literal_from(value, LITERALS);
+}
+/// Returns the literal from the given literals that the quoteless string is likely a misspelling of.
+/// 
+/// Use this when only some literals are allowed in context (e.g. only `null` for a unit):
+/// ```
+/// assert_eq!(jsonh_rs::suggest_literal_from("nul", &["true", "false"]), None);
+/// assert_eq!(jsonh_rs::suggest_literal_from("nul", &["null"]), Some("null"));
+/// ```
+pub fn suggest_literal_from(value: &str, literals: &[&'static str]) -> Option<&'static str> {
+    let lowercase: String = value.to_lowercase();
+    return literals.iter().copied().find(|literal| value != *literal && (lowercase == *literal || edit_distance(&lowercase, literal) <= 1));
+}
+
+/// Returns the number of inserted, deleted, replaced or swapped characters between the strings (optimal string alignment distance).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<Vec<usize>> = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost: usize = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            distances[i][j] = (distances[i - 1][j] + 1).min(distances[i][j - 1] + 1).min(distances[i - 1][j - 1] + cost);
+            // Swapped characters
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distances[i][j] = distances[i][j].min(distances[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    return distances[a.len()][b.len()];
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+//! Functions for writing tokens in a stable, human-readable format for snapshot testing, and reading them back.
+//!
+//! Each token is written on its own line with its span, type, value (as a JSON string) and whether it is escaped:
+//! ```
+//! 0..1 StartObject
+//! 2..3 PropertyName "a"
+//! 5..8 String "b\n" escaped
+//! 9..10 EndObject
+//! ```
+//! Values are omitted if empty, except for strings, property names and comments.
+//!
+//! ```
+//! let dump: String = jsonh_rs::dump_tokens_from_str("{ a: 1 }", JsonhReaderOptions::new())?;
+//! assert_eq!(dump, include_str!("snapshots/a.tokens"));
+//! ```
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhToken;
+use crate::JsonTokenType;
+
+/// Writes each token on its own line (see the module documentation).
+pub fn dump_tokens(tokens: &[JsonhToken]) -> String {
+    let mut dump: String = String::new();
+    for token in tokens {
+        dump += &format!("{}..{} {}", token.start, token.end, token_type_name(token.json_type));
+        // Value
+        if !token.value.is_empty() || matches!(token.json_type, JsonTokenType::PropertyName | JsonTokenType::String | JsonTokenType::Comment) {
+            dump.push(' ');
+            dump += &serde_json::to_string(&token.value).unwrap_or_default();
+        }
+        // Escaped
+        if token.is_escaped {
+            dump += " escaped";
+        }
+        dump.push('\n');
+    }
+    return dump;
+}
+/// Reads every token from the source and writes each token on its own line (see `dump_tokens`).
+/// 
+/// Comments and whitespace after the element are included. Errors if the source cannot be read.
+pub fn dump_tokens_from_str(source: &str, options: JsonhReaderOptions) -> Result<String, &'static str> {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(source, options);
+    let mut tokens: Vec<JsonhToken> = Vec::new();
+
+    // Read element
+    for token_result in reader.read_element().raw() {
+        tokens.push(token_result?);
+    }
+    // Read end of elements
+    for token_result in reader.read_end_of_elements().raw() {
+        tokens.push(token_result?);
+    }
+
+    return Ok(dump_tokens(&tokens));
+}
+/// Reads tokens written by `dump_tokens`.
+/// 
+/// Empty lines are ignored.
+pub fn parse_token_dump(dump: &str) -> Result<Vec<JsonhToken>, &'static str> {
+    let mut tokens: Vec<JsonhToken> = Vec::new();
+
+    for line in dump.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // Span
+        let (span, rest) = line.split_once(' ').ok_or("Expected token type in token dump")?;
+        let (start, end) = span.split_once("..").ok_or("Expected `..` in token span")?;
+        let start: u64 = start.parse().map_err(|_| "Invalid token span start")?;
+        let end: u64 = end.parse().map_err(|_| "Invalid token span end")?;
+
+        // Type
+        let (type_name, mut rest) = rest.split_once(' ').unwrap_or((rest, ""));
+        let json_type: JsonTokenType = parse_token_type_name(type_name).ok_or("Invalid token type in token dump")?;
+
+        // Value
+        let mut value: String = String::new();
+        if rest.starts_with('"') {
+            let mut values: serde_json::StreamDeserializer<'_, serde_json::de::StrRead<'_>, String> = serde_json::Deserializer::from_str(rest).into_iter();
+            value = values.next().ok_or("Expected token value")?.map_err(|_| "Invalid token value in token dump")?;
+            rest = rest[values.byte_offset()..].trim_start();
+        }
+
+        // Escaped
+        let is_escaped: bool = match rest {
+            "" => false,
+            "escaped" => true,
+            _ => return Err("Unexpected text after token in token dump"),
+        };
+
+        tokens.push(JsonhToken::new(json_type, value).with_span(start, end).with_escaped(is_escaped));
+    }
+
+    return Ok(tokens);
+}
+
+fn token_type_name(json_type: JsonTokenType) -> &'static str {
+    match json_type {
+        JsonTokenType::None => return "None",
+        JsonTokenType::StartObject => return "StartObject",
+        JsonTokenType::EndObject => return "EndObject",
+        JsonTokenType::StartArray => return "StartArray",
+        JsonTokenType::EndArray => return "EndArray",
+        JsonTokenType::PropertyName => return "PropertyName",
+        JsonTokenType::Comment => return "Comment",
+        JsonTokenType::String => return "String",
+        JsonTokenType::Number => return "Number",
+        JsonTokenType::True => return "True",
+        JsonTokenType::False => return "False",
+        JsonTokenType::Null => return "Null",
+    }
+}
+fn parse_token_type_name(name: &str) -> Option<JsonTokenType> {
+    match name {
+        "None" => return Some(JsonTokenType::None),
+        "StartObject" => return Some(JsonTokenType::StartObject),
+        "EndObject" => return Some(JsonTokenType::EndObject),
+        "StartArray" => return Some(JsonTokenType::StartArray),
+        "EndArray" => return Some(JsonTokenType::EndArray),
+        "PropertyName" => return Some(JsonTokenType::PropertyName),
+        "Comment" => return Some(JsonTokenType::Comment),
+        "String" => return Some(JsonTokenType::String),
+        "Number" => return Some(JsonTokenType::Number),
+        "True" => return Some(JsonTokenType::True),
+        "False" => return Some(JsonTokenType::False),
+        "Null" => return Some(JsonTokenType::Null),
+        _ => return None,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,182 @@
+//! Functions for converting JSONH to another format by passing each element from a deserializer directly to a serializer, in constant memory.
+//!
+//! ```
+//! let mut reader: JsonhReader = JsonhReader::from_read(File::open("export.jsonh")?, JsonhReaderOptions::new());
+//! jsonh_rs::transcode_to_json(&mut reader, BufWriter::new(File::create("export.json")?))?;
+//! ```
+//!
+//! `JsonhDeserializer` only relies on `deserialize_any` for untyped data, so it can also be passed to `serde_transcode::transcode`.
+
+use std::cell::Cell;
+use std::fmt;
+use std::io::Write;
+
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+use serde::de;
+use serde::de::DeserializeSeed;
+use serde::de::MapAccess;
+use serde::de::SeqAccess;
+use serde::de::Visitor;
+use serde::ser;
+use serde::ser::SerializeMap;
+use serde::ser::SerializeSeq;
+
+use crate::JsonhReader;
+use crate::JsonhDeserializer;
+use crate::JsonhError;
+
+/// Passes the next element from the deserializer to the serializer without building a `Value`.
+/// 
+/// Errors from the deserializer are converted to errors of the serializer.
+pub fn transcode<'de, D: Deserializer<'de>, S: Serializer>(deserializer: D, serializer: S) -> Result<S::Ok, S::Error> {
+    return Transcoder { deserializer: Cell::new(Some(deserializer)) }.serialize(serializer);
+}
+/// Writes the next JSONH element from the reader as compact JSON to a byte writer, ensuring that no other elements follow it.
+/// 
+/// Comments are discarded, and numbers too large for `f64` (e.g. `1e400`) are rejected rather than written as `null`.
+pub fn transcode_to_json<W: Write>(reader: &mut JsonhReader<'_>, writer: W) -> Result<(), JsonhError> {
+    let mut serializer: serde_json::Serializer<W> = serde_json::Serializer::new(writer);
+    let result: Result<(), JsonhError> = {
+        let mut deserializer: JsonhDeserializer<'_> = JsonhDeserializer::new(reader);
+        deserializer.peek_start().and_then(|start| transcode(&mut deserializer, &mut serializer).map_err(|error| {
+            // Return error from deserializer unchanged rather than its message
+            match deserializer.first_error.take() {
+                Some(first_error) => first_error,
+                None => deserializer.add_context(JsonhError::new(error.to_string()).with_cause(error), start),
+            }
+        }))
+    };
+
+    // Locate syntax errors where the reader stopped
+    result.map_err(|error| match error.position {
+        Some(_) => error,
+        None => error.with_position(reader.char_counter).with_location(reader.line_counter, reader.column_counter),
+    })?;
+
+    // Ensure exactly one element
+    for token_result in reader.read_end_of_elements() {
+        token_result?;
+    }
+
+    return Ok(());
+}
+
+/// Serializes the element read from a deserializer (can only be serialized once).
+struct Transcoder<D> {
+    deserializer: Cell<Option<D>>,
+}
+
+impl<'de, D: Deserializer<'de>> Serialize for Transcoder<D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let Some(deserializer) = self.deserializer.take() else {
+            return Err(ser::Error::custom("Element already transcoded"));
+        };
+        return deserializer.deserialize_any(TranscodeVisitor { serializer: serializer }).map_err(ser::Error::custom);
+    }
+}
+
+/// Passes each visited value to a serializer.
+struct TranscodeVisitor<S> {
+    serializer: S,
+}
+
+impl<'de, S: Serializer> Visitor<'de> for TranscodeVisitor<S> {
+    type Value = S::Ok;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        return formatter.write_str("any value");
+    }
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<S::Ok, E> {
+        return self.serializer.serialize_bool(value).map_err(de::Error::custom);
+    }
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<S::Ok, E> {
+        return self.serializer.serialize_i64(value).map_err(de::Error::custom);
+    }
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<S::Ok, E> {
+        return self.serializer.serialize_u64(value).map_err(de::Error::custom);
+    }
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<S::Ok, E> {
+        return self.serializer.serialize_i128(value).map_err(de::Error::custom);
+    }
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<S::Ok, E> {
+        return self.serializer.serialize_u128(value).map_err(de::Error::custom);
+    }
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<S::Ok, E> {
+        // Infinity and NaN (which JSON serializers may write as null)
+        if !value.is_finite() {
+            return Err(E::custom("Infinity and NaN are not supported"));
+        }
+        return self.serializer.serialize_f64(value).map_err(de::Error::custom);
+    }
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<S::Ok, E> {
+        return self.serializer.serialize_str(value).map_err(de::Error::custom);
+    }
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<S::Ok, E> {
+        return self.serializer.serialize_bytes(value).map_err(de::Error::custom);
+    }
+    fn visit_none<E: de::Error>(self) -> Result<S::Ok, E> {
+        return self.serializer.serialize_none().map_err(de::Error::custom);
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Ok, D::Error> {
+        return self.serializer.serialize_some(&Transcoder { deserializer: Cell::new(Some(deserializer)) }).map_err(de::Error::custom);
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<S::Ok, E> {
+        return self.serializer.serialize_unit().map_err(de::Error::custom);
+    }
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<S::Ok, D::Error> {
+        return transcode(deserializer, self.serializer).map_err(de::Error::custom);
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<S::Ok, A::Error> {
+        let mut serialize_seq: S::SerializeSeq = self.serializer.serialize_seq(seq.size_hint()).map_err(de::Error::custom)?;
+        while seq.next_element_seed(ItemSeed { serialize_seq: &mut serialize_seq })?.is_some() {}
+        return serialize_seq.end().map_err(de::Error::custom);
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<S::Ok, A::Error> {
+        let mut serialize_map: S::SerializeMap = self.serializer.serialize_map(map.size_hint()).map_err(de::Error::custom)?;
+        while map.next_key_seed(KeySeed { serialize_map: &mut serialize_map })?.is_some() {
+            map.next_value_seed(ValueSeed { serialize_map: &mut serialize_map })?;
+        }
+        return serialize_map.end().map_err(de::Error::custom);
+    }
+}
+
+/// Passes an item of an array to a sequence serializer.
+struct ItemSeed<'s, S> {
+    serialize_seq: &'s mut S,
+}
+
+impl<'de, S: SerializeSeq> DeserializeSeed<'de> for ItemSeed<'_, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        return self.serialize_seq.serialize_element(&Transcoder { deserializer: Cell::new(Some(deserializer)) }).map_err(de::Error::custom);
+    }
+}
+
+/// Passes a property name to a map serializer.
+struct KeySeed<'s, S> {
+    serialize_map: &'s mut S,
+}
+
+impl<'de, S: SerializeMap> DeserializeSeed<'de> for KeySeed<'_, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        return self.serialize_map.serialize_key(&Transcoder { deserializer: Cell::new(Some(deserializer)) }).map_err(de::Error::custom);
+    }
+}
+
+/// Passes a property value to a map serializer.
+struct ValueSeed<'s, S> {
+    serialize_map: &'s mut S,
+}
+
+impl<'de, S: SerializeMap> DeserializeSeed<'de> for ValueSeed<'_, S> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        return self.serialize_map.serialize_value(&Transcoder { deserializer: Cell::new(Some(deserializer)) }).map_err(de::Error::custom);
+    }
+}
\ No newline at end of file
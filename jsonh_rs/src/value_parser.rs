@@ -0,0 +1,83 @@
+//! A value parser for command-line arguments containing inline JSONH.
+//! 
+//! ```
+//! #[derive(clap::Parser)]
+//! struct Cli {
+//!     // --overrides 'retries: 3, hosts: [a, b]'
+//!     #[arg(long, value_parser = jsonh_rs::value_parser::parse::<Overrides>)]
+//!     overrides: Overrides,
+//! }
+//! ```
+//! 
+//! Clap accepts any function from `&str` to a `Result` whose error implements `std::error::Error`, so no clap dependency is needed.
+//! With the `clap` feature, `JsonhValueParser` can be used instead to report errors in clap's format and to set the reader options:
+//! 
+//! ```
+//! #[arg(long, value_parser = jsonh_rs::value_parser::JsonhValueParser::<Overrides>::new(JsonhReaderOptions::new()))]
+//! overrides: Overrides,
+//! ```
+
+use serde::de::DeserializeOwned;
+
+use crate::JsonhReader;
+use crate::JsonhReaderOptions;
+use crate::JsonhError;
+
+/// Deserializes a single JSONH element from a command-line argument.
+/// 
+/// Braceless objects are supported, so objects can be written without braces (e.g. `retries: 3, hosts: [a, b]`).
+/// Errors include the character position in the argument.
+pub fn parse<T: DeserializeOwned>(arg: &str) -> Result<T, JsonhError> {
+    return parse_with_options(arg, JsonhReaderOptions::new());
+}
+/// Deserializes a single JSONH element from a command-line argument with the given options.
+/// 
+/// See `parse`.
+pub fn parse_with_options<T: DeserializeOwned>(arg: &str, options: JsonhReaderOptions) -> Result<T, JsonhError> {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(arg, options);
+
+    let result: Result<T, JsonhError> = crate::from_reader(&mut reader);
+    return result.map_err(|error| {
+        // Position of error (or end of reading if not located)
+        let position: u64 = error.position.unwrap_or(reader.char_counter);
+        return JsonhError { message: format!("Invalid JSONH argument at character {position}: {}", error.message), position: Some(position), ..error };
+    });
+}
+
+/// A clap value parser deserializing a single JSONH element from a command-line argument (requires the `clap` feature).
+/// 
+/// See `parse`.
+#[cfg(feature = "clap")]
+pub struct JsonhValueParser<T> {
+    /// The options to use when reading the argument.
+    pub options: JsonhReaderOptions,
+    /// The type of element to deserialize.
+    element_type: std::marker::PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "clap")]
+impl<T> JsonhValueParser<T> {
+    /// Constructs a value parser reading arguments with the given options.
+    pub fn new(options: JsonhReaderOptions) -> Self {
+        return Self { options: options, element_type: std::marker::PhantomData };
+    }
+}
+
+#[cfg(feature = "clap")]
+impl<T> Clone for JsonhValueParser<T> {
+    fn clone(&self) -> Self {
+        return Self::new(self.options);
+    }
+}
+
+#[cfg(feature = "clap")]
+impl<T: DeserializeOwned + Clone + Send + Sync + 'static> clap::builder::TypedValueParser for JsonhValueParser<T> {
+    type Value = T;
+
+    fn parse_ref(&self, command: &clap::Command, _arg: Option<&clap::Arg>, value: &std::ffi::OsStr) -> Result<T, clap::Error> {
+        let Some(arg) = value.to_str() else {
+            return Err(clap::Error::new(clap::error::ErrorKind::InvalidUtf8).with_cmd(command));
+        };
+        return parse_with_options(arg, self.options).map_err(|error| clap::Error::raw(clap::error::ErrorKind::ValueValidation, format!("{}\n", error.message)).with_cmd(command));
+    }
+}
\ No newline at end of file
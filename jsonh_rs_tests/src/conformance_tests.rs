@@ -0,0 +1,146 @@
+use jsonh_rs::*;
+use std::path::{Path, PathBuf};
+
+/// The environment variable containing the directory of the shared JSONH conformance tests.
+/// 
+/// Each `.json` file in the directory is a suite containing an array of groups, each with a description, reader options and test cases:
+/// ```
+/// [
+///     {
+///         "description": "Verbatim strings",
+///         "options": { "version": 2 },
+///         "tests": [
+///             { "description": "Backslashes are not escapes", "jsonh": "@'a\\b'", "valid": true, "json": "a\\b" },
+///             { "description": "Unterminated string", "jsonh": "@'a", "valid": false },
+///             { "description": "V1 reads quoteless string", "jsonh": "@a", "options": { "version": 1 }, "valid": true, "json": "@a" }
+///         ]
+///     }
+/// ]
+/// ```
+/// 
+/// Valid cases must parse to the `json` element (with numbers compared by value), and invalid cases must fail to parse.
+/// Options of a case override the options of its group. Cases with options not supported by this crate are skipped.
+const CONFORMANCE_DIRECTORY_VARIABLE: &str = "JSONH_CONFORMANCE_DIR";
+
+#[test]
+pub fn conformance_test() {
+    let directory: PathBuf = match std::env::var_os(CONFORMANCE_DIRECTORY_VARIABLE) {
+        Some(directory) => PathBuf::from(directory),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join("conformance"),
+    };
+
+    // Find test suites
+    let mut suite_paths: Vec<PathBuf> = Vec::new();
+    find_suites(&directory, &mut suite_paths);
+    suite_paths.sort();
+    assert!(!suite_paths.is_empty(), "No conformance tests found in {}", directory.display());
+
+    // Run test cases
+    let mut case_count: usize = 0;
+    let mut skipped_count: usize = 0;
+    let mut failures: Vec<String> = Vec::new();
+    for suite_path in &suite_paths {
+        let suite_name: String = suite_path.strip_prefix(&directory).unwrap_or(suite_path).display().to_string();
+        let suite: Value = std::fs::read_to_string(suite_path).map_err(|error| error.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|error| error.to_string()))
+            .unwrap_or_else(|error| panic!("Failed to read {suite_name}: {error}"));
+
+        for group in suite.as_array().unwrap_or_else(|| panic!("Expected array of groups in {suite_name}")) {
+            for case in group["tests"].as_array().unwrap_or_else(|| panic!("Expected array of tests in {suite_name}")) {
+                let name: String = format!("{suite_name}: {} / {}", group["description"].as_str().unwrap_or_default(), case["description"].as_str().unwrap_or_default());
+                case_count += 1;
+
+                // Report result of each case
+                match run_case(group, case) {
+                    Ok(true) => println!("PASS {name}"),
+                    Ok(false) => {
+                        println!("SKIP {name}");
+                        skipped_count += 1;
+                    },
+                    Err(message) => {
+                        println!("FAIL {name}: {message}");
+                        failures.push(format!("{name}: {message}"));
+                    },
+                }
+            }
+        }
+    }
+    println!("{} passed, {} failed, {skipped_count} skipped", case_count - failures.len() - skipped_count, failures.len());
+
+    assert!(failures.is_empty(), "{} of {case_count} conformance tests failed:\n{}", failures.len(), failures.join("\n"));
+}
+
+fn find_suites(directory: &Path, suite_paths: &mut Vec<PathBuf>) -> () {
+    let entries: std::fs::ReadDir = std::fs::read_dir(directory).unwrap_or_else(|error| panic!("Failed to read {}: {error}", directory.display()));
+    for entry in entries {
+        let path: PathBuf = entry.unwrap().path();
+        if path.is_dir() {
+            find_suites(&path, suite_paths);
+        }
+        else if path.extension().is_some_and(|extension| extension == "json") {
+            suite_paths.push(path);
+        }
+    }
+}
+
+/// Runs the case, returning whether it was run (rather than skipped).
+fn run_case(group: &Value, case: &Value) -> Result<bool, String> {
+    let Some(jsonh) = case["jsonh"].as_str() else {
+        return Err("expected `jsonh` string".to_string());
+    };
+    let Some(valid) = case["valid"].as_bool() else {
+        return Err("expected `valid` boolean".to_string());
+    };
+
+    // Options (of group, then of case)
+    let mut options: JsonhReaderOptions = JsonhReaderOptions::new();
+    for options_value in [&group["options"], &case["options"]] {
+        let Some(options_object) = options_value.as_object() else {
+            continue;
+        };
+        for (name, value) in options_object {
+            match apply_option(options, name, value) {
+                Some(new_options) => options = new_options,
+                None => return Ok(false),
+            }
+        }
+    }
+
+    let result: Result<Value, JsonhError> = JsonhReader::parse_element_from_str(jsonh, options);
+
+    // Valid case
+    if valid {
+        let expected: Value = normalize_numbers(case["json"].clone());
+        let actual: Value = result.map_err(|error| format!("expected {expected}, got error: {error}"))?;
+        if actual != expected {
+            return Err(format!("expected {expected}, got {actual}"));
+        }
+    }
+    // Invalid case
+    else if let Ok(actual) = result {
+        return Err(format!("expected error, got {actual}"));
+    }
+    return Ok(true);
+}
+
+/// Applies the reader option, or returns `None` if it is not supported.
+fn apply_option(options: JsonhReaderOptions, name: &str, value: &Value) -> Option<JsonhReaderOptions> {
+    return match (name, value) {
+        ("version", Value::Number(number)) => Some(options.with_version(JsonhVersion::from_number(number.as_u64()? as u32))),
+        ("version", Value::String(version)) => Some(options.with_version(version.parse().ok()?)),
+        ("parseSingleElement", Value::Bool(enabled)) => Some(options.with_parse_single_element(*enabled)),
+        ("incompleteInputs", Value::Bool(enabled)) => Some(options.incomplete_inputs(*enabled)),
+        ("maxDepth", Value::Number(depth)) => Some(options.with_max_depth(depth.as_i64()? as i32)),
+        _ => None,
+    };
+}
+
+fn normalize_numbers(value: Value) -> Value {
+    // Parsed numbers are always floating point
+    return match value {
+        Value::Number(number) => number.as_f64().and_then(serde_json::Number::from_f64).map_or(Value::Null, Value::Number),
+        Value::Array(items) => Value::Array(items.into_iter().map(normalize_numbers).collect()),
+        Value::Object(properties) => Value::Object(properties.into_iter().map(|(name, value)| (name, normalize_numbers(value))).collect()),
+        other => other,
+    };
+}
\ No newline at end of file
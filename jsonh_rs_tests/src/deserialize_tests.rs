@@ -0,0 +1,509 @@
+use jsonh_rs::*;
+use serde::Deserialize;
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Server {
+    host: Commented<String>,
+    port: Commented<u16>,
+    tags: Vec<Commented<String>>,
+    backup: Option<String>,
+}
+
+#[test]
+pub fn commented_test() {
+    let jsonh: &str = r#"
+// The server to connect to.
+{
+    # The host name.
+    host: localhost
+    /* The port
+       to listen on. */
+    port: 8080
+    tags: [
+        // first
+        a
+        b
+    ]
+    backup: null
+}
+"#;
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let server: Commented<Server> = Commented::deserialize(&mut JsonhDeserializer::new(&mut reader)).unwrap();
+
+    assert_eq!(server.comments, ["The server to connect to."]);
+    assert_eq!(server.value.host, Commented::new(vec!["The host name.".to_string()], "localhost".to_string()));
+    assert_eq!(server.value.port, Commented::new(vec!["The port\n       to listen on.".to_string()], 8080));
+    assert_eq!(server.value.tags, [Commented::new(vec!["first".to_string()], "a".to_string()), Commented::new(Vec::new(), "b".to_string())]);
+    assert_eq!(server.value.backup, None);
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Listener {
+    port: Spanned<u32>,
+    hosts: Spanned<Vec<String>>,
+}
+
+#[test]
+pub fn spanned_test() {
+    let jsonh: &str = "{\n  port: 99999\n  hosts: ['a', \"b\"] # hosts\n}";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let listener: Spanned<Listener> = Spanned::deserialize(&mut JsonhDeserializer::new(&mut reader)).unwrap();
+
+    assert_eq!((listener.start, listener.end), (0, 45));
+    assert_eq!(listener.value.port, Spanned::new(10, 15, 99999));
+    assert_eq!(listener.value.port.location(jsonh), (2, 9));
+    assert_eq!((listener.value.hosts.start, listener.value.hosts.end), (25, 35));
+    assert_eq!(listener.value.hosts.location(jsonh), (3, 10));
+}
+
+#[derive(serde::Serialize, Deserialize, PartialEq, Debug)]
+struct Register {
+    #[serde(with = "jsonh_rs::radix::hex")]
+    address: u32,
+    #[serde(with = "jsonh_rs::radix::binary")]
+    flags: u8,
+    #[serde(with = "jsonh_rs::radix::octal")]
+    mode: i16,
+}
+
+#[test]
+pub fn radix_test() {
+    let jsonh: &str = "{address: 0x1F_00, flags: 0b1010, mode: '-0o755'}";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let register: Register = Register::deserialize(&mut JsonhDeserializer::new(&mut reader)).unwrap();
+
+    assert_eq!(register, Register { address: 0x1F00, flags: 0b1010, mode: -0o755 });
+    assert_eq!(serde_json::to_string(&register).unwrap(), r#"{"address":"0x1F00","flags":"0b1010","mode":"-0o755"}"#);
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{address: -1, flags: 0x100, mode: 1.5}", JsonhReaderOptions::new());
+    assert!(Register::deserialize(&mut JsonhDeserializer::new(&mut reader)).is_err());
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Attachment {
+    #[serde(with = "jsonh_rs::bytes")]
+    data: Vec<u8>,
+}
+
+#[test]
+pub fn bytes_test() {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{data: SGVsbG8=}", JsonhReaderOptions::new());
+    let attachment: Attachment = Attachment::deserialize(&mut JsonhDeserializer::new(&mut reader)).unwrap();
+    assert_eq!(attachment.data, b"Hello");
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{data: '48656C6c6f'}", JsonhReaderOptions::new());
+    let attachment: Attachment = Attachment::deserialize(&mut JsonhDeserializer::new(&mut reader).with_bytes_encoding(JsonhBytesEncoding::Hex)).unwrap();
+    assert_eq!(attachment.data, b"Hello");
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{data: [1, 2, 255]}", JsonhReaderOptions::new());
+    let attachment: Attachment = Attachment::deserialize(&mut JsonhDeserializer::new(&mut reader)).unwrap();
+    assert_eq!(attachment.data, [1, 2, 255]);
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{data: 'SGVsbG8*'}", JsonhReaderOptions::new());
+    assert!(Attachment::deserialize(&mut JsonhDeserializer::new(&mut reader)).is_err());
+
+    for length in 0..8 {
+        let bytes: Vec<u8> = (0..length).map(|index| (index * 37 + 200) as u8).collect();
+        for encoding in [JsonhBytesEncoding::Base64, JsonhBytesEncoding::Hex] {
+            assert_eq!(encoding.decode(&encoding.encode(&bytes)), Ok(bytes.clone()));
+        }
+    }
+    assert_eq!(JsonhBytesEncoding::Base64.encode(b"Hi"), "SGk=");
+    assert_eq!(JsonhBytesEncoding::Base64.decode("-_8"), Ok(vec![0xFB, 0xFF]));
+}
+
+#[derive(Deserialize, PartialEq, Clone, Debug)]
+struct Overrides {
+    retries: u32,
+    hosts: Vec<String>,
+}
+
+#[test]
+pub fn value_parser_test() {
+    let overrides: Overrides = jsonh_rs::value_parser::parse("retries: 3, hosts: [a, b]").unwrap();
+    assert_eq!(overrides, Overrides { retries: 3, hosts: vec!["a".to_string(), "b".to_string()] });
+
+    let error: JsonhError = jsonh_rs::value_parser::parse::<Overrides>("retries: 3, hosts: [a, b").unwrap_err();
+    assert!(error.message.starts_with("Invalid JSONH argument at character 24: "));
+    assert!(jsonh_rs::value_parser::parse::<u32>("1 2").is_err());
+
+    // Position of deserialization error
+    let error: JsonhError = jsonh_rs::value_parser::parse::<Overrides>("retries: three, hosts: []").unwrap_err();
+    assert_eq!(error.position, Some(9));
+    assert!(error.message.starts_with("Invalid JSONH argument at character 9: "));
+
+    // Clap
+    use clap::builder::TypedValueParser;
+    let command: clap::Command = clap::Command::new("test");
+    let parser: jsonh_rs::value_parser::JsonhValueParser<Overrides> = jsonh_rs::value_parser::JsonhValueParser::new(JsonhReaderOptions::new());
+    assert_eq!(parser.parse_ref(&command, None, std::ffi::OsStr::new("retries: 3, hosts: [a]")).unwrap(), Overrides { retries: 3, hosts: vec!["a".to_string()] });
+    let error: clap::Error = parser.parse_ref(&command, None, std::ffi::OsStr::new("retries: 3, hosts: [a")).unwrap_err();
+    assert_eq!(error.kind(), clap::error::ErrorKind::ValueValidation);
+    assert!(error.to_string().contains("Invalid JSONH argument at character 21: "));
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Config {
+    name: String,
+    port: u16,
+    ratio: f32,
+    enabled: bool,
+    owner: Option<String>,
+    limits: std::collections::BTreeMap<String, i64>,
+    point: (i32, i32),
+    unit: (),
+    initial: char,
+}
+
+#[test]
+pub fn from_str_test() {
+    let config: Config = jsonh_rs::from_str(r#"
+// Server configuration
+name: my server
+port: 0x1F90
+ratio: 0.5
+enabled: true
+owner: null
+limits: { memory: -1, cpu: 4 }
+point: [1, -2]
+unit: null
+initial: m
+"#).unwrap();
+    assert_eq!(config, Config {
+        name: "my server".to_string(),
+        port: 8080,
+        ratio: 0.5,
+        enabled: true,
+        owner: None,
+        limits: [("memory".to_string(), -1), ("cpu".to_string(), 4)].into_iter().collect(),
+        point: (1, -2),
+        unit: (),
+        initial: 'm',
+    });
+
+    assert_eq!(jsonh_rs::from_str::<Vec<u8>>("[1, 2, 3] # end").unwrap(), vec![1, 2, 3]);
+    assert!(jsonh_rs::from_str::<Vec<u8>>("[1, 2] [3]").is_err());
+    assert!(jsonh_rs::from_str::<u8>("256").is_err());
+    assert!(jsonh_rs::from_str_with_options::<String>("@\"C:\\path\"", JsonhReaderOptions::new().with_version(JsonhVersion::V1)).is_err());
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+enum External {
+    Unit,
+    Newtype(u8),
+    Tuple(i32, String),
+    Struct { a: bool },
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(tag = "type")]
+enum Internal {
+    Http { port: u16, tls: bool },
+    File { path: String },
+    Stdout,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(tag = "kind", content = "data")]
+enum Adjacent {
+    Text(String),
+    Pair(u8, u8),
+    Empty,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+#[serde(untagged)]
+enum Untagged {
+    Number(f64),
+    Text(String),
+    List(Vec<Untagged>),
+}
+
+#[test]
+pub fn enum_test() {
+    // Externally tagged
+    assert_eq!(jsonh_rs::from_str::<External>("Unit").unwrap(), External::Unit);
+    assert_eq!(jsonh_rs::from_str::<External>("{ Unit: null }").unwrap(), External::Unit);
+    assert_eq!(jsonh_rs::from_str::<External>("Newtype: 5").unwrap(), External::Newtype(5));
+    assert_eq!(jsonh_rs::from_str::<External>("{ /* comment */ Tuple: [-1, b] }").unwrap(), External::Tuple(-1, "b".to_string()));
+    assert_eq!(jsonh_rs::from_str::<External>("{ Struct: { a: true } }").unwrap(), External::Struct { a: true });
+    assert!(jsonh_rs::from_str::<External>("Other").is_err());
+    assert!(jsonh_rs::from_str::<External>("{ Newtype: 5, Unit: null }").is_err());
+    assert!(jsonh_rs::from_str::<External>("[Unit]").is_err());
+
+    // Internally tagged
+    assert_eq!(jsonh_rs::from_str::<Vec<Internal>>(r#"[
+        { type: Http, port: 0x50, tls: false }
+        { path: "/var/log/app.log", type: File }
+        { type: Stdout }
+    ]"#).unwrap(), vec![Internal::Http { port: 80, tls: false }, Internal::File { path: "/var/log/app.log".to_string() }, Internal::Stdout]);
+    assert!(jsonh_rs::from_str::<Internal>("{ port: 80 }").is_err());
+
+    // Adjacently tagged
+    assert_eq!(jsonh_rs::from_str::<Adjacent>("kind: Text, data: hello").unwrap(), Adjacent::Text("hello".to_string()));
+    assert_eq!(jsonh_rs::from_str::<Adjacent>("data: [1, 2], kind: Pair").unwrap(), Adjacent::Pair(1, 2));
+    assert_eq!(jsonh_rs::from_str::<Adjacent>("kind: Empty").unwrap(), Adjacent::Empty);
+
+    // Untagged
+    assert_eq!(jsonh_rs::from_str::<Untagged>("[1.5, text, [2]]").unwrap(), Untagged::List(vec![Untagged::Number(1.5), Untagged::Text("text".to_string()), Untagged::List(vec![Untagged::Number(2.0)])]));
+    assert!(jsonh_rs::from_str::<Untagged>("true").is_err());
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct LogEntry {
+    level: String,
+    message: String,
+}
+
+#[test]
+pub fn stream_deserializer_test() {
+    let jsonh: &[u8] = b"[
+        { level: info, message: started }
+        // Comment
+        { level: warn, message: \"low memory\" },
+    ]";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_read(jsonh, JsonhReaderOptions::new());
+    let entries: Vec<LogEntry> = JsonhStreamDeserializer::<LogEntry>::new(&mut reader).collect::<Result<Vec<LogEntry>, JsonhError>>().unwrap();
+    assert_eq!(entries, vec![
+        LogEntry { level: "info".to_string(), message: "started".to_string() },
+        LogEntry { level: "warn".to_string(), message: "low memory".to_string() },
+    ]);
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[]", JsonhReaderOptions::new());
+    assert_eq!(JsonhStreamDeserializer::<u8>::new(&mut reader).count(), 0);
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, 2, x, 4]", JsonhReaderOptions::new());
+    let items: Vec<Result<u8, JsonhError>> = JsonhStreamDeserializer::<u8>::new(&mut reader).collect();
+    assert_eq!(items.len(), 3);
+    assert!(items[2].is_err());
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{ a: 1 }", JsonhReaderOptions::new());
+    let items: Vec<Result<u8, JsonhError>> = JsonhStreamDeserializer::<u8>::new(&mut reader).collect();
+    assert_eq!(items.len(), 1);
+    assert!(items[0].is_err());
+}
+
+#[test]
+pub fn transcode_test() {
+    let jsonh: &str = r#"
+// Export
+users: [
+    { name: Alice, id: 0x10, score: 2.5, admin: true }
+    { name: "Bob \"B\"", id: -1, score: 1e3, manager: null }
+]
+"#;
+    let mut json: Vec<u8> = Vec::new();
+    jsonh_rs::transcode_to_json(&mut JsonhReader::from_str(jsonh, JsonhReaderOptions::new()), &mut json).unwrap();
+    assert_eq!(String::from_utf8(json).unwrap(), r#"{"users":[{"name":"Alice","id":16,"score":2.5,"admin":true},{"name":"Bob \"B\"","id":-1,"score":1000,"manager":null}]}"#);
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, 2] [3]", JsonhReaderOptions::new());
+    assert!(jsonh_rs::transcode_to_json(&mut reader, Vec::new()).is_err());
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, 2", JsonhReaderOptions::new());
+    assert!(jsonh_rs::transcode_to_json(&mut reader, Vec::new()).is_err());
+
+    // JSONH to JSONH
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("a: [b, 0b11]", JsonhReaderOptions::new());
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new().with_newline(""));
+    jsonh_rs::transcode(&mut JsonhDeserializer::new(&mut reader), &mut JsonhSerializer::new(&mut writer)).unwrap();
+    assert_eq!(String::from_utf8(writer.writer).unwrap(), r#"{"a":["b",3]}"#);
+
+    // Large integers and non-finite numbers
+    for jsonh in ["[18446744073709551616, -9223372036854775809]", "{ a: [1, { b: 340282366920938463463374607431768211456 }] }", "[1e400]", "{ a: -1e400 }"] {
+        let mut json: Vec<u8> = Vec::new();
+        let result: Result<(), JsonhError> = jsonh_rs::transcode_to_json(&mut JsonhReader::from_str(jsonh, JsonhReaderOptions::new()), &mut json);
+        assert_eq!(result.is_ok(), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()).is_ok());
+
+        // Same as serde_transcode
+        let mut serde_json: Vec<u8> = Vec::new();
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+        let serde_result: Result<(), serde_json::Error> = serde_transcode::transcode(&mut JsonhDeserializer::new(&mut reader), &mut serde_json::Serializer::new(&mut serde_json));
+        if result.is_ok() {
+            assert!(serde_result.is_ok());
+            assert_eq!(json, serde_json);
+        }
+    }
+
+    // Errors keep their path and position
+    let error: JsonhError = jsonh_rs::transcode_to_json(&mut JsonhReader::from_str("{ a: { b: [1, { c: 1e400 }] } }", JsonhReaderOptions::new()), Vec::new()).unwrap_err();
+    assert_eq!(error.to_string(), "Infinity and NaN are not supported at a.b[1].c (character 19)");
+    let error: JsonhError = jsonh_rs::transcode_to_json(&mut JsonhReader::from_str("{ a: { b: [1, { c: [1,, ] }] } }", JsonhReaderOptions::new()), Vec::new()).unwrap_err();
+    assert_eq!(error.message, "Empty quoteless string");
+    assert_eq!(error.path.unwrap().to_string(), "a.b[1].c");
+    let error: JsonhError = jsonh_rs::transcode_to_json(&mut JsonhReader::from_str("1e400", JsonhReaderOptions::new()), Vec::new()).unwrap_err();
+    assert_eq!(error.to_string(), "Infinity and NaN are not supported (character 0)");
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+struct Cluster {
+    servers: Vec<ClusterServer>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+#[serde(deny_unknown_fields)]
+struct ClusterServer {
+    host: String,
+    port: u16,
+}
+
+#[test]
+pub fn error_context_test() {
+    let jsonh: &str = "servers: [
+    { host: a, port: 1 }
+    { host: b, port: 2 }
+    {
+        host: c
+        port: 70000
+    }
+]";
+    let error: JsonhError = jsonh_rs::from_str::<Cluster>(jsonh).unwrap_err();
+    assert_eq!(error.path, Some("servers[2].port".parse().unwrap()));
+    assert_eq!(error.location, Some((6, 15)));
+    assert_eq!(error.to_string(), "invalid value: integer `70000`, expected u16 at servers[2].port (line 6, column 15)");
+
+    let error: JsonhError = jsonh_rs::from_str::<Cluster>("servers: [{ host: a }]").unwrap_err();
+    assert_eq!(error.to_string(), "missing field `port` at servers[0] (line 1, column 11)");
+
+    let error: JsonhError = jsonh_rs::from_str::<Cluster>("servers: [{ host: a, prot: 1 }]").unwrap_err();
+    assert_eq!(error.path, Some("servers[0].prot".parse().unwrap()));
+
+    let error: JsonhError = jsonh_rs::from_str::<Cluster>("servers: [{ host: [a }]").unwrap_err();
+    assert_eq!(error.path, Some("servers[0].host".parse().unwrap()));
+
+    let error: JsonhError = jsonh_rs::from_str::<Cluster>("\n5").unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: integer `5`, expected struct Cluster (line 2, column 1)");
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, 2, x]", JsonhReaderOptions::new());
+    let error: JsonhError = jsonh_rs::from_reader::<Vec<u8>>(&mut reader).unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: string \"x\", expected u8 at [2] (character 7)");
+}
+
+#[derive(Deserialize, Debug)]
+struct PluginConfig {
+    name: String,
+    plugin: JsonhRawValue,
+    plugins: Vec<JsonhRawValue>,
+}
+
+#[test]
+pub fn raw_value_test() {
+    let jsonh: &str = r#"
+name: ä
+// Plugin options
+plugin: /* inline */ {
+    level: 3, # high
+    path: @"C:\plugins"
+}
+plugins: [a, [1, 2], "ü"]
+"#;
+    let config: PluginConfig = jsonh_rs::from_str(jsonh).unwrap();
+    assert_eq!(config.name, "ä");
+    assert_eq!(config.plugin.get(), "/* inline */ {\n    level: 3, # high\n    path: @\"C:\\plugins\"\n}");
+    assert_eq!(config.plugins.iter().map(JsonhRawValue::get).collect::<Vec<&str>>(), vec!["a", "[1, 2]", "\"ü\""]);
+
+    assert_eq!(config.plugin.parse::<Value>(JsonhReaderOptions::new()).unwrap(), serde_json::json!({ "level": 3, "path": "C:\\plugins" }));
+    assert!(config.plugin.parse::<Value>(JsonhReaderOptions::new().with_version(JsonhVersion::V1)).is_err());
+    assert_eq!(serde_json::to_string(&config.plugins[1]).unwrap(), "[1.0,2.0]");
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1]", JsonhReaderOptions::new());
+    assert!(jsonh_rs::from_reader::<JsonhRawValue>(&mut reader).is_err());
+    assert!(serde_json::from_str::<JsonhRawValue>("[1]").is_err());
+}
+
+#[test]
+pub fn integer_test() {
+    assert_eq!(jsonh_rs::from_str::<u64>("18446744073709551615").unwrap(), u64::MAX);
+    assert_eq!(jsonh_rs::from_str::<u64>("0xFFFF_FFFF_FFFF_FFFF").unwrap(), u64::MAX);
+    assert_eq!(jsonh_rs::from_str::<i64>("-9223372036854775808").unwrap(), i64::MIN);
+    assert_eq!(jsonh_rs::from_str::<i64>("9007199254740993").unwrap(), 9007199254740993);
+    assert_eq!(jsonh_rs::from_str::<i128>("-0b1_0000000000000000000000000000000000000000000000000000000000000000").unwrap(), -(1i128 << 64));
+    assert_eq!(jsonh_rs::from_str::<i128>("+0o17").unwrap(), 15);
+    assert_eq!(jsonh_rs::from_str::<u16>("1e3").unwrap(), 1000);
+    assert_eq!(jsonh_rs::from_str::<f64>("0x1.8").unwrap(), 1.5);
+    assert_eq!(jsonh_rs::from_str::<f64>("0x1E+2").unwrap(), 100.0);
+    assert_eq!(jsonh_rs::from_str::<Value>("[12345678901234567890, -1, 2.5]").unwrap(), serde_json::json!([12345678901234567890u64, -1, 2.5]));
+    assert!(jsonh_rs::from_str::<u64>("18446744073709551616").is_err());
+    assert!(jsonh_rs::from_str::<i64>("9223372036854775808").is_err());
+    assert_eq!(jsonh_rs::from_str::<u128>("340282366920938463463374607431768211455").unwrap(), u128::MAX);
+    assert_eq!(jsonh_rs::from_str::<u128>("0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF").unwrap(), u128::MAX);
+    assert_eq!(jsonh_rs::from_str::<i128>("-170141183460469231731687303715884105728").unwrap(), i128::MIN);
+    assert_eq!(jsonh_rs::from_str::<u128>("1e3").unwrap(), 1000);
+    assert!(jsonh_rs::from_str::<u128>("-1").is_err());
+    assert_eq!(jsonh_rs::from_str::<f64>("18446744073709551616").unwrap(), 18446744073709551616.0);
+    assert_eq!(jsonh_rs::from_str::<Value>("[18446744073709551616, -9223372036854775809]").unwrap(), serde_json::json!([18446744073709551616.0, -9223372036854775809.0]));
+    assert_eq!(jsonh_rs::from_str::<Value>("340282366920938463463374607431768211456").unwrap(), serde_json::json!(340282366920938463463374607431768211456.0));
+    assert_eq!(jsonh_rs::from_str::<u128>(&jsonh_rs::to_string(&u128::MAX).unwrap()).unwrap(), u128::MAX);
+    assert_eq!(jsonh_rs::from_str::<i128>(&jsonh_rs::to_string(&i128::MIN).unwrap()).unwrap(), i128::MIN);
+
+    assert_eq!(JsonhNumberParser::parse_integer("-0xFFFF_FFFF_FFFF_FFFF"), Some(-18446744073709551615));
+    assert_eq!(JsonhNumberParser::parse_integer("1.0"), None);
+    assert_eq!(JsonhNumberParser::parse_integer("0x1E+2"), None);
+    assert_eq!(JsonhNumberParser::parse_integer("1e2"), None);
+    assert_eq!(JsonhNumberParser::parse_integer("-170141183460469231731687303715884105728"), Some(i128::MIN));
+    assert_eq!(JsonhNumberParser::parse_integer("170141183460469231731687303715884105728"), None);
+    assert_eq!(JsonhNumberParser::parse_unsigned_integer("170141183460469231731687303715884105728"), Some(1 << 127));
+    assert_eq!(JsonhNumberParser::parse_unsigned_integer("-1"), None);
+}
+
+#[test]
+pub fn map_key_test() {
+    let map: std::collections::HashMap<u32, u8> = std::collections::HashMap::from([(1, 2)]);
+    assert_eq!(jsonh_rs::from_str::<std::collections::HashMap<u32, u8>>(&jsonh_rs::to_string(&map).unwrap()).unwrap(), map);
+
+    assert_eq!(jsonh_rs::from_str::<std::collections::BTreeMap<i32, bool>>("{ -1: true, 0x10: false }").unwrap(), std::collections::BTreeMap::from([(-1, true), (16, false)]));
+    assert_eq!(jsonh_rs::from_str::<std::collections::BTreeMap<bool, char>>("{ true: a, 'false': b }").unwrap(), std::collections::BTreeMap::from([(true, 'a'), (false, 'b')]));
+    assert_eq!(jsonh_rs::from_str::<std::collections::BTreeMap<u128, ()>>("{ 340282366920938463463374607431768211455: null }").unwrap(), std::collections::BTreeMap::from([(u128::MAX, ())]));
+    assert_eq!(jsonh_rs::from_str::<std::collections::HashMap<String, f64>>("{ 1: 1.5 }").unwrap(), std::collections::HashMap::from([("1".to_string(), 1.5)]));
+    assert_eq!(jsonh_rs::from_str::<std::collections::HashMap<Option<u8>, u8>>("{ 5: 5 }").unwrap(), std::collections::HashMap::from([(Some(5), 5)]));
+
+    let error: JsonhError = jsonh_rs::from_str::<std::collections::HashMap<u8, u8>>("{ a: 1 }").unwrap_err();
+    assert_eq!(error.message, "invalid type: string \"a\", expected u8");
+    assert_eq!(error.path.unwrap().to_string(), "a");
+    assert!(jsonh_rs::from_str::<std::collections::HashMap<u8, u8>>("{ 256: 1 }").is_err());
+    assert!(jsonh_rs::from_str::<std::collections::BTreeMap<bool, u8>>("{ yes: 1 }").is_err());
+}
+
+#[test]
+pub fn render_error_test() {
+    let jsonh: &str = "servers: [\r\n    {\r\n        host: c\r\n        port: 70000\r\n    }\r\n]";
+    let error: JsonhError = jsonh_rs::from_str::<Cluster>(jsonh).unwrap_err();
+    assert_eq!(jsonh_rs::render_error(jsonh, &error), "error: invalid value: integer `70000`, expected u16
+ --> 4:15 (servers[0].port)
+  |
+4 |         port: 70000
+  |               ^");
+
+    let jsonh: &str = "a: [\n\t1\n\t2\n\t4,,\n]\n";
+    let error: JsonhError = jsonh_rs::from_str::<Value>(jsonh).unwrap_err();
+    assert_eq!(jsonh_rs::render_error(jsonh, &error), "error: Empty quoteless string
+ --> 4:4 (a)
+  |
+4 | \t4,,
+  | \t  ^");
+
+    assert_eq!(jsonh_rs::render_error("", &JsonhError::from("Failed")), "error: Failed");
+    assert_eq!(jsonh_rs::render_error("", &JsonhError::from("Failed").with_path("a[1]".parse().unwrap())), "error: Failed\n --> a[1]");
+}
+
+#[test]
+pub fn miette_report_test() {
+    use miette::Diagnostic;
+
+    let jsonh: &str = "servers: [\n    { host: ä, port: 70000 }\n]";
+    let error: JsonhError = jsonh_rs::from_str::<Cluster>(jsonh).unwrap_err();
+    assert_eq!(error.code().unwrap().to_string(), "jsonh::error");
+    assert_eq!(error.help().unwrap().to_string(), "at servers[0].port");
+
+    let report: JsonhErrorReport = error.to_report(jsonh);
+    assert_eq!(report.to_string(), "invalid value: integer `70000`, expected u16");
+    let labels: Vec<miette::LabeledSpan> = report.labels().unwrap().collect();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].label(), Some("servers[0].port"));
+    assert_eq!(&jsonh[labels[0].offset()..(labels[0].offset() + labels[0].len())], "70000");
+
+    let report: miette::Report = miette::Report::new(error.to_report(jsonh));
+    assert!(report.source_code().is_some());
+    let report: miette::Report = jsonh_rs::from_str::<Value>("[1, 2").unwrap_err().into();
+    assert_eq!(report.to_string(), "Expected `]` to end array, got end of input (line 1, column 6)");
+}
\ No newline at end of file
@@ -375,4 +375,33 @@ pub fn fractional_hexadecimal_numbers_test() {
         serde_json::from_value::<Vec<f64>>(JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()).unwrap()).unwrap(),
         [10.625, 10.62890625]
     );
+}
+
+#[test]
+pub fn no_panic_test() {
+    let sources: [&str; 12] = ["=#=*-}\r:u=u#u*\u{2028}", "{a:", "[[[", "}", "]", "0x", "1e", "\"\\u", "\"\"\"\n\"\"\"", "'''\r'''", "[\"\"\"\r\n\"\"\"]", "\"\"\"\\n\"\"\""];
+    for source in sources {
+        for options in [JsonhReaderOptions::new(), JsonhReaderOptions::new().incomplete_inputs(true)] {
+            let _ = JsonhReader::parse_element_from_str(source, options);
+            let _ = JsonhReader::from_str(source, options).parse_json(true, None);
+            let _ = JsonhReader::from_str(source, options).skip_value();
+            let _ = jsonh_rs::transcode_to_json(&mut JsonhReader::from_str(source, options), Vec::new());
+            let _ = jsonh_rs::parse_with_recovery(source, options);
+
+            let mut parser: JsonhPushParser = JsonhPushParser::new(options);
+            parser.feed(source);
+            let _ = parser.finish();
+        }
+        let _ = jsonh_rs::from_str::<Value>(source);
+        let _ = jsonh_rs::get(source, "[0]");
+        let _ = jsonh_rs::detect_format(source);
+        let _ = jsonh_rs::check_round_trip(source, JsonhFormatterOptions::new());
+        let _ = JsonhFormatter::format(source, JsonhFormatterOptions::new());
+        let _ = JsonhFormatter::infer_options(source, JsonhFormatterOptions::new());
+        let _ = JsonhLinter::new(JsonhReaderOptions::new()).fix(source);
+    }
+
+    // Multi-quoted strings containing only a newline
+    assert_eq!(JsonhReader::parse_element_from_str("\"\"\"\n\"\"\"", JsonhReaderOptions::new()), Ok(serde_json::json!("\n")));
+    assert_eq!(JsonhReader::parse_element_from_str("[\"\"\"\r\n\"\"\"]", JsonhReaderOptions::new()), Ok(serde_json::json!(["\r\n"])));
 }
\ No newline at end of file
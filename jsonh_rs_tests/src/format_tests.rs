@@ -0,0 +1,237 @@
+use jsonh_rs::*;
+
+#[test]
+pub fn format_layout_test() {
+    let jsonh: &str = "{a:1,b:[2,3]  # comment\n}";
+
+    assert_eq!(JsonhFormatter::format(jsonh, JsonhFormatterOptions::new()).unwrap(), r#"{
+    a: 1,
+    b: [
+        2,
+        3
+    ] # comment
+}
+"#);
+}
+
+#[test]
+pub fn format_comments_test() {
+    let jsonh: &str = r#"
+// header
+
+{ // open
+
+
+  a: 'x', /* one */
+
+
+  b: /* two */ 0x_FF
+  # end
+}
+"#;
+
+    assert_eq!(JsonhFormatter::format(jsonh, JsonhFormatterOptions::new().with_indent_width(2).with_commas(false)).unwrap(), r#"// header
+
+{ // open
+  a: 'x' /* one */
+
+  /* two */
+  b: 0x_FF
+  # end
+}
+"#);
+}
+
+#[test]
+pub fn format_braceless_object_test() {
+    let jsonh: &str = "a: b\nc: [ ]\n\n\nd: {  }";
+    let formatted: String = JsonhFormatter::format(jsonh, JsonhFormatterOptions::new().with_final_newline(false)).unwrap();
+
+    assert_eq!(formatted, "a: b,\nc: [],\n\nd: {}");
+    assert!(JsonhFormatter::is_formatted(&formatted, JsonhFormatterOptions::new().with_final_newline(false)).unwrap());
+}
+
+#[test]
+pub fn format_invalid_test() {
+    assert!(JsonhFormatter::format("[1, 2", JsonhFormatterOptions::new()).is_err());
+    assert!(JsonhFormatter::format("1\n2", JsonhFormatterOptions::new()).is_err());
+}
+
+
+#[test]
+pub fn format_quote_and_comment_style_test() {
+    let jsonh: &str = "{'a\"': 'b', # one\n  c: [\"d\", '''e'''] /* two */ /* three */\n  /* four\n  */\n}";
+    let options: JsonhFormatterOptions = JsonhFormatterOptions::new()
+        .with_quote_style(Some(JsonhQuoteStyle::Double))
+        .with_comment_style(Some(JsonhCommentStyle::Line));
+
+    assert_eq!(JsonhFormatter::format(jsonh, options).unwrap(), r#"{
+    "a\"": "b", // one
+    c: [
+        "d",
+        '''e'''
+    ] /* two */ // three
+    /* four
+  */
+}
+"#);
+}
+
+#[test]
+pub fn infer_options_test() {
+    let jsonh: &str = "{\r\n\t'a': 1 # one\r\n\tb: [\r\n\t\t2\r\n\t\t'3'\r\n\t]\r\n}";
+    let options: JsonhFormatterOptions = JsonhFormatter::infer_options(jsonh, JsonhFormatterOptions::new()).unwrap();
+
+    assert_eq!(options.newline, "\r\n");
+    assert_eq!(options.indent_char, '\t');
+    assert_eq!(options.indent_width, 1);
+    assert!(!options.commas);
+    assert_eq!(options.quote_style, Some(JsonhQuoteStyle::Single));
+    assert_eq!(options.comment_style, Some(JsonhCommentStyle::Hash));
+    assert!(!options.final_newline);
+    assert!(JsonhFormatter::is_formatted(jsonh, options).unwrap());
+
+    let single_line: JsonhFormatterOptions = JsonhFormatter::infer_options("[1, 2]\n", JsonhFormatterOptions::new().with_indent_width(2)).unwrap();
+    assert_eq!(single_line.indent_width, 2);
+    assert!(single_line.commas);
+    assert!(single_line.final_newline);
+}
+
+#[test]
+pub fn sort_keys_test() {
+    let jsonh: &str = "{\n  // second\n  b: [{z: 1, y: 2}],\n  a: 1, // first\n  'c': 3\n  # end\n}";
+
+    assert_eq!(JsonhFormatter::sort_keys(jsonh, JsonhFormatterOptions::new()).unwrap(), r#"{
+    a: 1, // first
+    // second
+    b: [
+        {
+            y: 2,
+            z: 1
+        }
+    ],
+    'c': 3
+    # end
+}
+"#);
+    assert_eq!(JsonhFormatter::sort_keys_by("b: 1\na: 2\nc: 3", JsonhFormatterOptions::new().with_final_newline(false), |name1, name2| name2.cmp(name1)).unwrap(), "c: 3,\nb: 1,\na: 2");
+}
+
+#[test]
+pub fn convert_comments_test() {
+    let jsonh: &str = "# one\n{a: /* two */ 1, /* three */\n  b: 2 // four\n} /* five\n*/";
+    let all_styles: [JsonhCommentStyle; 3] = [JsonhCommentStyle::Hash, JsonhCommentStyle::Line, JsonhCommentStyle::Block];
+
+    assert_eq!(JsonhFormatter::convert_comments(jsonh, &all_styles, JsonhCommentStyle::Line, JsonhVersion::Latest).unwrap(),
+        "// one\n{a: /* two */ 1, // three\n  b: 2 // four\n} /* five\n*/");
+    assert_eq!(JsonhFormatter::convert_comments(jsonh, &[JsonhCommentStyle::Hash, JsonhCommentStyle::Line], JsonhCommentStyle::Block, JsonhVersion::Latest).unwrap(),
+        "/* one */\n{a: /* two */ 1, /* three */\n  b: 2 /* four */\n} /* five\n*/");
+    assert_eq!(JsonhFormatter::convert_comments(jsonh, &[JsonhCommentStyle::Line], JsonhCommentStyle::Hash, JsonhVersion::Latest).unwrap(),
+        "# one\n{a: /* two */ 1, /* three */\n  b: 2 # four\n} /* five\n*/");
+}
+
+#[test]
+pub fn format_quoteless_strings_test() {
+    let jsonh: &str = r#"{"a": "b c", "d e": "true", "f": " g", 'h\'': "1.5", i: j\,k, "l": "m\nn", '''o''': @p}"#;
+
+    assert_eq!(JsonhFormatter::format(jsonh, JsonhFormatterOptions::new().with_quoteless_strings(Some(true))).unwrap(), r#"{
+    a: b c,
+    d e: "true",
+    f: " g",
+    'h\'': "1.5",
+    i: j\,k,
+    l: "m\nn",
+    o: @p
+}
+"#);
+    assert_eq!(JsonhFormatter::format(jsonh, JsonhFormatterOptions::new().with_quoteless_strings(Some(false))).unwrap(), r#"{
+    "a": "b c",
+    "d e": "true",
+    "f": " g",
+    'h\'': "1.5",
+    "i": "j,k",
+    "l": "m\nn",
+    '''o''': "p"
+}
+"#);
+}
+
+#[test]
+pub fn format_multi_quoted_strings_test() {
+    let jsonh: &str = r#"{a: "line 1\n  line 2\n\n\"\"\"\\", b: 'x\ny', c: "short"}"#;
+    let options: JsonhFormatterOptions = JsonhFormatterOptions::new().with_multi_quoted_strings(Some(8));
+    let formatted: String = JsonhFormatter::format(jsonh, options).unwrap();
+
+    assert_eq!(formatted, r#"{
+    a: """"
+        line 1
+          line 2
+
+        """\\
+        """",
+    b: 'x\ny',
+    c: "short"
+}
+"#);
+    assert_eq!(JsonhReader::parse_element_from_str(&formatted, JsonhReaderOptions::new()), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()));
+}
+
+#[test]
+pub fn skeleton_test() {
+    let schema: Value = serde_json::json!({
+        "description": "The server configuration.",
+        "type": "object",
+        "properties": {
+            "host": { "description": "The host name.", "type": "string", "default": "localhost" },
+            "port": { "description": "The port\nto listen on.", "type": "integer" },
+            "backup": { "anyOf": [{ "$ref": "#/$defs/Backup" }, { "type": "null" }] },
+            "tags": { "type": ["array", "null"] },
+        },
+        "$defs": {
+            "Backup": {
+                "description": "A backup server.",
+                "type": "object",
+                "properties": {
+                    "enabled": { "type": "boolean" },
+                    "next": { "$ref": "#/$defs/Backup" },
+                },
+            },
+        },
+    });
+
+    assert_eq!(JsonhFormatter::skeleton(&schema, JsonhFormatterOptions::new().with_indent_width(2).with_quoteless_strings(Some(true))).unwrap(), r#"// The server configuration.
+{
+  backup: {
+    enabled: false,
+    // A backup server.
+    next: null
+  },
+  // The host name.
+  host: localhost,
+  // The port
+  // to listen on.
+  port: 0,
+  tags: []
+}
+"#);
+    assert!(JsonhFormatter::skeleton(&serde_json::json!({ "$ref": "#/$defs/Missing" }), JsonhFormatterOptions::new()).is_err());
+}
+#[test]
+pub fn quoting_predicates_test() {
+    assert!(jsonh_rs::is_valid_quoteless_string("hello world", JsonhVersion::Latest));
+    assert!(!jsonh_rs::is_valid_quoteless_string("a, b", JsonhVersion::Latest));
+    assert!(!jsonh_rs::is_valid_quoteless_string("10", JsonhVersion::Latest));
+    assert!(!jsonh_rs::is_valid_quoteless_string("null", JsonhVersion::Latest));
+    assert!(!jsonh_rs::is_valid_quoteless_string(" padded", JsonhVersion::Latest));
+    assert!(!jsonh_rs::is_valid_quoteless_string("", JsonhVersion::Latest));
+    assert!(jsonh_rs::is_valid_quoteless_string("a@b", JsonhVersion::V1));
+    assert!(!jsonh_rs::is_valid_quoteless_string("@b", JsonhVersion::V2));
+
+    assert!(!jsonh_rs::key_needs_quotes("key"));
+    assert!(!jsonh_rs::key_needs_quotes("10"));
+    assert!(jsonh_rs::key_needs_quotes("a:b"));
+    assert!(jsonh_rs::key_needs_quotes(""));
+
+    assert!(jsonh_rs::string_requires_multiquote("line 1\nline 2"));
+    assert!(!jsonh_rs::string_requires_multiquote("line"));
+}
\ No newline at end of file
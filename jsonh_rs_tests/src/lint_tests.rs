@@ -0,0 +1,178 @@
+use jsonh_rs::*;
+
+#[test]
+pub fn lint_built_in_rules_test() {
+    let jsonh: &str = "{\n  \"a\": 007,  \n  'a': 12345678901234567890,\n  b: '''\n    text  \n    ''',\n  c: [[[[[[[[[]]]]]]]]]\n}";
+    let diagnostics: Vec<JsonhDiagnostic> = JsonhLinter::new(JsonhReaderOptions::new()).lint(jsonh);
+    let codes: Vec<&str> = diagnostics.iter().map(|diagnostic| diagnostic.code).collect();
+
+    assert_eq!(codes, [
+        "suspicious-numbers",
+        "trailing-whitespace",
+        "duplicate-keys",
+        "inconsistent-quotes",
+        "suspicious-numbers",
+        "inconsistent-quotes",
+        "deep-nesting",
+    ]);
+    assert_eq!(diagnostics[1].severity, JsonhSeverity::Warning);
+    assert_eq!((diagnostics[1].start, diagnostics[1].end), (13, 15));
+    assert_eq!(diagnostics[3].severity, JsonhSeverity::Info);
+}
+
+#[test]
+pub fn lint_syntax_error_test() {
+    let diagnostics: Vec<JsonhDiagnostic> = JsonhLinter::new(JsonhReaderOptions::new()).lint("[1, 2");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].code, "syntax");
+    assert_eq!(diagnostics[0].severity, JsonhSeverity::Error);
+    assert_eq!(diagnostics[0].message, "Expected `]` to end array, got end of input");
+    assert_eq!(diagnostics[0].start, 5);
+}
+
+struct NoNullRule;
+
+impl JsonhLintRule for NoNullRule {
+    fn name(&self) -> &'static str {
+        return "no-null";
+    }
+    fn check(&self, context: &JsonhLintContext<'_>, diagnostics: &mut Vec<JsonhDiagnostic>) {
+        for token in &context.tokens {
+            if token.json_type == JsonTokenType::Null {
+                diagnostics.push(JsonhDiagnostic::new(JsonhSeverity::Error, self.name(), "Unexpected null".to_string())
+                    .with_span(token.start, token.end));
+            }
+        }
+    }
+}
+
+#[test]
+pub fn lint_custom_rule_test() {
+    let linter: JsonhLinter = JsonhLinter::without_rules(JsonhReaderOptions::new())
+        .with_rule(NoNullRule);
+    let diagnostics: Vec<JsonhDiagnostic> = linter.lint("[1, null, 'null']");
+
+    assert_eq!(diagnostics, [JsonhDiagnostic::new(JsonhSeverity::Error, "no-null", "Unexpected null".to_string()).with_span(4, 8)]);
+}
+
+
+#[test]
+pub fn lint_fix_test() {
+    let jsonh: &str = "{\n  \"a\": 1,  \n  'b\"c': 'd\\'e',\n  f: 2\n}";
+    let linter: JsonhLinter = JsonhLinter::new(JsonhReaderOptions::new());
+
+    let diagnostics: Vec<JsonhDiagnostic> = linter.lint(jsonh);
+    assert!(diagnostics.iter().all(JsonhDiagnostic::is_fixable));
+
+    let fixed: String = linter.fix(jsonh);
+    assert_eq!(fixed, "{\n  \"a\": 1,\n  \"b\\\"c\": \"d\\'e\",\n  f: 2\n}");
+    assert!(linter.lint(&fixed).is_empty());
+}
+
+#[test]
+pub fn apply_fixes_overlapping_test() {
+    let diagnostics: Vec<JsonhDiagnostic> = vec![
+        JsonhDiagnostic::new(JsonhSeverity::Info, "a", "A".to_string()).with_edit(JsonhTextEdit::new(0, 3, "x".to_string())),
+        JsonhDiagnostic::new(JsonhSeverity::Info, "b", "B".to_string()).with_edit(JsonhTextEdit::delete(2, 4)),
+        JsonhDiagnostic::new(JsonhSeverity::Info, "c", "C".to_string()).with_edit(JsonhTextEdit::insert(5, "y".to_string())),
+        JsonhDiagnostic::new(JsonhSeverity::Info, "d", "D".to_string()),
+    ];
+
+    assert_eq!(JsonhLinter::apply_fixes("abcdef", &diagnostics), "xdeyf");
+}
+
+#[test]
+pub fn check_compatibility_test() {
+    let jsonh: &str = "{\n  a: @\"C:\\Users\",\n  @b: c /=* nested *=/\n}";
+    let diagnostics: Vec<JsonhDiagnostic> = JsonhLinter::check_compatibility(jsonh, JsonhVersion::V1);
+
+    assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.message.as_str()).collect::<Vec<&str>>(), [
+        "Verbatim strings require JSONH V2",
+        "Verbatim strings require JSONH V2",
+        "Nestable block comments require JSONH V2",
+    ]);
+    assert_eq!((diagnostics[0].start, diagnostics[0].end), (7, 18));
+    assert!(JsonhLinter::check_compatibility(jsonh, JsonhVersion::V2).is_empty());
+    assert!(JsonhLinter::check_compatibility("a@b", JsonhVersion::V1).is_empty());
+}
+
+#[test]
+pub fn parse_with_recovery_test() {
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("{ a: 1, b: , c: 3 }", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!({ "a": 1.0, "c": 3.0 }));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].code, diagnostics[0].start, diagnostics[0].end), ("syntax", 7, 12));
+
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("[\n    1\n    }\n    3, 4 5\n]", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!([1.0, 3.0, "4 5"]));
+    assert_eq!(diagnostics.iter().map(|diagnostic| (diagnostic.start, diagnostic.end)).collect::<Vec<(u64, u64)>>(), vec![(12, 13)]);
+
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("a: [1, 2", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!({ "a": [1.0, 2.0] }));
+    assert_eq!(diagnostics.len(), 1);
+
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("{ a: 1 }", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!({ "a": 1.0 }));
+    assert!(diagnostics.is_empty());
+}
+#[test]
+pub fn parse_with_recovery_nested_test() {
+    // Mismatched closing bracket
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("{a: 1, b: [1, 2, }, c: 3}", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!({ "a": 1.0, "b": [1.0, 2.0], "c": 3.0 }));
+    assert_eq!(diagnostics.iter().map(|diagnostic| (diagnostic.start, diagnostic.end)).collect::<Vec<(u64, u64)>>(), vec![(17, 18)]);
+
+    // Missing value before closing bracket
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("{ a: 1, b: {c: }, d: 3 }", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!({ "a": 1.0, "b": {}, "d": 3.0 }));
+    assert_eq!(diagnostics.iter().map(|diagnostic| (diagnostic.start, diagnostic.end)).collect::<Vec<(u64, u64)>>(), vec![(12, 15)]);
+
+    // Closing bracket of no structure
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("[1, }, 3]", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!([1.0, 3.0]));
+    assert_eq!(diagnostics.len(), 1);
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("[[1, 2}, 3]", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!([[1.0, 2.0, 3.0]]));
+    assert_eq!(diagnostics.iter().map(|diagnostic| (diagnostic.start, diagnostic.end)).collect::<Vec<(u64, u64)>>(), vec![(6, 7), (11, 11)]);
+
+    // Malformed item in nested structure
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("{ a: { b: [1, x: 2], c: 3 } }", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!({ "a": { "b": [1.0], "c": 3.0 } }));
+    assert_eq!(diagnostics.len(), 1);
+    let (element, diagnostics): (Value, Vec<JsonhDiagnostic>) = jsonh_rs::parse_with_recovery("[1,,2]", JsonhReaderOptions::new());
+    assert_eq!(element, serde_json::json!([1.0, 2.0]));
+    assert_eq!(diagnostics.len(), 1);
+}
+
+#[test]
+pub fn suspicious_literals_test() {
+    let jsonh: &str = "{ a: ture, b: 'flase', c: Null, d: nul, e: truth, f: true }";
+    let diagnostics: Vec<JsonhDiagnostic> = JsonhLinter::new(JsonhReaderOptions::new()).lint(jsonh);
+    assert_eq!(diagnostics.iter().map(|diagnostic| (diagnostic.code, diagnostic.message.as_str())).collect::<Vec<(&str, &str)>>(), vec![
+        ("suspicious-literals", "Quoteless string looks like a misspelled `true`"),
+        ("suspicious-literals", "Quoteless string looks like a misspelled `null`"),
+        ("suspicious-literals", "Quoteless string looks like a misspelled `null`"),
+    ]);
+    assert_eq!(JsonhLinter::new(JsonhReaderOptions::new()).fix(jsonh), "{ a: true, b: 'flase', c: null, d: null, e: truth, f: true }");
+
+    assert_eq!(jsonh_rs::suggest_literal("flase"), Some("false"));
+    assert_eq!(jsonh_rs::suggest_literal("FALSE"), Some("false"));
+    assert_eq!(jsonh_rs::suggest_literal("tru"), Some("true"));
+    assert_eq!(jsonh_rs::suggest_literal("true"), None);
+    assert_eq!(jsonh_rs::suggest_literal("nil"), None);
+
+    let error: JsonhError = jsonh_rs::from_str::<std::collections::BTreeMap<String, bool>>("enabled: ture").unwrap_err();
+    assert_eq!(error.message, "invalid type: string \"ture\", expected a boolean, did you mean `true`?");
+    assert_eq!(error.path, Some("enabled".parse().unwrap()));
+    assert!(jsonh_rs::from_str::<()>("nul").unwrap_err().message.ends_with("did you mean `null`?"));
+    assert_eq!(jsonh_rs::from_str::<String>("ture").unwrap(), "ture");
+
+    // Only suggest literals allowed in context
+    assert_eq!(jsonh_rs::suggest_literal_from("nul", &["true", "false"]), None);
+    assert_eq!(jsonh_rs::from_str::<bool>("nul").unwrap_err().message, "invalid type: string \"nul\", expected a boolean");
+    assert!(jsonh_rs::from_str::<()>("ture").unwrap_err().message.starts_with("invalid type: string \"ture\", expected unit"));
+    assert_eq!(jsonh_rs::from_str::<Option<i32>>("nul").unwrap_err().message, "invalid type: string \"nul\", expected i32, did you mean `null`?");
+    assert_eq!(jsonh_rs::from_str::<Option<bool>>("ture").unwrap_err().message, "invalid type: string \"ture\", expected a boolean, did you mean `true`?");
+    assert_eq!(jsonh_rs::from_str::<Option<String>>("nul").unwrap(), Some("nul".to_string()));
+}
\ No newline at end of file
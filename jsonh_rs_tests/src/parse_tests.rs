@@ -249,4 +249,721 @@ a: /*b*/ c
         .with_parse_single_element(false)
     );
     assert_eq!(reader7.parse_json(false, None).unwrap(), "{\"a\":\"c\"}");
+}
+#[test]
+pub fn parse_stats_test() {
+    let jsonh: &str = r#"
+{
+    // comment
+    a: [1, "two", { b: null }],
+    cd: true
+}
+"#;
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let (element, stats) = reader.parse_element_with_stats();
+
+    assert!(element.is_ok());
+    assert_eq!(stats.token_count(JsonTokenType::StartObject), 2);
+    assert_eq!(stats.token_count(JsonTokenType::PropertyName), 3);
+    assert_eq!(stats.token_count(JsonTokenType::Number), 1);
+    assert_eq!(stats.token_count(JsonTokenType::Comment), 1);
+    assert_eq!(stats.total_tokens(), 14);
+    assert_eq!(stats.max_depth, 3);
+    assert_eq!(stats.string_bytes, "a".len() as u64 + "two".len() as u64 + "b".len() as u64 + "cd".len() as u64);
+    assert_eq!(stats.comment_bytes, " comment".len() as u64);
+}
+
+
+#[test]
+pub fn unknown_version_test() {
+    let jsonh: &str = r#"@"a\b""#;
+
+    let strict_options: JsonhReaderOptions = JsonhReaderOptions::new().with_version(JsonhVersion::from_number(3));
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, strict_options).unwrap_err().message, "Unsupported JSONH version");
+
+    let best_effort_options: JsonhReaderOptions = strict_options.with_unknown_versions(true);
+    assert_eq!(best_effort_options.version, JsonhVersion::Other(3));
+    assert!(best_effort_options.supports_version(JsonhVersion::V2));
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, best_effort_options).unwrap(), "a\\b");
+
+    assert_eq!(JsonhVersion::from_number(2), JsonhVersion::V2);
+    assert_eq!(JsonhVersion::Latest.number(), JsonhVersion::LATEST_KNOWN.number());
+}
+
+#[test]
+pub fn version_display_from_str_test() {
+    assert_eq!("latest".parse::<JsonhVersion>(), Ok(JsonhVersion::Latest));
+    assert_eq!("V2".parse::<JsonhVersion>(), Ok(JsonhVersion::V2));
+    assert_eq!(" 1 ".parse::<JsonhVersion>(), Ok(JsonhVersion::V1));
+    assert_eq!("v3".parse::<JsonhVersion>(), Ok(JsonhVersion::Other(3)));
+    assert!("v".parse::<JsonhVersion>().is_err());
+    assert!("-1".parse::<JsonhVersion>().is_err());
+
+    assert_eq!(JsonhVersion::Latest.to_string(), "latest");
+    assert_eq!(JsonhVersion::V1.to_string(), "v1");
+    assert_eq!(JsonhVersion::Other(7).to_string(), "v7");
+
+    assert_eq!(JsonhVersion::Latest.resolved(), JsonhVersion::V2);
+    assert_eq!(JsonhVersion::V1.resolved(), JsonhVersion::V1);
+    assert!(JsonhVersion::Latest.resolved() > JsonhVersion::V1);
+}
+
+#[test]
+pub fn parse_json_source_map_test() {
+    let jsonh: &str = "{\n  a: 1 # one\n  'b': [true, 'x']\n}";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let (json, source_map): (String, JsonhSourceMap) = reader.parse_json_with_source_map(false, None).unwrap();
+
+    assert_eq!(json, r#"{"a":1,"b":[true,"x"]}"#);
+
+    let true_mapping: &JsonhSourceMapping = source_map.find(json.find("true").unwrap()).unwrap();
+    assert_eq!(*true_mapping, JsonhSourceMapping::new(12, 16, 23, 27));
+
+    let name_mapping: &JsonhSourceMapping = source_map.find(json.find("\"b\"").unwrap()).unwrap();
+    assert_eq!((name_mapping.input_start, name_mapping.input_end), (17, 20));
+
+    let array_mapping: &JsonhSourceMapping = source_map.find(json.find(',').unwrap() + 5).unwrap();
+    assert_eq!((array_mapping.output_start, array_mapping.output_end, array_mapping.input_start, array_mapping.input_end), (11, 21, 22, 33));
+
+    assert_eq!(source_map.find(0).unwrap().input_end, 35);
+}
+
+#[test]
+pub fn nested_element_test() {
+    let jsonh: &str = "{a: {b: 1}, c: [1, [2, {d: 3}]]}";
+    let element: Value = JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()).unwrap();
+
+    assert_eq!(element, serde_json::json!({ "a": { "b": 1.0 }, "c": [1.0, [2.0, { "d": 3.0 }]] }));
+}
+
+#[test]
+pub fn parse_element_with_comments_test() {
+    let jsonh: &str = "// The server\n{\n  # The port\n  port: 8080\n  hosts: [\n    /* first */ a\n    # second\n    b\n    // end of hosts\n  ]\n}\n# trailing";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let (mut element, comments): (Value, Vec<JsonhComment>) = reader.parse_element_with_comments().unwrap();
+
+    let paths: Vec<String> = comments.iter().map(|comment| comment.path.to_string()).collect();
+    assert_eq!(paths, ["$", "port", "hosts[0]", "hosts[1]", "hosts", "$"]);
+    assert_eq!(comments[1].value, " The port");
+    assert_eq!((comments[1].start, comments[1].end), (18, 28));
+
+    JsonhComment::inject_all(&mut element, &comments);
+    assert_eq!(element, serde_json::json!({
+        "$comment": "The server\ntrailing",
+        "$comment:port": "The port",
+        "$comment:hosts": "end of hosts",
+        "port": 8080.0,
+        "hosts": ["a", "b"],
+    }));
+}
+
+#[test]
+pub fn embedded_element_test() {
+    let markdown: &str = "# Config\n```jsonh\nport: 8080\n```\n```json\n[]\n```\n~~~ JSONH\n[1, 2]\n~~~";
+    let elements: Vec<JsonhEmbeddedElement> = JsonhEmbeddedElement::parse_markdown(markdown, JsonhReaderOptions::new()).unwrap();
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[0].value, serde_json::json!({ "port": 8080.0 }));
+    assert_eq!((elements[0].start, elements[0].end), (18, 29));
+    assert_eq!(elements[1].value, serde_json::json!([1.0, 2.0]));
+
+    let document: &str = "---\ntitle: Hello\n---\nContent";
+    let front_matter: JsonhEmbeddedElement = JsonhEmbeddedElement::parse_front_matter(document, JsonhReaderOptions::new()).unwrap().unwrap();
+    assert_eq!(front_matter.value, serde_json::json!({ "title": "Hello" }));
+    assert_eq!((front_matter.start, front_matter.end), (4, 17));
+    assert_eq!(JsonhEmbeddedElement::parse_front_matter("Content", JsonhReaderOptions::new()), Ok(None));
+
+    let template: &str = "<p>é</p><%jsonh { a: b } %><%jsonh [c] %>";
+    let regions: Vec<JsonhEmbeddedElement> = JsonhEmbeddedElement::parse_delimited(template, "<%jsonh", "%>", JsonhReaderOptions::new()).unwrap();
+    assert_eq!(regions.len(), 2);
+    assert_eq!(regions[0].value, serde_json::json!({ "a": "b" }));
+    assert_eq!((regions[0].start, regions[0].end), (15, 25));
+    assert!(JsonhEmbeddedElement::parse_delimited("<%jsonh a", "<%jsonh", "%>", JsonhReaderOptions::new()).is_err());
+}
+
+#[test]
+pub fn max_allocation_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_max_allocation(Some(8));
+    assert_eq!(JsonhReader::parse_element_from_str("\"a very long string\"", options).unwrap_err().message, "Exceeded max allocation");
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("\"a very long string\"", options);
+    assert!(reader.read_element().any(|token| token.is_err_and(|error| error.message == "Exceeded max allocation")));
+
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_max_allocation(Some(1000));
+    assert!(JsonhReader::parse_element_from_str("[1, 2, 3]", options).is_ok());
+    assert_eq!(JsonhReader::parse_element_from_str(&"[".repeat(60), options.incomplete_inputs(true)).unwrap_err().message, "Exceeded max allocation");
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("ab", JsonhReaderOptions::new());
+    reader.parse_element().unwrap();
+    assert_eq!(reader.allocated_bytes, 2 + size_of::<Value>());
+}
+
+struct ChunkStream {
+    chunks: Vec<&'static str>,
+}
+
+impl futures_core::Stream for ChunkStream {
+    type Item = String;
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, _context: &mut std::task::Context<'_>) -> std::task::Poll<Option<String>> {
+        if self.chunks.is_empty() {
+            return std::task::Poll::Ready(None);
+        }
+        return std::task::Poll::Ready(Some(self.chunks.remove(0).to_string()));
+    }
+}
+
+#[test]
+pub fn partial_parser_test() {
+    let chunks: ChunkStream = ChunkStream { chunks: vec!["{ name: Al", "ice, tags: [\"", "a\"", ", b", "", "] }", " # done"] };
+    let mut stream: JsonhPartialStream<ChunkStream> = JsonhPartialParser::new(JsonhReaderOptions::new()).stream(chunks);
+
+    let mut context: std::task::Context<'_> = std::task::Context::from_waker(std::task::Waker::noop());
+    let mut values: Vec<Result<Value, JsonhError>> = Vec::new();
+    while let std::task::Poll::Ready(Some(value)) = futures_core::Stream::poll_next(std::pin::Pin::new(&mut stream), &mut context) {
+        values.push(value);
+    }
+
+    assert_eq!(values, [
+        Ok(serde_json::json!({ "name": "Al" })),
+        Ok(serde_json::json!({ "name": "Alice", "tags": [""] })),
+        Ok(serde_json::json!({ "name": "Alice", "tags": ["a"] })),
+        Ok(serde_json::json!({ "name": "Alice", "tags": ["a", "b"] })),
+    ]);
+
+    let mut parser: JsonhPartialParser = JsonhPartialParser::new(JsonhReaderOptions::new());
+    assert_eq!(parser.push("[1, "), Ok(serde_json::json!([1.0])));
+    assert!(parser.finish().is_err());
+}
+
+#[test]
+pub fn escape_unescape_test() {
+    assert_eq!(jsonh_rs::unescape("c\\u0064\\x65\\n"), Ok("cde\n".to_string()));
+    assert!(jsonh_rs::unescape("\\u12").is_err());
+
+    assert_eq!(jsonh_rs::escape("a\"'\tb", JsonhStringStyle::Double), "a\\\"'\\tb");
+    assert_eq!(jsonh_rs::escape("a\"'\tb", JsonhStringStyle::Single), "a\"\\'\\tb");
+    assert_eq!(jsonh_rs::escape("1, 2", JsonhStringStyle::Quoteless), "\\u0031\\, 2");
+
+    let values: [&str; 7] = ["1, 2", "true", "a: b", "{x}", "c\\d", "-e/f#", "@g'h\"\ni"];
+    for value in values {
+        for (style, quote) in [(JsonhStringStyle::Double, "\""), (JsonhStringStyle::Single, "'"), (JsonhStringStyle::Quoteless, "")] {
+            let source: String = format!("[{quote}{}{quote}]", jsonh_rs::escape(value, style));
+            assert_eq!(JsonhReader::parse_element_from_str(&source, JsonhReaderOptions::new()), Ok(serde_json::json!([value])), "{source}");
+        }
+    }
+
+    // Reserved characters of reader
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_version(JsonhVersion::V1);
+    let reader: JsonhReader<'_> = JsonhReader::from_str("", options).with_reserved_chars(&['$']);
+    assert_eq!(jsonh_rs::escape_for_reader("@a$b", JsonhStringStyle::Quoteless, &reader), "@a\\$b");
+    assert_eq!(jsonh_rs::escape("@a$b", JsonhStringStyle::Quoteless), "\\@a$b");
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[@a\\$b]", options).with_reserved_chars(&['$']);
+    assert_eq!(reader.parse_element(), Ok(serde_json::json!(["@a$b"])));
+}
+#[test]
+pub fn round_trip_test() {
+    let jsonh: &str = r#"
+// Config
+{
+  name: '''
+    multi
+    line
+    '''
+  ports: [0x1F90, 443,] # trailing comma
+  nested: { a\:b: null }
+}
+"#;
+    assert_round_trip(jsonh, JsonhFormatterOptions::new());
+    assert_eq!(check_round_trip(jsonh, JsonhFormatterOptions::new()), Ok(None));
+
+    let divergence: JsonhDivergence = JsonhDivergence::find(&serde_json::json!({ "a": [1, 2] }), &serde_json::json!({ "a": [1, 3] })).unwrap();
+    assert_eq!(divergence.to_string(), "a[1]: expected 2, got 3");
+    assert_eq!(JsonhDivergence::find(&serde_json::json!({ "a": 1 }), &serde_json::json!({})).unwrap().to_string(), "a: expected 1, got nothing");
+    assert_eq!(JsonhDivergence::find(&serde_json::json!({ "a": 1, "b": 2 }), &serde_json::json!({ "b": 2, "a": 1 })), None);
+}
+#[test]
+pub fn semantic_hash_test() {
+    let options: JsonhSemanticOptions = JsonhSemanticOptions::new();
+    let hash: u64 = semantic_hash_str("{ a: 0x10, b: [c, '''d'''] } // comment", options).unwrap();
+
+    assert_eq!(hash, semantic_hash_str("{\"b\": [\"c\", \"d\"], \"a\": 16}", options).unwrap());
+    assert_eq!(hash, semantic_hash(&serde_json::json!({ "a": 16, "b": ["c", "d"] }), options));
+    assert_ne!(hash, semantic_hash_str("{ a: 0x11, b: [c, d] }", options).unwrap());
+    assert_ne!(semantic_hash_str("[a, b]", options).unwrap(), semantic_hash_str("[b, a]", options).unwrap());
+    assert_ne!(semantic_hash_str("['ab', '']", options).unwrap(), semantic_hash_str("['a', 'b']", options).unwrap());
+    assert_eq!(semantic_hash_str("-0", options).unwrap(), semantic_hash_str("0", options).unwrap());
+}
+#[test]
+pub fn semantic_eq_test() {
+    let options: JsonhSemanticOptions = JsonhSemanticOptions::new();
+
+    assert_eq!(semantic_eq_str("{ a: 0x10, b: 'c' } # comment", "{\"b\": \"c\", \"a\": 16}", options), Ok(true));
+    assert_eq!(semantic_eq_str("[1, 2]", "[2, 1]", options), Ok(false));
+    assert_eq!(semantic_eq_str("{ a: 1 }", "{ a: 1, b: 2 }", options), Ok(false));
+    assert!(semantic_eq_str("[", "[]", options).is_err());
+
+    assert!(semantic_eq(&serde_json::json!({ "a": [16] }), &serde_json::json!({ "a": [16.0] }), options));
+    assert!(!semantic_eq(&serde_json::json!("16"), &serde_json::json!(16), options));
+}
+#[test]
+pub fn diff_test() {
+    let a: &str = "{ a: 0x10, b: [1, 2, 3], c: { d: x } } // comment";
+    let b: &str = r#"{"c": {"d": "y", "e/f": 1}, "a": 16.0, "b": [1, 4]}"#;
+    let operations: Vec<JsonhPatchOperation> = diff_str(a, b, JsonhReaderOptions::new()).unwrap();
+
+    assert_eq!(operations.iter().map(|operation| operation.to_string()).collect::<Vec<String>>(), [
+        "replace b[1]: 4.0",
+        "remove b[2]",
+        "replace c.d: \"y\"",
+        "add c.e/f: 1.0",
+    ]);
+    assert_eq!(patch_to_value(&operations)[3], serde_json::json!({ "op": "add", "path": "/c/e~1f", "value": 1.0 }));
+    assert_eq!(diff_str("[1, 2]", "[1.0, 2, [3]]", JsonhReaderOptions::new()).unwrap(), [
+        JsonhPatchOperation::add(JsonhPath::from_pointer("/2").unwrap(), serde_json::json!([3.0])),
+    ]);
+    assert_eq!(diff_str("{ a: 1 } # comment", "{\"a\": 1}", JsonhReaderOptions::new()).unwrap(), []);
+
+    let mut value: Value = JsonhReader::parse_element_from_str(a, JsonhReaderOptions::new()).unwrap();
+    apply_patch(&mut value, &operations).unwrap();
+    assert!(semantic_eq(&value, &JsonhReader::parse_element_from_str(b, JsonhReaderOptions::new()).unwrap(), JsonhSemanticOptions::new()));
+}
+#[test]
+pub fn patch_test() {
+    let patch: &str = r#"
+[
+    { op: test, path: "/a", value: 1 }
+    { op: add, path: "/b/-", value: 3 }
+    { op: add, path: "/b/0", value: 0 }
+    { op: move, from: "/a", path: "/c/a" }
+    { op: copy, from: "/b/1", path: "/c/0" }
+    { op: replace, path: "/d~1e", value: null }
+    { op: remove, path: "/b/2" }
+]
+"#;
+    let operations: Vec<JsonhPatchOperation> = parse_patch(patch, JsonhReaderOptions::new()).unwrap();
+    assert_eq!(operations[3].op, JsonhPatchOp::Move);
+    assert_eq!(operations[5].path.to_string(), "d/e");
+
+    let mut value: Value = serde_json::json!({ "a": 1, "b": [1, 2], "c": {}, "d/e": true });
+    apply_patch(&mut value, &operations).unwrap();
+    assert!(semantic_eq(&value, &serde_json::json!({ "b": [0, 1, 3], "c": { "a": 1, "0": 1 }, "d/e": null }), JsonhSemanticOptions::new()));
+
+    // Failed operations leave the element unchanged
+    let mut value: Value = serde_json::json!({ "a": 1 });
+    assert!(apply_patch(&mut value, &parse_patch(r#"[{ op: remove, path: "/a" }, { op: test, path: "/a", value: 1 }]"#, JsonhReaderOptions::new()).unwrap()).is_err());
+    assert_eq!(value, serde_json::json!({ "a": 1 }));
+    assert!(apply_patch(&mut value, &parse_patch(r#"[{ op: move, from: "", path: "/b" }]"#, JsonhReaderOptions::new()).unwrap()).is_err());
+    assert!(apply_patch(&mut value, &parse_patch(r#"[{ op: add, path: "/b/c", value: 1 }]"#, JsonhReaderOptions::new()).unwrap()).is_err());
+
+    assert!(parse_patch("[{ op: add, path: \"/a\" }]", JsonhReaderOptions::new()).is_err());
+    assert!(parse_patch("[{ op: rename, path: \"/a\" }]", JsonhReaderOptions::new()).is_err());
+    assert!(parse_patch("[{ op: remove, path: \"a\" }]", JsonhReaderOptions::new()).is_err());
+    assert_eq!(JsonhPath::from_pointer("/a~0b/01/1/-").unwrap().segments, [
+        JsonhPathSegment::Property("a~b".to_string()),
+        JsonhPathSegment::Property("01".to_string()),
+        JsonhPathSegment::Index(1),
+        JsonhPathSegment::Property("-".to_string()),
+    ]);
+    assert_eq!(JsonhPath::from_pointer("").unwrap().to_pointer(), "");
+    assert!(JsonhPath::from_pointer("/a~2").is_err());
+}
+#[test]
+pub fn type_hint_test() {
+    let jsonh: &str = r#"
+version: 1.20
+port: "0x1F90"
+enabled: ' true '
+tags: [null, 5]
+nested: { id: 007 }
+"#;
+    let element: Value = JsonhReader::from_str(jsonh, JsonhReaderOptions::new())
+        .with_type_hint("version", JsonhTypeHint::String)
+        .with_type_hint("port", JsonhTypeHint::Number)
+        .with_type_hint("enabled", JsonhTypeHint::Bool)
+        .with_type_hint("tags[1]", JsonhTypeHint::String)
+        .with_type_hint("nested.id", JsonhTypeHint::String)
+        .parse_element().unwrap();
+
+    assert_eq!(element, serde_json::json!({ "version": "1.20", "port": 8080.0, "enabled": true, "tags": [null, "5"], "nested": { "id": "007" } }));
+
+    assert_eq!(JsonhReader::from_str("a: b", JsonhReaderOptions::new()).with_type_hint("a", JsonhTypeHint::Number).parse_element().unwrap_err().message, "Expected number for type hint");
+    assert_eq!(JsonhReader::from_str("a: [1]", JsonhReaderOptions::new()).with_type_hint("a", JsonhTypeHint::String).parse_element().unwrap_err().message, "Expected string for type hint");
+    assert_eq!(JsonhReader::from_str("5", JsonhReaderOptions::new()).with_type_hint("$", JsonhTypeHint::String).parse_element(), Ok(serde_json::json!("5")));
+    assert_eq!(JsonhReader::from_str("{ 'a.b': 1, a: { b: 2 } }", JsonhReaderOptions::new()).with_type_hint(r"a\.b", JsonhTypeHint::String).parse_element(), Ok(serde_json::json!({ "a.b": "1", "a": { "b": 2.0 } })));
+}
+#[test]
+pub fn comment_limits_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_max_comment_length(Some(8));
+    assert_eq!(JsonhReader::parse_element_from_str("/* a very long comment */ 1", options).unwrap_err().message, "Exceeded max comment length");
+    assert_eq!(JsonhReader::parse_element_from_str(&format!("# {}", "a".repeat(1_000_000)), options).unwrap_err().message, "Exceeded max comment length");
+    assert!(JsonhReader::parse_element_from_str("/* short */ 1 # comment", options).is_ok());
+
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_max_comment_bytes(Some(10));
+    assert!(JsonhReader::parse_element_from_str("# 1234\n[1, # 5678\n2]", options).is_ok());
+    assert_eq!(JsonhReader::parse_element_from_str("# 1234\n[1, # 5678\n2, # 9\n]", options).unwrap_err().message, "Exceeded max comment bytes");
+}
+#[test]
+pub fn allow_comments_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_allow_comments(false);
+    for jsonh in ["# comment\n1", "[1, // comment\n2]", "{ a: /* comment */ 1 }", "1 /* trailing */"] {
+        assert_eq!(JsonhReader::parse_element_from_str(jsonh, options.with_parse_single_element(true)).unwrap_err().message, "Comments are not allowed", "{jsonh}");
+        assert!(JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new().with_parse_single_element(true)).is_ok());
+    }
+    assert_eq!(JsonhReader::parse_element_from_str("{ a: '# not a comment', b: 'x // y' }", options), Ok(serde_json::json!({ "a": "# not a comment", "b": "x // y" })));
+}
+#[test]
+pub fn strict_json_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::strict_json();
+    let json: &str = r#"{ "a": [1, -2.5, 3e10, 0.1E-2, true, false, null], "b": { "c": "d\n" }, "e": [], "f": {} }"#;
+    assert_eq!(JsonhReader::parse_element_from_str(json, options), JsonhReader::parse_element_from_str(json, JsonhReaderOptions::new()));
+    assert_eq!(JsonhReader::parse_element_from_str("\"text\"", options), Ok(serde_json::json!("text")));
+    assert_eq!(JsonhReader::parse_element_from_str("\t[\"\\\"\\\\\\/\\b\\f\\n\\r\\t\\u00e9\\uD83D\\uDE00\"]\r\n", options), Ok(serde_json::json!(["\"\\/\u{8}\u{c}\n\r\té😀"])));
+
+    // JSONH syntax
+    for (jsonh, error) in [
+        ("{ a: 1 }", "Quoteless strings are not allowed in JSON"),
+        ("['a']", "Single-quoted strings are not allowed in JSON"),
+        ("[\"\"\"a\"\"\"]", "Multi-quoted strings are not allowed in JSON"),
+        ("[@\"a\"]", "Verbatim strings are not allowed in JSON"),
+        ("\"a\": 1", "Braceless objects are not allowed in JSON"),
+        ("[1, 2,]", "Trailing commas are not allowed in JSON"),
+        ("{ \"a\": 1, }", "Trailing commas are not allowed in JSON"),
+        ("[1\n2]", "Expected `,` between elements in JSON"),
+        ("[1_000]", "Invalid number in JSON"),
+        ("[0x10]", "Invalid number in JSON"),
+        ("[01]", "Invalid number in JSON"),
+        ("[+1]", "Invalid number in JSON"),
+        ("[.5]", "Invalid number in JSON"),
+        ("# comment\n1", "Comments are not allowed"),
+        ("[1] [2]", "Expected end of elements"),
+        ("[1,,2]", "Expected element after `,` in JSON"),
+        ("{ \"a\": 1,, \"b\": 2 }", "Expected element after `,` in JSON"),
+        ("[,1]", "Expected element in JSON"),
+        ("[\"\\x41\"]", "Invalid escape sequence in JSON"),
+        ("[\"\\U00000041\"]", "Invalid escape sequence in JSON"),
+        ("[\"\\v\"]", "Invalid escape sequence in JSON"),
+        ("[\"\\'\"]", "Invalid escape sequence in JSON"),
+        ("[\"\\\n\"]", "Invalid escape sequence in JSON"),
+        ("[\"\\uD83D\\x00\"]", "Invalid escape sequence in JSON"),
+        ("[\"a\tb\"]", "Control characters must be escaped in JSON"),
+        ("[\"a\nb\"]", "Control characters must be escaped in JSON"),
+        ("[1,\u{00A0}2]", "Invalid whitespace in JSON"),
+        ("[1\u{00A0}, 2]", "Invalid whitespace in JSON"),
+        ("\u{2028}[1]", "Invalid whitespace in JSON"),
+        ("{ \"a\":\u{000B}1 }", "Invalid whitespace in JSON"),
+        ("[true\u{00A0}]", "Invalid whitespace in JSON"),
+    ] {
+        assert_eq!(JsonhReader::parse_element_from_str(jsonh, options).unwrap_err().message, error, "{jsonh}");
+    }
+}
+#[test]
+pub fn options_presets_test() {
+    assert_eq!(JsonhReader::parse_element_from_str("[1] trailing text", JsonhReaderOptions::lenient()), Ok(serde_json::json!([1.0])));
+    assert_eq!(JsonhReader::parse_element_from_str("[1] trailing text", JsonhReaderOptions::strict()).unwrap_err().message, "Expected end of elements");
+    assert!(JsonhReader::parse_element_from_str("[1", JsonhReaderOptions::strict()).is_err());
+
+    let options: JsonhReaderOptions = JsonhReaderOptions::llm_streaming();
+    assert_eq!(JsonhReader::parse_element_from_str("{ \"answer\": \"Paris\", \"sources\": [1, 2", options), Ok(serde_json::json!({ "answer": "Paris", "sources": [1.0, 2.0] })));
+    assert_eq!(JsonhReader::parse_element_from_str("[1, 2] Hope this helps!", options), Ok(serde_json::json!([1.0, 2.0])));
+    assert_eq!(JsonhReader::parse_element_from_str("{ \"answer\": \"Par", options), Ok(serde_json::json!({ "answer": "Par" })));
+    assert_eq!(JsonhReader::parse_element_from_str("{ answer: 'Paris\\", options), Ok(serde_json::json!({ "answer": "Paris" })));
+    assert_eq!(JsonhReader::parse_element_from_str("[\"\"\"\n  multi\n  line", options), Ok(serde_json::json!(["\n  multi\n  line"])));
+    assert!(options.unknown_versions && options.incomplete_inputs && !options.parse_single_element);
+    assert!(JsonhReader::parse_element_from_str("{ \"answer\": \"Par", JsonhReaderOptions::lenient()).is_err());
+
+    // Versions
+    let jsonh: &str = "{ a: 1 }";
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::lenient().with_version(JsonhVersion::Other(99))), Ok(serde_json::json!({ "a": 1.0 })));
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::strict().with_version(JsonhVersion::Other(99))).unwrap_err().message, "Unsupported JSONH version");
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::strict()), Ok(serde_json::json!({ "a": 1.0 })));
+
+    // JSON
+    assert_eq!(JsonhReader::parse_element_from_str("{ \"a\": [1, \"b\", null] }", JsonhReaderOptions::strict_json()), Ok(serde_json::json!({ "a": [1.0, "b", null] })));
+    assert!(JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::strict_json()).is_err());
+    assert!(JsonhReader::parse_element_from_str("[1] // comment", JsonhReaderOptions::strict_json()).is_err());
+}
+#[test]
+pub fn max_properties_and_items_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_max_properties(Some(2)).with_max_items(Some(2));
+    assert_eq!(JsonhReader::parse_element_from_str("{ a: 1, b: 2, c: 3 }", options).unwrap_err().message, "Exceeded max properties");
+    assert_eq!(JsonhReader::parse_element_from_str("a: 1\nb: 2\nc: 3", options).unwrap_err().message, "Exceeded max properties");
+    assert_eq!(JsonhReader::parse_element_from_str("[1, 2, 3]", options).unwrap_err().message, "Exceeded max items");
+    assert_eq!(JsonhReader::parse_element_from_str(&format!("[{}]", "0,".repeat(1_000_000)), options).unwrap_err().message, "Exceeded max items");
+    assert_eq!(JsonhReader::parse_element_from_str("{ a: [1, 2], b: { c: 3, d: 4 } }", options), Ok(serde_json::json!({ "a": [1.0, 2.0], "b": { "c": 3.0, "d": 4.0 } })));
+    assert_eq!(JsonhReader::parse_element_from_str("{ a: 1, b: 2, a: 3 }", options).unwrap_err().message, "Exceeded max properties");
+    assert_eq!(JsonhReader::parse_element_from_str("{a:1,a:2,a:3}", options.with_max_properties(Some(1))).unwrap_err().message, "Exceeded max properties");
+    assert_eq!(JsonhReader::parse_element_from_str("{ a: 1, a: 2 }", options), Ok(serde_json::json!({ "a": 2.0 })));
+}
+#[test]
+pub fn max_chars_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_max_chars(Some(8));
+    assert_eq!(JsonhReader::parse_element_from_str("[1, 2, 3, 4, 5]", options).unwrap_err().message, "Exceeded max chars");
+    assert_eq!(JsonhReader::parse_element_from_str("\"a very long string\"", options).unwrap_err().message, "Exceeded max chars");
+    assert_eq!(JsonhReader::parse_element_from_str(&format!("# {}", "a".repeat(1_000_000)), options).unwrap_err().message, "Exceeded max chars");
+    assert_eq!(JsonhReader::parse_element_from_str("[a, b, c]", options).unwrap_err().message, "Exceeded max chars");
+    assert_eq!(JsonhReader::parse_element_from_str("[a, b, c", options.incomplete_inputs(true)), Ok(serde_json::json!(["a", "b", "c"])));
+    assert_eq!(JsonhReader::parse_element_from_str("[a,b,c]", options), Ok(serde_json::json!(["a", "b", "c"])));
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, 2, 3]", options);
+    assert_eq!(reader.parse_element().unwrap_err().message, "Exceeded max chars");
+    assert_eq!(reader.char_counter, 8);
+}
+#[test]
+pub fn max_string_length_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_max_string_length(Some(8));
+    assert_eq!(JsonhReader::parse_element_from_str("\"a very long string\"", options).unwrap_err().message, "Exceeded max string length");
+    assert_eq!(JsonhReader::parse_element_from_str("[a very long string]", options).unwrap_err().message, "Exceeded max string length");
+    assert_eq!(JsonhReader::parse_element_from_str("{ \"a very long property\": 1 }", options).unwrap_err().message, "Exceeded max string length");
+    assert_eq!(JsonhReader::parse_element_from_str(&format!("'{}", "a".repeat(1_000_000)), options).unwrap_err().message, "Exceeded max string length");
+    assert_eq!(JsonhReader::parse_element_from_str("/* a very long comment */ 1", options), Ok(serde_json::json!(1.0)));
+    assert_eq!(JsonhReader::parse_element_from_str("[123456789]", options).unwrap_err().message, "Exceeded max string length");
+    assert_eq!(JsonhReader::parse_element_from_str(&format!("0x{}", "f".repeat(1_000_000)), options).unwrap_err().message, "Exceeded max string length");
+    assert_eq!(JsonhReader::parse_element_from_str("[12_345.5, 1e+100]", options), Ok(serde_json::json!([12345.5, 1e100])));
+    assert_eq!(JsonhReader::parse_element_from_str("{ key: 'short', list: [abc, \"12345678\"] } # comment", options), Ok(serde_json::json!({ "key": "short", "list": ["abc", "12345678"] })));
+}
+#[test]
+pub fn capacity_divisor_test() {
+    let jsonh: &str = "{ a: [1, 2, 3], b: 'string', c: quoteless # comment\n }";
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_capacity_divisor(Some(4));
+
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, options), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()));
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, options.with_capacity_divisor(Some(0))), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()));
+
+    let jsonh: String = format!("[1]{}", " ".repeat(1000));
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, options).unwrap().as_array().unwrap().capacity(), 16);
+    assert!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap().as_array().unwrap().capacity() < 16);
+}
+#[test]
+pub fn named_literal_test() {
+    let jsonh: &str = "[yes, no, None, pi, 'yes', \\u0079es, yes please]";
+    let element: Value = JsonhReader::from_str(jsonh, JsonhReaderOptions::new())
+        .with_named_literal("yes", Value::Bool(true))
+        .with_named_literal("no", Value::Bool(false))
+        .with_named_literal("None", Value::Null)
+        .with_named_literal("pi", serde_json::json!(3.14))
+        .parse_element().unwrap();
+
+    assert_eq!(element, serde_json::json!([true, false, null, 3.14, "yes", "yes", "yes please"]));
+
+    assert_eq!(JsonhReader::from_str("a: list", JsonhReaderOptions::new()).with_named_literal("list", serde_json::json!([])).parse_element().unwrap_err().message, "Named literal must not be an object or array");
+    assert_eq!(JsonhReader::from_str("a: name", JsonhReaderOptions::new()).with_named_literal("name", serde_json::json!("b")).parse_element(), Ok(serde_json::json!({ "a": "b" })));
+}
+#[test]
+pub fn arena_value_test() {
+    let bump: bumpalo::Bump = bumpalo::Bump::new();
+    let jsonh: &str = "{ a: [1, 'b\\tc', null], d: { e: true } } # comment";
+    let element: JsonhArenaValue<'_> = JsonhArenaValue::parse_from_str(jsonh, JsonhReaderOptions::new(), &bump).unwrap();
+
+    let JsonhArenaValue::Object(properties) = &element else {
+        panic!("Expected object");
+    };
+    assert_eq!(properties[0].0, "a");
+    assert_eq!(element.to_value().ok(), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()).ok());
+
+    assert_eq!(JsonhArenaValue::parse_from_str("[1, 2", JsonhReaderOptions::new(), &bump).unwrap_err().message, "Expected `]` to end array, got end of input");
+    assert!(JsonhArenaValue::parse_from_str("[1] [2]", JsonhReaderOptions::new().with_parse_single_element(true), &bump).is_err());
+}
+#[test]
+pub fn detect_format_test() {
+    assert_eq!(detect_format(" {\"a\": [1, -2.5e3, \"b\\u0041\", true, null], \"c\": {}} "), JsonhFormatGuess::Json);
+    assert_eq!(detect_format("\"\""), JsonhFormatGuess::Json);
+
+    assert_eq!(detect_format("{ a: [1, 2,] } // comment"), JsonhFormatGuess::JsonhV1);
+    assert_eq!(detect_format("a: b"), JsonhFormatGuess::JsonhV1);
+    assert_eq!(detect_format("[0x10]"), JsonhFormatGuess::JsonhV1);
+    assert_eq!(detect_format("[01]"), JsonhFormatGuess::JsonhV1);
+    assert_eq!(detect_format("['a']"), JsonhFormatGuess::JsonhV1);
+    assert_eq!(detect_format("[\"a\\'\"]"), JsonhFormatGuess::JsonhV1);
+    assert_eq!(detect_format("[1 2]"), JsonhFormatGuess::JsonhV1);
+    assert_eq!(detect_format("[1\n2]"), JsonhFormatGuess::JsonhV1);
+
+    assert_eq!(detect_format("{ a: @\"C:\\path\" }"), JsonhFormatGuess::JsonhV2);
+    assert_eq!(detect_format("/=* comment *=/ 1"), JsonhFormatGuess::JsonhV2);
+
+    assert_eq!(detect_format("{ a: [1, 2 }"), JsonhFormatGuess::NotJsonh);
+    assert_eq!(detect_format(""), JsonhFormatGuess::NotJsonh);
+    assert_eq!(detect_format("[1] [2]"), JsonhFormatGuess::NotJsonh);
+
+    let large: String = format!("[{}", "1, ".repeat(2000));
+    assert_eq!(detect_format(&large), JsonhFormatGuess::Json);
+}
+
+struct ByteStream {
+    chunks: Vec<&'static [u8]>,
+}
+
+impl futures_core::Stream for ByteStream {
+    type Item = &'static [u8];
+
+    fn poll_next(mut self: std::pin::Pin<&mut Self>, _context: &mut std::task::Context<'_>) -> std::task::Poll<Option<&'static [u8]>> {
+        if self.chunks.is_empty() {
+            return std::task::Poll::Ready(None);
+        }
+        return std::task::Poll::Ready(Some(self.chunks.remove(0)));
+    }
+}
+
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    let mut context: std::task::Context<'_> = std::task::Context::from_waker(std::task::Waker::noop());
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+pub fn async_reader_test() {
+    // Tokens from chunks (splitting 'ü' across chunks)
+    let chunks: ByteStream = ByteStream { chunks: vec![b"{ na", b"me: 'Al\xC3", b"\xBC', tags: [1", b"0, b] }", b" # done"] };
+    let mut reader: JsonhAsyncReader<'_> = JsonhAsyncReader::from_stream(chunks, JsonhReaderOptions::new());
+    let tokens: Vec<(JsonTokenType, String)> = block_on(async {
+        let mut tokens: Vec<(JsonTokenType, String)> = Vec::new();
+        while let Some(token) = reader.next_token().await {
+            let token: JsonhToken = token.unwrap();
+            tokens.push((token.json_type, token.value));
+        }
+        tokens
+    });
+    assert_eq!(tokens, [
+        (JsonTokenType::StartObject, "".to_string()),
+        (JsonTokenType::PropertyName, "name".to_string()),
+        (JsonTokenType::String, "Alü".to_string()),
+        (JsonTokenType::PropertyName, "tags".to_string()),
+        (JsonTokenType::StartArray, "".to_string()),
+        (JsonTokenType::Number, "10".to_string()),
+        (JsonTokenType::String, "b".to_string()),
+        (JsonTokenType::EndArray, "".to_string()),
+        (JsonTokenType::EndObject, "".to_string()),
+    ]);
+
+    // Errors at end of input
+    let chunks: ByteStream = ByteStream { chunks: vec![b"[1, ", b"2"] };
+    let mut reader: JsonhAsyncReader<'_> = JsonhAsyncReader::from_stream(chunks, JsonhReaderOptions::new());
+    let results: Vec<Result<JsonTokenType, JsonhError>> = block_on(async {
+        let mut results: Vec<Result<JsonTokenType, JsonhError>> = Vec::new();
+        while let Some(token) = reader.next_token().await {
+            results.push(token.map(|token| token.json_type));
+        }
+        results
+    });
+    assert_eq!(results, [Ok(JsonTokenType::StartArray), Ok(JsonTokenType::Number), Ok(JsonTokenType::Number), Err(JsonhError::from("Expected `]` to end array, got end of input").with_position(5).with_location(1, 6))]);
+
+    // Elements from async readers
+    let mut reader: JsonhAsyncReader<'_> = JsonhAsyncReader::from_async_read("{ a: [1, 2] }".as_bytes(), JsonhReaderOptions::new());
+    assert_eq!(block_on(reader.parse_element()), Ok(serde_json::json!({ "a": [1.0, 2.0] })));
+    assert_eq!(block_on(reader.next_token()), None);
+}
+
+#[test]
+pub fn push_parser_test() {
+    let mut parser: JsonhPushParser = JsonhPushParser::new(JsonhReaderOptions::new());
+    let json_types = |tokens: Vec<JsonhToken>| tokens.into_iter().map(|token| token.json_type).collect::<Vec<JsonTokenType>>();
+
+    parser.feed("{ a: [1");
+    assert_eq!(json_types(parser.drain_tokens()), [JsonTokenType::StartObject, JsonTokenType::PropertyName, JsonTokenType::StartArray]);
+    parser.feed("0, tr");
+    assert_eq!(json_types(parser.drain_tokens()), [JsonTokenType::Number]);
+    parser.feed("ue] }");
+    assert_eq!(json_types(parser.drain_tokens()), [JsonTokenType::True, JsonTokenType::EndArray, JsonTokenType::EndObject]);
+    assert_eq!(parser.finish(), Ok(()));
+    assert!(parser.drain_tokens().is_empty());
+
+    // Last token
+    let mut parser: JsonhPushParser = JsonhPushParser::new(JsonhReaderOptions::new());
+    parser.feed("12");
+    assert_eq!(parser.next_token(), None);
+    parser.feed("3");
+    assert_eq!(parser.finish(), Ok(()));
+    assert_eq!(parser.next_token().map(|token| token.value), Some("123".to_string()));
+
+    // Errors
+    let mut parser: JsonhPushParser = JsonhPushParser::new(JsonhReaderOptions::new());
+    parser.feed("[1, 2");
+    assert_eq!(parser.finish(), Err(JsonhError::from("Expected `]` to end array, got end of input").with_position(5).with_location(1, 6)));
+    assert_eq!(json_types(parser.drain_tokens()), [JsonTokenType::StartArray, JsonTokenType::Number, JsonTokenType::Number]);
+}
+
+#[test]
+pub fn push_parser_chunks_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new();
+    for (jsonh, options) in [
+        ("{ a: [1, 2.5e3, 'b', \"c\\nd\"], /* e */ f: { g: true } } # end", options),
+        ("a: b\r\nc: '''x''' , 'é😀': 0x1F", options),
+        ("[1] /* a */ [2]", options.with_parse_single_element(true)),
+        ("[1, { a: 'b", options.incomplete_inputs(true)),
+        ("[1, } ", options),
+        ("[1, 2", options),
+    ] {
+        // Read whole input
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, options);
+        let mut expected: Vec<JsonhToken> = Vec::new();
+        let mut expected_error: Option<JsonhError> = None;
+        for token in reader.read_element() {
+            match token {
+                Ok(token) => expected.push(token),
+                Err(error) => { expected_error = Some(error); break; },
+            }
+        }
+        if expected_error.is_none() && options.parse_single_element {
+            for token in reader.read_end_of_elements() {
+                match token {
+                    Ok(token) => expected.push(token),
+                    Err(error) => { expected_error = Some(error); break; },
+                }
+            }
+        }
+
+        // Split input at every offset
+        let mut chunkings: Vec<Vec<&str>> = jsonh.char_indices().map(|(offset, _)| vec![&jsonh[..offset], &jsonh[offset..]]).collect();
+        chunkings.push(jsonh.char_indices().map(|(offset, char)| &jsonh[offset..offset + char.len_utf8()]).collect());
+        for chunks in chunkings {
+            let mut parser: JsonhPushParser = JsonhPushParser::new(options);
+            let mut tokens: Vec<JsonhToken> = Vec::new();
+            for chunk in &chunks {
+                parser.feed(chunk);
+                tokens.extend(parser.drain_tokens());
+            }
+            let result: Result<(), JsonhError> = parser.finish();
+            tokens.extend(parser.drain_tokens());
+            assert_eq!(tokens, expected, "{chunks:?}");
+            assert_eq!(result.err(), expected_error, "{chunks:?}");
+        }
+    }
+}
+#[test]
+pub fn read_state_test() {
+    let mut parser: JsonhPushParser = JsonhPushParser::new(JsonhReaderOptions::new());
+    parser.feed("{ a: 'b");
+    assert!(matches!(parser.read_token(), Ok(JsonhReadState::Token(JsonhToken { json_type: JsonTokenType::StartObject, .. }))));
+    assert!(matches!(parser.read_token(), Ok(JsonhReadState::Token(JsonhToken { json_type: JsonTokenType::PropertyName, .. }))));
+    assert_eq!(parser.read_token(), Ok(JsonhReadState::NeedMoreInput));
+
+    // Resume
+    parser.feed("c' }");
+    assert_eq!(parser.read_token().map(|state| match state { JsonhReadState::Token(token) => token.value, _ => String::new() }), Ok("bc".to_string()));
+    assert!(matches!(parser.read_token(), Ok(JsonhReadState::Token(JsonhToken { json_type: JsonTokenType::EndObject, .. }))));
+    assert_eq!(parser.read_token(), Ok(JsonhReadState::NeedMoreInput));
+    assert_eq!(parser.finish(), Ok(()));
+    assert_eq!(parser.read_token(), Ok(JsonhReadState::EndOfInput));
+
+    // Resume partial tokens
+    let mut parser: JsonhPushParser = JsonhPushParser::new(JsonhReaderOptions::new());
+    parser.feed("[[{ a: \"x");
+    assert_eq!(parser.drain_tokens().len(), 4);
+    for chunk in ["y\\", "nz", "\"", "}]]"] {
+        assert_eq!(parser.read_token(), Ok(JsonhReadState::NeedMoreInput));
+        parser.feed(chunk);
+    }
+    let tokens: Vec<JsonhToken> = parser.drain_tokens();
+    assert_eq!((tokens[0].value.as_str(), tokens[0].start, tokens[0].end), ("xy\nz", 7, 14));
+    assert_eq!(tokens.len(), 4);
+
+    // Errors before the end of the input
+    let mut parser: JsonhPushParser = JsonhPushParser::new(JsonhReaderOptions::new());
+    parser.feed("[1, } ");
+    assert!(matches!(parser.read_token(), Ok(JsonhReadState::Token(JsonhToken { json_type: JsonTokenType::StartArray, .. }))));
+    assert!(matches!(parser.read_token(), Ok(JsonhReadState::Token(JsonhToken { json_type: JsonTokenType::Number, .. }))));
+    assert_eq!(parser.read_token().map_err(|error| (error.position, error.location)), Err((Some(4), Some((1, 5)))));
 }
\ No newline at end of file
@@ -8,7 +8,7 @@ pub fn basic_object_test() {
 }
 "#;
     let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
-    let tokens: Vec<Result<JsonhToken, &str>> = reader.read_element().collect();
+    let tokens: Vec<Result<JsonhToken, JsonhError>> = reader.read_element().collect();
 
     for token in &tokens {
         assert!(token.is_ok());
@@ -31,7 +31,7 @@ pub fn nestable_block_comment_test() {
 0
 "#;
     let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
-    let tokens: Vec<Result<JsonhToken, &str>> = reader.read_element().collect();
+    let tokens: Vec<Result<JsonhToken, JsonhError>> = reader.read_element().collect();
 
     for token in &tokens {
         assert!(token.is_ok());
@@ -50,7 +50,7 @@ pub fn nestable_block_comment_test() {
     let mut reader2: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new()
         .with_version(JsonhVersion::V1)
     );
-    let tokens2: Vec<Result<JsonhToken, &str>> = reader2.read_element().collect();
+    let tokens2: Vec<Result<JsonhToken, JsonhError>> = reader2.read_element().collect();
 
     assert!(tokens2[1].as_ref().is_err());
 }
@@ -71,4 +71,780 @@ pub fn find_property_value_test() {
 
     assert!(reader.find_property_value("c"));
     assert_eq!(reader.parse_element().unwrap(), "3");
+}
+#[test]
+pub fn structure_callback_test() {
+    let jsonh = r#"
+{
+  a: [1, { b: 2 }],
+  c: {}
+}
+"#;
+    let mut events: Vec<(JsonTokenType, String, i32)> = Vec::new();
+    {
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new())
+            .with_structure_callback(|json_type, path, depth| {
+                events.push((json_type, path.to_string(), depth));
+                return Ok(());
+            });
+        assert!(reader.parse_element().is_ok());
+    }
+
+    assert_eq!(events, [
+        (JsonTokenType::StartObject, "$".to_string(), 1),
+        (JsonTokenType::StartArray, "a".to_string(), 2),
+        (JsonTokenType::StartObject, "a[1]".to_string(), 3),
+        (JsonTokenType::EndObject, "a[1]".to_string(), 3),
+        (JsonTokenType::EndArray, "a".to_string(), 2),
+        (JsonTokenType::StartObject, "c".to_string(), 2),
+        (JsonTokenType::EndObject, "c".to_string(), 2),
+        (JsonTokenType::EndObject, "$".to_string(), 1),
+    ]);
+
+    let mut reader2: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new())
+        .with_structure_callback(|_json_type, path, _depth| {
+            return if path.to_string() == "c" { Err("Forbidden path") } else { Ok(()) };
+        });
+
+    assert_eq!(reader2.parse_element().unwrap_err().message, "Forbidden path");
+}
+
+#[test]
+pub fn token_span_test() {
+    let jsonh = r#"{ "a": b c , # d
+}"#;
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let tokens: Vec<JsonhToken> = reader.read_element().map(|token| token.unwrap()).collect();
+
+    let spans: Vec<(JsonTokenType, u64, u64)> = tokens.iter().map(|token| (token.json_type, token.start, token.end)).collect();
+    assert_eq!(spans, [
+        (JsonTokenType::StartObject, 0, 1),
+        (JsonTokenType::PropertyName, 2, 5),
+        (JsonTokenType::String, 7, 10),
+        (JsonTokenType::Comment, 13, 16),
+        (JsonTokenType::EndObject, 17, 18),
+    ]);
+}
+
+
+#[test]
+pub fn lazy_unescaping_test() {
+    let jsonh: &str = r#"{"a\tb": "c\u0064\
+e", f: 'g\\', h: """i\nj"""}"#;
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_lazy_unescaping(true);
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, options);
+    let tokens: Vec<JsonhToken> = reader.read_element().map(Result::unwrap).collect();
+    assert_eq!(tokens[1].value, "a\tb");
+    assert!(!tokens[1].is_escaped);
+    assert_eq!(tokens[2].value, "c\\u0064\\\ne");
+    assert!(tokens[2].is_escaped);
+    assert_eq!(tokens[2].unescaped_value().unwrap(), "cde");
+    assert_eq!(tokens[4].unescaped_value().unwrap(), "g\\");
+    assert_eq!(tokens[6].value, "i\nj");
+
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, options), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()));
+    assert!(JsonhReader::parse_element_from_str(r#""\q\u12""#, options).is_err());
+}
+
+struct OffsetSource {
+    chars: Vec<char>,
+    index: usize,
+    offset: u64,
+}
+
+impl JsonhSource for OffsetSource {
+    fn peek(&mut self) -> Option<char> {
+        return self.chars.get(self.index).copied();
+    }
+    fn next(&mut self) -> Option<char> {
+        let next: Option<char> = self.peek();
+        self.index += 1;
+        return next;
+    }
+    fn position(&self) -> u64 {
+        return self.offset + self.index as u64;
+    }
+}
+
+#[test]
+pub fn custom_source_test() {
+    let source: OffsetSource = OffsetSource { chars: "[a, 1]".chars().collect(), index: 0, offset: 10 };
+    let mut reader: JsonhReader<'_> = JsonhReader::from_source(source, JsonhReaderOptions::new());
+    let tokens: Vec<JsonhToken> = reader.read_element().map(Result::unwrap).collect();
+    assert_eq!((tokens[1].start, tokens[1].end), (11, 12));
+
+    let source: OffsetSource = OffsetSource { chars: "{a: b}".chars().collect(), index: 0, offset: 0 };
+    assert_eq!(JsonhReader::parse_element_from_source(source, JsonhReaderOptions::new()).unwrap(), serde_json::json!({ "a": "b" }));
+    assert_eq!(JsonhReader::parse_element_from_source(JsonhCharSource::new("[c]".chars()), JsonhReaderOptions::new()).unwrap(), serde_json::json!(["c"]));
+}
+
+#[test]
+pub fn token_display_test() {
+    let jsonh: &str = "{'a\"': [\"b\\n\", 0x_1F, null, true] /* c */ # d */\n}";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new().with_lazy_unescaping(true));
+    let tokens: Vec<String> = reader.read_element().map(|token| token.unwrap().to_string()).collect();
+
+    assert_eq!(tokens, ["{", "\"a\\\"\":", "[", "\"b\\n\"", "0x_1F", "null", "true", "]", "/* c */", "/=* d */*=/", "}"]);
+
+    let joined: String = tokens.join("\n");
+    assert_eq!(JsonhReader::parse_element_from_str(&joined, JsonhReaderOptions::new()), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()));
+}
+#[test]
+pub fn single_token_test() {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("'a\\tb' rest", JsonhReaderOptions::new());
+    let string: JsonhToken = reader.read_string().unwrap();
+    assert_eq!((string.json_type, string.value.as_str()), (JsonTokenType::String, "a\tb"));
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("-0x_1F apples", JsonhReaderOptions::new());
+    let number: JsonhToken = reader.read_number().unwrap();
+    assert_eq!((number.json_type, number.value.as_str()), (JsonTokenType::Number, "-0x_1F"));
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("/* note */ 1", JsonhReaderOptions::new());
+    let comment: JsonhToken = reader.read_comment().unwrap();
+    assert_eq!((comment.json_type, comment.value.as_str()), (JsonTokenType::Comment, " note "));
+
+    assert!(JsonhReader::from_str("apples", JsonhReaderOptions::new()).read_number().is_err());
+    assert!(JsonhReader::from_str("apples", JsonhReaderOptions::new()).read_comment().is_err());
+}
+#[test]
+pub fn tee_source_test() {
+    let jsonh: &str = "{ a: 'b' /* c */ } [d]";
+    let mut archive: String = String::new();
+
+    let source: JsonhTeeSource<_, _> = JsonhTeeSource::new(JsonhCharSource::new(jsonh.chars()), &mut archive);
+    let element: Value = JsonhReader::parse_element_from_source(source, JsonhReaderOptions::new()).unwrap();
+
+    assert_eq!(element, serde_json::json!({ "a": "b" }));
+    assert_eq!(archive, "{ a: 'b' /* c */ }");
+}
+#[test]
+pub fn read_source_test() {
+    let bytes: &[u8] = "{ a: 'ünïcødé 🎉', b: [1] }".as_bytes();
+    let element: Value = JsonhReader::parse_element_from_read(bytes, JsonhReaderOptions::new()).unwrap();
+    assert_eq!(element, serde_json::json!({ "a": "ünïcødé 🎉", "b": [1.0] }));
+
+    let invalid: &[u8] = b"'a\xFFb'";
+    assert_eq!(JsonhReader::parse_element_from_read(invalid, JsonhReaderOptions::new()), Ok(serde_json::json!("a\u{FFFD}b")));
+
+    // Failing byte reader
+    struct FailingRead {
+        bytes: &'static [u8],
+    }
+    impl std::io::Read for FailingRead {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            if self.bytes.is_empty() {
+                return Err(std::io::Error::other("disk unplugged"));
+            }
+            let length: usize = self.bytes.len().min(buffer.len());
+            buffer[..length].copy_from_slice(&self.bytes[..length]);
+            self.bytes = &self.bytes[length..];
+            return Ok(length);
+        }
+    }
+    for jsonh in ["[1, 2", "{ a: 1", "'abc", "abc"] {
+        let error: JsonhError = JsonhReader::parse_element_from_read(FailingRead { bytes: jsonh.as_bytes() }, JsonhReaderOptions::new()).unwrap_err();
+        assert_eq!(error.message, "Failed to read input", "{jsonh}");
+        assert_eq!(error.cause.map(|cause| cause.to_string()), Some("disk unplugged".to_string()), "{jsonh}");
+    }
+    let mut reader: JsonhReader<'_> = JsonhReader::from_read(FailingRead { bytes: b"[1, 2]" }, JsonhReaderOptions::new());
+    assert_eq!(jsonh_rs::from_reader::<Value>(&mut reader).unwrap_err().message, "Failed to read input");
+}
+
+#[test]
+pub fn parse_element_from_path_test() {
+    use std::io::Write;
+
+    let jsonh: &str = "# config\nport: 8080";
+    let directory: std::path::PathBuf = std::env::temp_dir().join(format!("jsonh_rs_tests_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let plain_path: std::path::PathBuf = directory.join("config.jsonh");
+    std::fs::write(&plain_path, jsonh).unwrap();
+
+    let gzip_path: std::path::PathBuf = directory.join("config.jsonh.gz");
+    let mut gzip_encoder: flate2::write::GzEncoder<Vec<u8>> = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    gzip_encoder.write_all(jsonh.as_bytes()).unwrap();
+    std::fs::write(&gzip_path, gzip_encoder.finish().unwrap()).unwrap();
+
+    let zstd_path: std::path::PathBuf = directory.join("config.jsonh.zst");
+    std::fs::write(&zstd_path, zstd::encode_all(jsonh.as_bytes(), 0).unwrap()).unwrap();
+
+    for path in [&plain_path, &gzip_path, &zstd_path] {
+        assert_eq!(JsonhReader::parse_element_from_path(path, JsonhReaderOptions::new()), Ok(serde_json::json!({ "port": 8080.0 })));
+    }
+    let gzip_file: std::fs::File = std::fs::File::open(&gzip_path).unwrap();
+    assert_eq!(JsonhReader::parse_element_from_read(flate2::read::GzDecoder::new(gzip_file), JsonhReaderOptions::new()), Ok(serde_json::json!({ "port": 8080.0 })));
+    assert_eq!(JsonhReader::parse_element_from_path(directory.join("missing.jsonh"), JsonhReaderOptions::new()).unwrap_err().message, "Failed to open file");
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+#[test]
+pub fn retain_escapes_test() {
+    let jsonh: &str = r#"{ "k\u0065y": ['a\tb', """
+    c\nd
+    """, quoteless\, e\ , plain] }"#;
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_retain_escapes(true);
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, options);
+    let tokens: Vec<JsonhToken> = reader.read_element().map(Result::unwrap).collect();
+    let values: Vec<(&str, bool)> = tokens.iter()
+        .filter(|token| matches!(token.json_type, JsonTokenType::PropertyName | JsonTokenType::String))
+        .map(|token| (token.value.as_str(), token.is_escaped))
+        .collect();
+    assert_eq!(values, [("k\\u0065y", true), ("a\\tb", true), ("c\\nd", true), ("quoteless\\, e", true), ("plain", false)]);
+
+    assert_eq!(JsonhReader::parse_element_from_str(jsonh, options), JsonhReader::parse_element_from_str(jsonh, JsonhReaderOptions::new()));
+}
+#[test]
+pub fn syntax_extension_test() {
+    let jsonh: &str = "{ a: $name, b: [%50, c], d: e$f }";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new())
+        .with_reserved_chars(&['$', '%'])
+        .with_primitive_extension('$', |source| {
+            source.next();
+            let mut name: String = String::new();
+            while let Some(next) = source.peek().filter(char::is_ascii_alphanumeric) {
+                name.push(next);
+                source.next();
+            }
+            return Ok(JsonhToken::new(JsonTokenType::String, format!("${{{name}}}")));
+        })
+        .with_primitive_extension('%', |source| {
+            source.next();
+            let mut digits: String = String::new();
+            while let Some(next) = source.peek().filter(char::is_ascii_digit) {
+                digits.push(next);
+                source.next();
+            }
+            return Ok(JsonhToken::new(JsonTokenType::Number, format!("0.{digits}")));
+        });
+
+    let tokens: Vec<Result<JsonhToken, JsonhError>> = reader.read_element().collect();
+    let variable: &JsonhToken = tokens.iter().flatten().find(|token| token.value == "${name}").unwrap();
+    assert_eq!((variable.start, variable.end), (5, 10));
+    assert_eq!(tokens.last().unwrap().as_ref().unwrap_err().message, "Empty quoteless string");
+
+    let element: Result<Value, JsonhError> = JsonhReader::from_str("[$]", JsonhReaderOptions::new())
+        .with_primitive_extension('$', |source| { source.next(); return Ok(JsonhToken::new(JsonTokenType::Number, "1".to_string())); })
+        .parse_element();
+    assert_eq!(element, Ok(serde_json::json!([1.0])));
+}
+#[test]
+pub fn comment_callback_test() {
+    let jsonh: &str = "# root\n{\n  // before a\n  a: /* a */ 1,\n  b: [ # first\n    2 ]\n}";
+    let mut comments: Vec<(String, String)> = Vec::new();
+
+    let element: Value = JsonhReader::from_str(jsonh, JsonhReaderOptions::new())
+        .with_comment_callback(|comment| {
+            comments.push((comment.path.to_string(), comment.value.clone()));
+            return Ok(());
+        })
+        .parse_element().unwrap();
+
+    assert_eq!(element, serde_json::json!({ "a": 1.0, "b": [2.0] }));
+    assert_eq!(comments, [
+        ("$".to_string(), " root".to_string()),
+        ("$".to_string(), " before a".to_string()),
+        ("a".to_string(), " a ".to_string()),
+        ("b".to_string(), " first".to_string()),
+    ]);
+
+    let result: Result<Value, JsonhError> = JsonhReader::from_str("[1, # TODO\n 2]", JsonhReaderOptions::new())
+        .with_comment_callback(|comment| if comment.value.contains("TODO") { Err("TODOs are forbidden") } else { Ok(()) })
+        .parse_element();
+    assert_eq!(result.unwrap_err().message, "TODOs are forbidden");
+}
+#[test]
+pub fn rope_source_test() {
+    let mut rope: ropey::Rope = ropey::Rope::from_str("{ a: 'ü' }");
+    rope.insert(8, ", b: [1, 2]");
+    let text: String = rope.to_string();
+
+    let mut rope_reader: JsonhReader<'_> = JsonhReader::from_source(JsonhRopeSource::new(rope.slice(..)), JsonhReaderOptions::new());
+    let rope_tokens: Vec<JsonhToken> = rope_reader.read_element().map(Result::unwrap).collect();
+    let mut string_reader: JsonhReader<'_> = JsonhReader::from_str(&text, JsonhReaderOptions::new());
+    let string_tokens: Vec<JsonhToken> = string_reader.read_element().map(Result::unwrap).collect();
+    assert_eq!(rope_tokens, string_tokens);
+
+    let b: &JsonhToken = rope_tokens.iter().find(|token| token.value == "b").unwrap();
+    assert_eq!(rope.slice(b.start as usize..b.end as usize), "b");
+
+    let element: Value = JsonhReader::parse_element_from_source(JsonhRopeSource::with_position(rope.slice(13..), 13), JsonhReaderOptions::new()).unwrap();
+    assert_eq!(element, serde_json::json!([1.0, 2.0]));
+}
+#[test]
+pub fn get_test() {
+    let jsonh: &str = r#"
+// Comment
+server: {
+    name: "a.b",
+    hosts: [
+        { port: 80, tags: [x, y] },
+        { port: 443, tags: [z] },
+    ],
+    "a.b": { c: true },
+}
+"#;
+
+    assert_eq!(jsonh_rs::get(jsonh, "server.hosts[1].port"), Some(serde_json::json!(443.0)));
+    assert_eq!(jsonh_rs::get(jsonh, "server.hosts[0].tags"), Some(serde_json::json!(["x", "y"])));
+    assert_eq!(jsonh_rs::get(jsonh, "server.hosts[1]"), Some(serde_json::json!({ "port": 443.0, "tags": ["z"] })));
+    assert_eq!(jsonh_rs::get(jsonh, "server.name"), Some(serde_json::json!("a.b")));
+    assert_eq!(jsonh_rs::get(jsonh, r"server.a\.b.c"), Some(serde_json::json!(true)));
+    assert_eq!(jsonh_rs::get(jsonh, "server.hosts[2]"), None);
+    assert_eq!(jsonh_rs::get(jsonh, "server.hosts.port"), None);
+    assert_eq!(jsonh_rs::get(jsonh, "server.missing"), None);
+    assert_eq!(jsonh_rs::get(jsonh, "$").unwrap()["server"]["hosts"][0]["port"], serde_json::json!(80.0));
+
+    // Invalid after the value
+    assert_eq!(jsonh_rs::get("[1, 2, {", "[1]"), Some(serde_json::json!(2.0)));
+
+    let path: JsonhPath = r"a.b\.c[2]".parse().unwrap();
+    assert_eq!(path.segments, vec![JsonhPathSegment::Property("a".to_string()), JsonhPathSegment::Property("b.c".to_string()), JsonhPathSegment::Index(2)]);
+    assert!("a[x]".parse::<JsonhPath>().is_err());
+
+    // Display escapes names
+    assert_eq!(path.to_string(), r"a.b\.c[2]");
+    let path: JsonhPath = JsonhPath { segments: vec![JsonhPathSegment::Property("$".to_string()), JsonhPathSegment::Property("[0]".to_string()), JsonhPathSegment::Property("d\\".to_string())] };
+    assert_eq!(path.to_string(), r"\$.\[0\].d\\");
+    assert_eq!(path.to_string().parse::<JsonhPath>(), Ok(path));
+}
+#[test]
+pub fn token_dump_test() {
+    let dump: String = jsonh_rs::dump_tokens_from_str("{ a: 'b\\n', c: [1, null] } # end", JsonhReaderOptions::new().with_lazy_unescaping(true)).unwrap();
+    assert_eq!(dump, r#"0..1 StartObject
+2..3 PropertyName "a"
+5..10 String "b\\n" escaped
+12..13 PropertyName "c"
+15..16 StartArray
+16..17 Number "1"
+19..23 Null "null"
+23..24 EndArray
+25..26 EndObject
+27..32 Comment " end"
+"#);
+
+    let tokens: Vec<JsonhToken> = jsonh_rs::parse_token_dump(&dump).unwrap();
+    assert_eq!(tokens.len(), 10);
+    assert_eq!(tokens[2], JsonhToken::new(JsonTokenType::String, "b\\n".to_string()).with_span(5, 10).with_escaped(true));
+    assert_eq!(jsonh_rs::dump_tokens(&tokens), dump);
+
+    assert!(jsonh_rs::parse_token_dump("0..1 Teapot").is_err());
+    assert!(jsonh_rs::parse_token_dump("0..1 String \"a\" b").is_err());
+}
+
+#[test]
+pub fn line_column_test() {
+    let jsonh: &str = "{\r\n    a: 1\r    b: [\u{2028}2, 😀]\n    c: 3\n    d]\n}";
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let error: JsonhError = reader.parse_element().unwrap_err();
+    assert_eq!(error.message, "Expected `:` after property name in object");
+    assert_eq!((reader.line_counter, reader.column_counter), (6, 6));
+    assert_eq!(error.to_string(), "Expected `:` after property name in object (line 6, column 6)");
+
+    let error: JsonhError = jsonh_rs::from_str::<Value>(jsonh).unwrap_err();
+    assert_eq!(error.location, Some((6, 6)));
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    let error: JsonhError = reader.read_element().find_map(|token| token.err()).unwrap();
+    assert_eq!(error.location, Some((6, 6)));
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_read("[1]\n\n[2]".as_bytes(), JsonhReaderOptions::new());
+    let error: JsonhError = jsonh_rs::from_reader::<Value>(&mut reader).unwrap_err();
+    assert_eq!(error.location, Some((3, 1)));
+}
+
+#[test]
+pub fn token_location_test() {
+    let jsonh: &str = "{\n    ä: 0x10, # ü\n    b: [\"😀\"]\n}";
+    let tokens: Vec<JsonhToken> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new()).read_element().collect::<Result<Vec<JsonhToken>, JsonhError>>().unwrap();
+
+    assert_eq!(tokens[1].json_type, JsonTokenType::PropertyName);
+    assert_eq!(tokens[1].location(jsonh), (2, 5));
+    assert_eq!(&jsonh[tokens[1].byte_span(jsonh)], "ä");
+    assert_eq!(tokens[2].location(jsonh), (2, 8));
+    assert_eq!(tokens[2].end_location(jsonh), (2, 12));
+    assert_eq!(&jsonh[tokens[2].byte_span(jsonh)], "0x10");
+    assert_eq!(&jsonh[tokens[3].byte_span(jsonh)], "# ü");
+    assert_eq!(&jsonh[tokens[6].byte_span(jsonh)], "\"😀\"");
+    assert_eq!(tokens[6].location(jsonh), (3, 9));
+    assert_eq!(tokens.last().unwrap().end_location(jsonh), (4, 2));
+}
+
+#[test]
+pub fn line_index_test() {
+    let source: &str = "{\r\n  a: \"é\"\n\r}\u{2028}";
+    let line_index: JsonhLineIndex<'_> = JsonhLineIndex::new(source);
+
+    assert_eq!(line_index.line_count(), 5);
+    assert_eq!(line_index.line(2), Some("  a: \"é\""));
+    assert_eq!(line_index.line(3), Some(""));
+    assert_eq!(line_index.line(6), None);
+
+    // Character positions
+    assert_eq!(line_index.location(0), (1, 1));
+    assert_eq!(line_index.location(2), (1, 2));
+    assert_eq!(line_index.location(5), (2, 3));
+    assert_eq!(line_index.location(9), (2, 7));
+    assert_eq!(line_index.location(13), (4, 1));
+    assert_eq!(line_index.location(100), (5, 1));
+    assert_eq!(line_index.position(2, 3), Some(5));
+    assert_eq!(line_index.position(2, 9), Some(11));
+    assert_eq!(line_index.position(2, 10), None);
+    assert_eq!(line_index.position(0, 1), None);
+
+    // Byte offsets
+    assert_eq!(line_index.offset_location(11), (2, 8));
+    assert_eq!(line_index.offset_location(10), (2, 7));
+    assert_eq!(line_index.offset(2, 8), Some(11));
+    assert_eq!(line_index.offset(4, 2), Some(15));
+    assert_eq!(line_index.offset_to_position(11), 10);
+    assert_eq!(line_index.position_to_offset(9), 9);
+    assert_eq!(line_index.position_to_offset(100), source.len());
+
+    // Token spans
+    for token in JsonhReader::from_str(source, JsonhReaderOptions::new()).read_element() {
+        let token: JsonhToken = token.unwrap();
+        assert_eq!(line_index.location(token.start), token.location(source));
+        assert_eq!(line_index.position_to_offset(token.start)..line_index.position_to_offset(token.end), token.byte_span(source));
+    }
+}
+
+#[test]
+pub fn char_iter_test() {
+    // Generated stream
+    let generated = std::iter::once('[').chain((1..=3).flat_map(|number| format!("{number},").chars().collect::<Vec<char>>())).chain(std::iter::once(']'));
+    assert_eq!(JsonhReader::parse_element_from_char_iter(generated, JsonhReaderOptions::new()).unwrap(), serde_json::json!([1.0, 2.0, 3.0]));
+
+    // Decoded stream
+    let decoded = char::decode_utf16("{ a: 'ü' }".encode_utf16()).map(|next| next.unwrap_or(char::REPLACEMENT_CHARACTER));
+    let mut reader: JsonhReader<'_> = JsonhReader::from_char_iter(decoded, JsonhReaderOptions::new());
+    assert_eq!(reader.parse_element().unwrap(), serde_json::json!({ "a": "ü" }));
+    assert_eq!(reader.char_counter, 10);
+}
+
+#[test]
+pub fn buf_read_test() {
+    // Multi-byte characters split across buffer boundaries
+    let jsonh: &str = "{ a: 'ünïcødé 🎉', b: [1, 2] }";
+    let buffered: std::io::BufReader<&[u8]> = std::io::BufReader::with_capacity(3, jsonh.as_bytes());
+    let mut reader: JsonhReader<'_> = JsonhReader::from_source(JsonhReadSource::from_buf_read(buffered), JsonhReaderOptions::new());
+    assert_eq!(reader.parse_element().unwrap(), serde_json::json!({ "a": "ünïcødé 🎉", "b": [1.0, 2.0] }));
+    assert_eq!(reader.char_counter, jsonh.chars().count() as u64);
+
+    // Already buffered byte readers
+    assert_eq!(JsonhReader::parse_element_from_read(std::io::Cursor::new("[a, b]"), JsonhReaderOptions::new()), Ok(serde_json::json!(["a", "b"])));
+    let mut source: JsonhReadSource<&[u8]> = JsonhReadSource::from_buf_read(b"\xC3\xBC".as_slice());
+    assert_eq!((source.next(), source.next(), source.position), (Some('ü'), None, 1));
+}
+
+#[test]
+pub fn from_bytes_test() {
+    assert_eq!(JsonhReader::parse_element_from_bytes("{ a: 'é🎉' }".as_bytes(), JsonhReaderOptions::new()), Ok(serde_json::json!({ "a": "é🎉" })));
+
+    // Invalid sequence
+    let mut reader: JsonhReader<'_> = JsonhReader::from_bytes(b"[1,\n 'a\xFFb']", JsonhReaderOptions::new());
+    let error: JsonhError = reader.parse_element().unwrap_err();
+    assert_eq!(error.message, "Invalid UTF-8 sequence");
+    assert_eq!(error.position, Some(7));
+    assert_eq!(error.to_string(), "Invalid UTF-8 sequence (line 2, column 4)");
+
+    // Truncated sequence
+    assert_eq!(JsonhReader::parse_element_from_bytes(b"'a\xC3", JsonhReaderOptions::new()).unwrap_err().message, "Invalid UTF-8 sequence");
+    // Invalid sequence after element
+    assert_eq!(JsonhReader::parse_element_from_bytes(b"[1] \xFF", JsonhReaderOptions::new().with_parse_single_element(true)).unwrap_err().message, "Invalid UTF-8 sequence");
+}
+#[test]
+pub fn next_token_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new();
+    let incomplete_options: JsonhReaderOptions = JsonhReaderOptions::new().incomplete_inputs(true);
+    for (jsonh, options) in [
+        ("{ a: [1, 'b', { c: null }], /* d */ e: true }", options),
+        ("a: 1 # comment\nb: { c: [] }", options),
+        ("'text' /* comment */", options),
+        ("[1, { a: 2", options),
+        ("[1, { a: 2", incomplete_options),
+        ("{ a: [1, b: 2 }", options),
+        ("{ a 1 }", options),
+        ("", options),
+    ] {
+        let expected: Vec<Result<JsonhToken, JsonhError>> = JsonhReader::from_str(jsonh, options).read_element().collect();
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, options);
+        let actual: Vec<Result<JsonhToken, JsonhError>> = std::iter::from_fn(|| reader.next_token()).collect();
+        assert_eq!(actual, expected, "{jsonh}");
+    }
+
+    // Peek tokens
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1] 2", JsonhReaderOptions::new());
+    assert_eq!(reader.peek_token().map(|token| token.as_ref().unwrap().json_type), Some(JsonTokenType::StartArray));
+    assert_eq!(reader.next_token().map(|token| token.unwrap().json_type), Some(JsonTokenType::StartArray));
+    assert_eq!(reader.next_token().map(|token| token.unwrap().value), Some("1".to_string()));
+    assert_eq!(reader.next_token().map(|token| token.unwrap().json_type), Some(JsonTokenType::EndArray));
+    assert!(reader.peek_token().is_none());
+    assert!(reader.next_token().is_none());
+
+    // Next element
+    assert_eq!(reader.next_token().map(|token| token.unwrap().value), Some("2".to_string()));
+    assert!(reader.next_token().is_none());
+}
+#[test]
+pub fn token_iter_test() {
+    struct TokenCounter<'r> {
+        tokens: std::iter::Peekable<JsonhTokenIter<'r>>,
+        count: usize,
+    }
+
+    // Stored iterator
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, { a: 2 }] 3", JsonhReaderOptions::new());
+    let mut counter: TokenCounter<'_> = TokenCounter { tokens: reader.read_element().peekable(), count: 0 };
+    while counter.tokens.next_if(|token| token.is_ok()).is_some() {
+        counter.count += 1;
+    }
+    assert_eq!(counter.count, 7);
+    assert!(counter.tokens.peek().is_none());
+
+    // Abandoned iterator
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{ a: [1, 2], b: 3 }", JsonhReaderOptions::new());
+    assert!(reader.find_property_value("a"));
+    assert_eq!(reader.parse_element(), Ok(serde_json::json!([1.0, 2.0])));
+}
+#[test]
+pub fn skip_value_test() {
+    // Next element
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, { a: [2] }] 'next'", JsonhReaderOptions::new());
+    assert_eq!(reader.skip_value(), Ok(()));
+    assert_eq!(reader.parse_element(), Ok(serde_json::json!("next")));
+
+    // Properties in element
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{ a: { b: [1, 2] }, /* c */ c: 3, d: [4] }", JsonhReaderOptions::new());
+    let mut values: Vec<JsonhToken> = Vec::new();
+    while let Some(token) = reader.next_token() {
+        let token: JsonhToken = token.unwrap();
+        if token.json_type == JsonTokenType::PropertyName && token.value != "c" {
+            reader.skip_value().unwrap();
+        }
+        else if token.json_type == JsonTokenType::Number {
+            values.push(token);
+        }
+    }
+    assert_eq!(values.iter().map(|token| token.value.as_str()).collect::<Vec<&str>>(), ["3"]);
+
+    // Values not kept
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{ a: 'long string' /* comment */, b: quoteless }", JsonhReaderOptions::new());
+    assert_eq!(reader.next_token().unwrap().unwrap().json_type, JsonTokenType::StartObject);
+    assert_eq!(reader.next_token().unwrap().unwrap().value, "a");
+    assert_eq!(reader.skip_value(), Ok(()));
+    assert_eq!(reader.allocated_bytes, 1);
+    assert_eq!(reader.next_token().unwrap().unwrap().value, "b");
+    assert_eq!(reader.next_token().unwrap().unwrap().value, "quoteless");
+
+    // Error
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{ a: [1, 2 }", JsonhReaderOptions::new());
+    assert!(reader.next_token().is_some());
+    assert!(reader.next_token().is_some());
+    assert!(reader.skip_value().is_err());
+}
+#[test]
+pub fn reset_test() {
+    let mut depths: Vec<i32> = Vec::new();
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[1, { a: 2", JsonhReaderOptions::new())
+        .with_structure_callback(|_, _, depth| { depths.push(depth); Ok(()) });
+    assert!(reader.parse_element().is_err());
+    assert_eq!(reader.depth, 2);
+
+    // Reuse reader
+    reader.reset(JsonhCharSource::new("{\n  a: [2]\n}".chars()));
+    assert_eq!((reader.char_counter, reader.line_counter, reader.depth), (0, 1, 0));
+    assert_eq!(reader.parse_element(), Ok(serde_json::json!({ "a": [2.0] })));
+    assert_eq!((reader.char_counter, reader.line_counter, reader.column_counter), (12, 3, 2));
+    drop(reader);
+    assert_eq!(depths, [1, 2, 1, 2, 2, 1]);
+}
+#[test]
+pub fn parse_element_from_path_errors_test() {
+    let directory: std::path::PathBuf = std::env::temp_dir().join(format!("jsonh_rs_file_tests_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let path: std::path::PathBuf = directory.join("config.jsonh");
+    std::fs::write(&path, "# config\nport: 8080").unwrap();
+    assert_eq!(JsonhReader::parse_element_from_path(&path, JsonhReaderOptions::new()), Ok(serde_json::json!({ "port": 8080.0 })));
+
+    // Syntax error
+    std::fs::write(&path, "{\n  port 8080\n}").unwrap();
+    let error: JsonhError = JsonhReader::parse_element_from_path(&path, JsonhReaderOptions::new()).unwrap_err();
+    assert_eq!(error.file_path.as_deref(), Some(path.as_path()));
+    assert_eq!(error.to_string(), format!("Expected `:` after property name in object in {} (line 3, column 1)", path.display()));
+
+    // I/O error
+    let missing_path: std::path::PathBuf = directory.join("missing.jsonh");
+    let error: JsonhError = JsonhReader::parse_element_from_path(&missing_path, JsonhReaderOptions::new()).unwrap_err();
+    assert_eq!(error.file_path.as_deref(), Some(missing_path.as_path()));
+    assert!(std::error::Error::source(&error).is_some());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+#[test]
+pub fn remaining_input_test() {
+    let (header, body): (serde_json::Value, &str) = JsonhReader::parse_element_prefix("{ version: 2 }\n---\nbody é", JsonhReaderOptions::new().with_parse_single_element(true)).unwrap();
+    assert_eq!(header, serde_json::json!({ "version": 2.0 }));
+    assert_eq!(body, "\n---\nbody é");
+    assert_eq!(JsonhReader::parse_element_prefix("[1, 'ü']", JsonhReaderOptions::new()), Ok((serde_json::json!([1.0, "ü"]), "")));
+    assert!(JsonhReader::parse_element_prefix("{ a: ", JsonhReaderOptions::new()).is_err());
+
+    // Rest of source
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("'ü' rest\nof source", JsonhReaderOptions::new());
+    assert_eq!(reader.parse_element(), Ok(serde_json::json!("ü")));
+    assert_eq!(reader.read_rest(), "rest\nof source");
+    assert_eq!((reader.char_counter, reader.line_counter), (18, 2));
+}
+#[test]
+pub fn validate_test() {
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_parse_single_element(true);
+    assert_eq!(JsonhReader::from_str("{ a: [1, 'b', { c: null }] } # comment", options).validate(), Ok(()));
+    assert_eq!(JsonhReader::from_str("a: 1\nb: 2", options).validate(), Ok(()));
+
+    // Invalid
+    let error: JsonhError = JsonhReader::from_str("{ a: [1, 2 }", options).validate().unwrap_err();
+    assert_eq!(JsonhReader::parse_element_from_str("{ a: [1, 2 }", options), Err(error));
+    assert_eq!(JsonhReader::from_str("[1] [2]", options).validate().unwrap_err().message, "Expected end of elements");
+    assert_eq!(JsonhReader::from_str("[[[1]]]", options.with_max_depth(2)).validate().unwrap_err().message, "Exceeded max depth");
+    assert_eq!(JsonhReader::from_str("[1] [2]", JsonhReaderOptions::new()).validate(), Ok(()));
+    assert_eq!(JsonhReader::from_str(r#"["\q"]"#, options.with_strict_json(true)).validate().unwrap_err().message, "Invalid escape sequence in JSON");
+
+    // Values not kept
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("{ name: 'long string', \"\"\"\n  multi\n  \"\"\" : quoteless string } /* comment */", options.with_max_allocation(Some(4)));
+    assert_eq!(reader.validate(), Ok(()));
+    assert_eq!(reader.allocated_bytes, 0);
+}
+#[test]
+pub fn visitor_test() {
+    use std::ops::ControlFlow;
+
+    #[derive(Default)]
+    struct EventVisitor {
+        events: Vec<String>,
+        stop_at: Option<&'static str>,
+    }
+    impl JsonhVisitor for EventVisitor {
+        fn on_start_object(&mut self, _token: &JsonhToken) -> ControlFlow<()> {
+            self.events.push("{".to_string());
+            return ControlFlow::Continue(());
+        }
+        fn on_end_object(&mut self, _token: &JsonhToken) -> ControlFlow<()> {
+            self.events.push("}".to_string());
+            return ControlFlow::Continue(());
+        }
+        fn on_property_name(&mut self, name: &str, _token: &JsonhToken) -> ControlFlow<()> {
+            self.events.push(format!("{name}:"));
+            if self.stop_at == Some(name) {
+                return ControlFlow::Break(());
+            }
+            return ControlFlow::Continue(());
+        }
+        fn on_string(&mut self, value: &str, _token: &JsonhToken) -> ControlFlow<()> {
+            self.events.push(format!("'{value}'"));
+            return ControlFlow::Continue(());
+        }
+        fn on_number(&mut self, value: &str, _token: &JsonhToken) -> ControlFlow<()> {
+            self.events.push(value.to_string());
+            return ControlFlow::Continue(());
+        }
+        fn on_bool(&mut self, value: bool, _token: &JsonhToken) -> ControlFlow<()> {
+            self.events.push(value.to_string());
+            return ControlFlow::Continue(());
+        }
+        fn on_comment(&mut self, value: &str, _token: &JsonhToken) -> ControlFlow<()> {
+            self.events.push(format!("#{value}"));
+            return ControlFlow::Continue(());
+        }
+    }
+
+    let jsonh: &str = "{ a: 'x\\ty', /*c*/ b: 0x1F, d: [true, null] }";
+    let mut visitor: EventVisitor = EventVisitor::default();
+    assert_eq!(JsonhReader::from_str(jsonh, JsonhReaderOptions::new()).drive(&mut visitor), Ok(()));
+    assert_eq!(visitor.events, ["{", "a:", "'x\ty'", "#c", "b:", "0x1F", "d:", "true", "}"]);
+
+    // Stop early
+    let mut visitor: EventVisitor = EventVisitor { stop_at: Some("b"), ..EventVisitor::default() };
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    assert_eq!(reader.drive(&mut visitor), Ok(()));
+    assert_eq!(visitor.events, ["{", "a:", "'x\ty'", "#c", "b:"]);
+    assert_eq!(reader.parse_element(), Ok(serde_json::json!(31.0)));
+
+    // Error
+    assert_eq!(JsonhReader::from_str("{ a: [1", JsonhReaderOptions::new()).drive(&mut EventVisitor::default()).unwrap_err().message, "Expected `]` to end array, got end of input");
+}
+#[test]
+pub fn reader_stats_test() {
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("# header\n{ a: [[1], 'b'], /* c */ c: null } [2]", JsonhReaderOptions::new());
+    assert!(reader.parse_element().is_ok());
+    assert!(reader.parse_element().is_ok());
+
+    assert_eq!(reader.stats.token_count(JsonTokenType::Comment), 2);
+    assert_eq!(reader.stats.token_count(JsonTokenType::Number), 2);
+    assert_eq!(reader.stats.token_count(JsonTokenType::StartArray), 3);
+    assert_eq!(reader.stats.total_tokens(), 16);
+    assert_eq!(reader.stats.max_depth, 3);
+    assert_eq!(reader.char_counter, 47);
+
+    // Reset
+    reader.reset(JsonhCharSource::new("1".chars()));
+    assert_eq!(reader.stats, JsonhParseStats::new());
+}
+#[test]
+pub fn find_path_value_test() {
+    let jsonh: &str = r#"
+        # Config
+        a: { c: 1 }
+        b: [
+            { c: 2 },
+            /* Item */ { c: 3, d: [4, [5, 6]] },
+        ]
+    "#;
+    let find = |path: &str| -> Option<serde_json::Value> {
+        let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+        if !reader.find_path_value(&path.parse().unwrap()) {
+            return None;
+        }
+        return Some(reader.parse_element().unwrap());
+    };
+    assert_eq!(find("a.c"), Some(serde_json::json!(1.0)));
+    assert_eq!(find("b[1].c"), Some(serde_json::json!(3.0)));
+    assert_eq!(find("b[0]"), Some(serde_json::json!({ "c": 2.0 })));
+    assert_eq!(find("b[1].d[1][0]"), Some(serde_json::json!(5.0)));
+    assert_eq!(find("b[1].d[1]"), Some(serde_json::json!([5.0, 6.0])));
+
+    // Not found
+    assert_eq!(find("c"), None);
+    assert_eq!(find("b[2]"), None);
+    assert_eq!(find("a[0]"), None);
+    assert_eq!(find("a.c.d"), None);
+
+    // Segments
+    let path: JsonhPath = JsonhPath { segments: vec![JsonhPathSegment::Index(1), JsonhPathSegment::Property("x".to_string())] };
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str("[{ x: 1 }, { x: 'two' }]", JsonhReaderOptions::new());
+    assert!(reader.find_path_value(&path));
+    assert_eq!(reader.parse_element(), Ok(serde_json::json!("two")));
+}
+#[test]
+pub fn find_and_parse_property_test() {
+    let jsonh: &str = "server: { Port: 8080, hosts: ['a', 'b'] }";
+
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    assert_eq!(reader.find_and_parse_property::<Vec<String>>(&"server.hosts".parse().unwrap()), Ok(Some(vec!["a".to_string(), "b".to_string()])));
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, JsonhReaderOptions::new());
+    assert_eq!(reader.find_and_parse_property::<u16>(&"server.port".parse().unwrap()), Ok(None));
+
+    // Ignore case
+    let options: JsonhReaderOptions = JsonhReaderOptions::new().with_ignore_property_name_case(true);
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, options);
+    assert_eq!(reader.find_and_parse_property::<u16>(&"SERVER.port".parse().unwrap()), Ok(Some(8080)));
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, options);
+    assert!(reader.find_property_value("Server"));
+
+    // Invalid value
+    let mut reader: JsonhReader<'_> = JsonhReader::from_str(jsonh, options);
+    let error: JsonhError = reader.find_and_parse_property::<bool>(&"server.port".parse().unwrap()).unwrap_err();
+    assert!(error.message.starts_with("invalid type"));
 }
\ No newline at end of file
@@ -1,3 +1,8 @@
 pub mod read_tests;
 pub mod parse_tests;
-pub mod edge_case_tests;
\ No newline at end of file
+pub mod edge_case_tests;
+pub mod format_tests;
+pub mod lint_tests;
+pub mod deserialize_tests;
+pub mod conformance_tests;
+pub mod write_tests;
\ No newline at end of file
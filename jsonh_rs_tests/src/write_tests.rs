@@ -0,0 +1,373 @@
+use jsonh_rs::*;
+
+#[test]
+pub fn write_value_test() {
+    let element: Value = serde_json::json!({
+        "a": [1, 2.5, "b\n\"c\""],
+        "d": { "e": null, "f": true, "g": false },
+        "h": [],
+        "i": {},
+    });
+
+    let jsonh: String = JsonhWriter::write_to_string(&element, JsonhWriterOptions::new());
+    assert_eq!(jsonh, r#"{
+    "a": [
+        1,
+        2.5,
+        "b\n\"c\""
+    ],
+    "d": {
+        "e": null,
+        "f": true,
+        "g": false
+    },
+    "h": [],
+    "i": {}
+}"#);
+
+    let reparsed: Value = JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap();
+    assert!(jsonh_rs::semantic_eq(&reparsed, &element, JsonhSemanticOptions::new()));
+}
+#[test]
+pub fn writer_options_test() {
+    let element: Value = serde_json::json!({ "a": [1, "b"], "c": {} });
+    let options: JsonhWriterOptions = JsonhWriterOptions::new()
+        .with_indent_width(1)
+        .with_indent_char('\t')
+        .with_newline("\r\n")
+        .with_quote_style(JsonhQuoteStyle::Single)
+        .with_braceless_root(true)
+        .with_commas(false);
+
+    let jsonh: String = JsonhWriter::write_to_string(&element, options);
+    assert_eq!(jsonh, "'a': [\r\n\t1\r\n\t'b'\r\n]\r\n'c': {}");
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), serde_json::json!({ "a": [1.0, "b"], "c": {} }));
+
+    assert_eq!(JsonhWriter::write_to_string(&serde_json::json!({}), options), "{}");
+
+    // Commas are still written on a single line
+    let element: Value = serde_json::json!({ "a": [12, "x y", "a\nb"], "b": true });
+    let jsonh: String = JsonhWriter::write_to_string(&element, options.with_newline("").with_quoteless_strings(true));
+    assert_eq!(jsonh, "a:[12,x y,'a\\nb'],b:true");
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), serde_json::json!({ "a": [12.0, "x y", "a\nb"], "b": true }));
+}
+#[test]
+pub fn write_tokens_test() {
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new().with_indent_width(2));
+    writer.write_start_object().unwrap();
+    writer.write_property_name("rows").unwrap();
+    writer.write_start_array().unwrap();
+    for row in 0..3 {
+        writer.write_number(&serde_json::Number::from(row)).unwrap();
+    }
+    writer.write_end_array().unwrap();
+    writer.write_property_name("meta").unwrap();
+    writer.write_value(&serde_json::json!({ "done": true })).unwrap();
+    writer.write_property_name("empty").unwrap();
+    writer.write_start_array().unwrap();
+    writer.write_end_array().unwrap();
+    writer.write_property_name("none").unwrap();
+    writer.write_null().unwrap();
+    assert!(!writer.is_complete());
+    writer.write_end_object().unwrap();
+    assert!(writer.is_complete());
+
+    assert_eq!(String::from_utf8(writer.writer).unwrap(), r#"{
+  "rows": [
+    0,
+    1,
+    2
+  ],
+  "meta": {
+    "done": true
+  },
+  "empty": [],
+  "none": null
+}"#);
+
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new());
+    writer.write_start_object().unwrap();
+    assert_eq!(writer.write_string("a"), Err("Expected property name in object"));
+    assert_eq!(writer.write_end_array(), Err("Expected `}` to end object"));
+    writer.write_property_name("a").unwrap();
+    assert_eq!(writer.write_end_object(), Err("Expected property value before end of object"));
+    writer.write_bool(false).unwrap();
+    writer.write_end_object().unwrap();
+    assert_eq!(writer.write_null(), Err("Expected single element"));
+
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new().with_braceless_root(true));
+    writer.write_start_object().unwrap();
+    writer.write_end_object().unwrap();
+    assert_eq!(String::from_utf8(writer.writer).unwrap(), "{}");
+}
+#[test]
+pub fn write_quoteless_strings_test() {
+    let element: Value = serde_json::json!({
+        "name": "hello world",
+        "a:b": "true",
+        "10": ["10", " padded", "x, y", "line 1\nline 2", "@verbatim", ""],
+    });
+
+    let jsonh: String = JsonhWriter::write_to_string(&element, JsonhWriterOptions::new().with_quoteless_strings(true));
+    assert_eq!(jsonh, r#"{
+    10: [
+        "10",
+        " padded",
+        "x, y",
+        "line 1\nline 2",
+        "@verbatim",
+        ""
+    ],
+    "a:b": "true",
+    name: hello world
+}"#);
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), element);
+
+    let jsonh: String = JsonhWriter::write_to_string(&serde_json::json!(["@verbatim"]), JsonhWriterOptions::new().with_version(JsonhVersion::V1).with_quoteless_strings(true));
+    assert_eq!(jsonh, "[\n    @verbatim\n]");
+}
+#[test]
+pub fn write_multi_quoted_strings_test() {
+    let element: Value = serde_json::json!({
+        "a": "line 1\n  line 2\n\n\"\"\"quoted\"\"\"\n",
+        "b": ["x\ny", "short"],
+    });
+
+    let jsonh: String = JsonhWriter::write_to_string(&element, JsonhWriterOptions::new().with_multi_quoted_strings(Some(0)));
+    assert_eq!(jsonh, r#"{
+    "a": """"
+        line 1
+          line 2
+
+        """quoted"""
+
+        """",
+    "b": [
+        """
+            x
+            y
+            """,
+        "short"
+    ]
+}"#);
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), element);
+
+    let jsonh: String = JsonhWriter::write_to_string(&element, JsonhWriterOptions::new().with_multi_quoted_strings(Some(5)));
+    assert!(jsonh.contains(r#""x\ny""#));
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), element);
+}
+#[test]
+pub fn write_comment_test() {
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new());
+    writer.write_comment("Generated file", JsonhCommentStyle::Hash).unwrap();
+    writer.write_start_object().unwrap();
+    writer.write_comment("The port to listen on.\nDefaults to 80.", JsonhCommentStyle::Line).unwrap();
+    writer.write_property_name("port").unwrap();
+    writer.write_comment("number", JsonhCommentStyle::Block).unwrap();
+    writer.write_number(&serde_json::Number::from(80)).unwrap();
+    writer.write_comment("trailing", JsonhCommentStyle::Block).unwrap();
+    writer.write_end_object().unwrap();
+    writer.write_comment("end", JsonhCommentStyle::Line).unwrap();
+
+    let jsonh: String = String::from_utf8(writer.writer).unwrap();
+    assert_eq!(jsonh, r#"# Generated file
+{
+    // The port to listen on.
+    // Defaults to 80.
+    "port": /* number */ 80,
+    /* trailing */
+}
+// end"#);
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), serde_json::json!({ "port": 80.0 }));
+
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new().with_braceless_root(true));
+    writer.write_start_object().unwrap();
+    writer.write_comment("first", JsonhCommentStyle::Line).unwrap();
+    writer.write_property_name("a").unwrap();
+    assert_eq!(writer.write_comment("a", JsonhCommentStyle::Line), Err("Expected block comment between property name and value"));
+    writer.write_null().unwrap();
+    assert_eq!(writer.write_comment("*/", JsonhCommentStyle::Block), Err("Block comment cannot contain `*/`"));
+    writer.write_end_object().unwrap();
+    assert_eq!(String::from_utf8(writer.writer).unwrap(), "// first\n\"a\": null");
+}
+#[test]
+pub fn write_radix_test() {
+    let element: Value = serde_json::json!({
+        "address": 7936,
+        "flags": [10, 5.0, -3],
+        "mode": 0.5,
+        "size": 8,
+    });
+
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new().with_braceless_root(true))
+        .with_radix_hint("address", 16)
+        .with_radix_hint("flags[1]", 2)
+        .with_radix_hint("flags[2]", 8)
+        .with_radix_hint("mode", 16);
+    writer.write_value(&element).unwrap();
+    writer.write_comment("", JsonhCommentStyle::Line).unwrap();
+
+    let jsonh: String = String::from_utf8(writer.writer).unwrap();
+    assert_eq!(jsonh, r#""address": 0x1F00,
+"flags": [
+    10,
+    0b101,
+    -0o3
+],
+"mode": 0.5,
+"size": 8
+//"#);
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), serde_json::json!({ "address": 7936.0, "flags": [10.0, 5.0, -3.0], "mode": 0.5, "size": 8.0 }));
+
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new());
+    assert_eq!(writer.write_number_in_radix(&serde_json::Number::from(1), 3), Err("Radix must be 2, 8, 10 or 16"));
+    writer.write_number_in_radix(&serde_json::Number::from(255), 16).unwrap();
+    assert_eq!(String::from_utf8(writer.writer).unwrap(), "0xFF");
+
+    // Property names containing path symbols
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new()).with_radix_hint(r"a\.b", 16);
+    writer.write_value(&serde_json::json!({ "a.b": 255, "a": { "b": 255 } })).unwrap();
+    assert_eq!(String::from_utf8(writer.writer).unwrap(), "{\n    \"a\": {\n        \"b\": 255\n    },\n    \"a.b\": 0xFF\n}");
+}
+#[test]
+pub fn write_digit_grouping_test() {
+    let element: Value = serde_json::json!([1000000, -1234.5678, 999, 1.5e300, 7936, 10]);
+
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), JsonhWriterOptions::new().with_digit_grouping(true))
+        .with_radix_hint("[4]", 16)
+        .with_radix_hint("[5]", 2);
+    writer.write_value(&element).unwrap();
+
+    let jsonh: String = String::from_utf8(writer.writer).unwrap();
+    assert_eq!(jsonh, r#"[
+    1_000_000,
+    -1_234.5678,
+    999,
+    1.5e+300,
+    0x1F00,
+    0b1010
+]"#);
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), serde_json::json!([1000000.0, -1234.5678, 999.0, 1.5e300, 7936.0, 10.0]));
+
+    assert_eq!(JsonhWriter::write_to_string(&serde_json::json!(-123456789), JsonhWriterOptions::new().with_digit_grouping(true)), "-123_456_789");
+}
+#[test]
+pub fn write_sort_keys_test() {
+    let element: Value = JsonhReader::parse_element_from_str("{ c: 1, bb: { z: 2, a: 3 }, a: [{ y: 4, x: 5 }] }", JsonhReaderOptions::new()).unwrap();
+    let options: JsonhWriterOptions = JsonhWriterOptions::new().with_indent_width(0).with_newline(" ").with_quoteless_strings(true);
+
+    assert_eq!(JsonhWriter::write_to_string(&element, options.with_sort_keys(true)), "{ a: [ { x: 5.0, y: 4.0 } ], bb: { a: 3.0, z: 2.0 }, c: 1.0 }");
+
+    let mut writer: JsonhWriter<Vec<u8>> = JsonhWriter::new(Vec::new(), options);
+    writer.write_value_sorted_by(&element, |name1, name2| name2.len().cmp(&name1.len())).unwrap();
+    assert_eq!(String::from_utf8(writer.writer).unwrap(), "{ bb: { a: 3.0, z: 2.0 }, a: [ { x: 5.0, y: 4.0 } ], c: 1.0 }");
+}
+#[test]
+pub fn write_escape_style_test() {
+    let element: Value = serde_json::json!({ "café": ["a\"b\\c\nd\te", "😀 ü", "plain"] });
+
+    let options: JsonhWriterOptions = JsonhWriterOptions::new().with_indent_width(0).with_newline(" ");
+    let jsonh: String = JsonhWriter::write_to_string(&element, options.with_escape_style(JsonhEscapeStyle::Ascii).with_quoteless_strings(true));
+    assert_eq!(jsonh, r#"{ "caf\u00e9": [ "a\"b\\c\nd\te", "\U0001f600 \u00fc", plain ] }"#);
+    assert!(jsonh.is_ascii());
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), element);
+
+    let jsonh: String = JsonhWriter::write_to_string(&element, options.with_escape_style(JsonhEscapeStyle::Minimal));
+    assert_eq!(jsonh, "{ \"café\": [ \"a\\\"b\\\\c\nd\te\", \"😀 ü\", \"plain\" ] }");
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), element);
+
+    let multi_quoted: Value = serde_json::json!("é\n😀");
+    let jsonh: String = JsonhWriter::write_to_string(&multi_quoted, JsonhWriterOptions::new().with_multi_quoted_strings(Some(0)).with_escape_style(JsonhEscapeStyle::Ascii));
+    assert!(jsonh.is_ascii());
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), multi_quoted);
+}
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Point(i32, i32),
+    Rectangle { width: u32, height: u32 },
+}
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Drawing {
+    name: String,
+    #[serde(with = "jsonh_rs::radix::hex")]
+    color: u32,
+    shapes: Vec<Shape>,
+    parent: Option<String>,
+}
+
+#[test]
+pub fn serialize_test() {
+    let drawing: Drawing = Drawing {
+        name: "logo".to_string(),
+        color: 0xFF8800,
+        shapes: vec![Shape::Empty, Shape::Circle(1.5), Shape::Point(-1, 2), Shape::Rectangle { width: 3, height: 4 }],
+        parent: None,
+    };
+
+    assert_eq!(jsonh_rs::to_string(&drawing).unwrap(), r#"{"name":"logo","color":0xFF8800,"shapes":["Empty",{"Circle":1.5},{"Point":[-1,2]},{"Rectangle":{"width":3,"height":4}}],"parent":null}"#);
+    assert_eq!(jsonh_rs::to_string_pretty(&drawing).unwrap(), r#"{
+    "name": "logo",
+    "color": 0xFF8800,
+    "shapes": [
+        "Empty",
+        {
+            "Circle": 1.5
+        },
+        {
+            "Point": [
+                -1,
+                2
+            ]
+        },
+        {
+            "Rectangle": {
+                "width": 3,
+                "height": 4
+            }
+        }
+    ],
+    "parent": null
+}"#);
+    let jsonh: String = jsonh_rs::to_string_with_options(&drawing, JsonhWriterOptions::new().with_braceless_root(true).with_quoteless_strings(true)).unwrap();
+    assert_eq!(JsonhReader::parse_element_from_str(&jsonh, JsonhReaderOptions::new()).unwrap(), JsonhReader::parse_element_from_str(&jsonh_rs::to_string(&drawing).unwrap(), JsonhReaderOptions::new()).unwrap());
+
+    let mut map: std::collections::BTreeMap<u32, bool> = std::collections::BTreeMap::new();
+    map.insert(1, true);
+    assert_eq!(jsonh_rs::to_string(&map).unwrap(), r#"{"1":true}"#);
+    assert!(jsonh_rs::to_string(&f64::NAN).is_err());
+    assert!(jsonh_rs::to_string(&std::collections::HashMap::from([((), 1)])).is_err());
+}
+
+struct FailingWriter;
+
+impl std::io::Write for FailingWriter {
+    fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+        return Err(std::io::Error::other("disk full"));
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        return Ok(());
+    }
+}
+
+#[test]
+pub fn error_source_test() {
+    use std::error::Error;
+
+    let error: JsonhError = jsonh_rs::to_writer(FailingWriter, &[1, 2], JsonhWriterOptions::new()).unwrap_err();
+    assert_eq!(error.to_string(), "Failed to write");
+    assert_eq!(error.source().unwrap().to_string(), "disk full");
+    assert_eq!(error.clone(), error);
+
+    let boxed: Box<dyn Error + Send + Sync> = Box::new(error);
+    assert_eq!(boxed.source().map(|source| source.to_string()), Some("disk full".to_string()));
+
+    let parse = || -> Result<Value, Box<dyn Error>> {
+        let element: Value = jsonh_rs::from_str("[1, 2")?;
+        return Ok(element);
+    };
+    assert!(parse().unwrap_err().source().is_none());
+}
\ No newline at end of file